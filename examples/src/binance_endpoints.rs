@@ -131,7 +131,7 @@ fn market_data() {
     }
 
     // last 10 5min klines (candlesticks) for a symbol:
-    match market.get_klines("BNBETH", "5m", 10) {
+    match market.get_klines("BNBETH", "5m", 10, None, None) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
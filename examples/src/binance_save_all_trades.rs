@@ -2,7 +2,6 @@ extern crate csv;
 extern crate binance;
 
 use std::error::Error;
-use std::cell::RefCell;
 use std::fs::File;
 use csv::Writer;
 
@@ -16,26 +15,25 @@ fn main() {
 fn save_all_trades_websocket() { 
 
     struct WebSocketHandler {
-        wrt: RefCell<Writer<File>>
+        wrt: Writer<File>
     };
 
     impl WebSocketHandler {
         pub fn new(local_wrt: Writer<File>) -> Self {
             WebSocketHandler {
-                wrt: RefCell::new(local_wrt)
+                wrt: local_wrt
             }
         }
 
         // serialize DayTickerEvent as CSV records
-        pub fn write_to_file(&self, event: DayTickerEvent) -> Result<(), Box<Error>> {
-            let mut local_wrt = self.wrt.borrow_mut();
-            local_wrt.serialize(event)?;
+        pub fn write_to_file(&mut self, event: DayTickerEvent) -> Result<(), Box<Error>> {
+            self.wrt.serialize(event)?;
             Ok(())
         }
     }
 
     impl DayTickerEventHandler for WebSocketHandler {
-        fn day_ticker_handler(&self, events: &[DayTickerEvent]) {
+        fn day_ticker_handler(&mut self, events: &[DayTickerEvent]) {
             for event in events {
                 if let Err(error) = self.write_to_file(event.clone()) {
                     println!("{}", error);
@@ -53,5 +51,7 @@ fn save_all_trades_websocket() {
 
     web_socket.add_day_ticker_handler(web_socket_handler);
     web_socket.connect(&agg_trade).unwrap(); // check error
-    web_socket.event_loop();
+    if let Err(e) = web_socket.event_loop() {
+        println!("Error: {}", e);
+    }
 }
\ No newline at end of file
@@ -2,8 +2,9 @@ extern crate binance;
 
 use binance::api::*;
 use binance::userstream::*;
+use binance::streams;
 use binance::websockets::*;
-use binance::model::{AccountUpdateEvent, KlineEvent, OrderTradeEvent,
+use binance::model::{AccountUpdateEvent, KlineEvent, KlineInterval, OrderTradeEvent,
                      TradesEvent, DayTickerEvent, OrderBook, DepthOrderBookEvent};
 
 fn main() {
@@ -40,7 +41,7 @@ fn user_stream_websocket() {
     struct WebSocketHandler;
 
     impl UserStreamEventHandler for WebSocketHandler {
-        fn account_update_handler(&self, event: &AccountUpdateEvent) {
+        fn account_update_handler(&mut self, event: &AccountUpdateEvent) {
             for balance in &event.balance {
                 println!(
                     "Asset: {}, free: {}, locked: {}",
@@ -49,7 +50,7 @@ fn user_stream_websocket() {
             }
         }
 
-        fn order_trade_handler(&self, event: &OrderTradeEvent) {
+        fn order_trade_handler(&mut self, event: &OrderTradeEvent) {
             println!(
                 "Symbol: {}, Side: {}, Price: {}, Execution Type: {}",
                 event.symbol, event.side, event.price, event.execution_type
@@ -66,7 +67,9 @@ fn user_stream_websocket() {
         let mut web_socket: WebSockets = WebSockets::new();
         web_socket.add_user_stream_handler(WebSocketHandler);
         web_socket.connect(&listen_key).unwrap(); // check error
-        web_socket.event_loop();
+        if let Err(e) = web_socket.event_loop() {
+            println!("Error: {}", e);
+        }
     } else {
         println!("Not able to start an User Stream (Check your API_KEY)");
     }
@@ -76,21 +79,21 @@ fn market_websocket() {
     struct WebSocketHandler;
 
     impl MarketEventHandler for WebSocketHandler {
-        fn aggregated_trades_handler(&self, event: &TradesEvent) {
+        fn aggregated_trades_handler(&mut self, event: &TradesEvent) {
             println!(
                 "Symbol: {}, price: {}, qty: {}",
                 event.symbol, event.price, event.qty
             );
         }
 
-        fn depth_orderbook_handler(&self, event: &DepthOrderBookEvent) {
+        fn depth_orderbook_handler(&mut self, event: &DepthOrderBookEvent) {
             println!(
                 "Symbol: {}, Bids: {:?}, Ask: {:?}",
                 event.symbol, event.bids, event.asks
             );
         }
 
-        fn partial_orderbook_handler(&self, order_book: &OrderBook) {
+        fn partial_orderbook_handler(&mut self, order_book: &OrderBook) {
             println!(
                 "last_update_id: {}, Bids: {:?}, Ask: {:?}",
                 order_book.last_update_id, order_book.bids, order_book.asks
@@ -103,14 +106,16 @@ fn market_websocket() {
 
     web_socket.add_market_handler(WebSocketHandler);
     web_socket.connect(&agg_trade).unwrap(); // check error
-    web_socket.event_loop();
+    if let Err(e) = web_socket.event_loop() {
+        println!("Error: {}", e);
+    }
 }
 
 fn all_trades_websocket() {
     struct WebSocketHandler;
 
     impl DayTickerEventHandler for WebSocketHandler {
-        fn day_ticker_handler(&self, events: &[DayTickerEvent]) {
+        fn day_ticker_handler(&mut self, events: &[DayTickerEvent]) {
             for event in events {
                 println!(
                     "Symbol: {}, price: {}, qty: {}",
@@ -125,14 +130,16 @@ fn all_trades_websocket() {
 
     web_socket.add_day_ticker_handler(WebSocketHandler);
     web_socket.connect(&agg_trade).unwrap(); // check error
-    web_socket.event_loop();
+    if let Err(e) = web_socket.event_loop() {
+        println!("Error: {}", e);
+    }
 }
 
 fn kline_websocket() {
     struct WebSocketHandler;
 
     impl KlineEventHandler for WebSocketHandler {
-        fn kline_handler(&self, event: &KlineEvent) {
+        fn kline_handler(&mut self, event: &KlineEvent) {
             println!(
                 "Symbol: {}, high: {}, low: {}",
                 event.kline.symbol, event.kline.low, event.kline.high
@@ -140,10 +147,12 @@ fn kline_websocket() {
         }
     }
 
-    let kline: String = format!("{}", "ethbtc@kline_1m");
+    let kline: String = streams::kline("ETHBTC", KlineInterval::Minutes1).unwrap(); // check error
     let mut web_socket: WebSockets = WebSockets::new();
 
     web_socket.add_kline_handler(WebSocketHandler);
     web_socket.connect(&kline).unwrap(); // check error
-    web_socket.event_loop();
+    if let Err(e) = web_socket.event_loop() {
+        println!("Error: {}", e);
+    }
 }
@@ -2,44 +2,174 @@ use util::*;
 use model::*;
 use client::*;
 use errors::*;
-use std::collections::BTreeMap;
+use filters::*;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use serde_json::from_str;
 
-static ORDER_TYPE_LIMIT: &'static str = "LIMIT";
-static ORDER_TYPE_MARKET: &'static str = "MARKET";
-static ORDER_SIDE_BUY: &'static str = "BUY";
-static ORDER_SIDE_SELL: &'static str = "SELL";
-static TIME_IN_FORCE_GTC: &'static str = "GTC";
+
+// "Unknown order sent.", also what canceling without open orders answers
+const UNKNOWN_ORDER: i64 = -2011;
 
 static API_V3_ORDER: &'static str = "/api/v3/order";
+static API_V3_ORDER_TEST: &'static str = "/api/v3/order/test";
+static API_V3_ORDER_CANCEL_REPLACE: &'static str = "/api/v3/order/cancelReplace";
+static API_V3_ORDER_OCO: &'static str = "/api/v3/order/oco";
+static API_V3_ORDER_LIST: &'static str = "/api/v3/orderList";
+static API_V3_OPEN_ORDERS: &'static str = "/api/v3/openOrders";
+static API_V3_ALL_ORDERS: &'static str = "/api/v3/allOrders";
+static ALL_ORDERS_MAX_LIMIT: u16 = 1000;
+static API_V3_MY_TRADES: &'static str = "/api/v3/myTrades";
+static MY_TRADES_MAX_LIMIT: u16 = 1000;
+static API_V3_RATE_LIMIT_ORDER: &'static str = "/api/v3/rateLimit/order";
+static API_V3_ACCOUNT_COMMISSION: &'static str = "/api/v3/account/commission";
+static API_V3_EXCHANGE_INFO: &'static str = "/api/v3/exchangeInfo";
+pub static DEFAULT_FILTERS_MAX_AGE_SECS: u64 = 3600;
+static API_V3_OPEN_ORDER_LIST: &'static str = "/api/v3/openOrderList";
 
 #[derive(Clone)]
 pub struct Account {
     pub client: Client,
     pub recv_window: u64,
+    // newOrderRespType of placed orders, None leaves Binance's default (FULL for LIMIT and MARKET)
+    pub order_response_type: Option<OrderResponseType>,
+    // selfTradePreventionMode of placed orders, None leaves the default of the symbol
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    // How long place_order_validated trusts the exchange filters it fetched
+    pub filters_max_age: Duration,
+    // Exchange filters per symbol and when they were fetched, shared by the clones
+    pub(crate) filters: Arc<Mutex<HashMap<String, (Instant, SymbolFilters)>>>,
+}
+
+// Filters of get_all_orders, the default gives the latest 500 orders
+#[derive(Debug, Clone, Default)]
+pub struct OrderHistoryOptions {
+    // Orders from this id onward
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // At most 1000
+    pub limit: Option<u16>,
+}
+
+// Filters of get_my_trades. To page through all trades, pass the last id + 1 as from_id
+#[derive(Debug, Clone, Default)]
+pub struct TradeHistoryOptions {
+    pub from_id: Option<u64>,
+    // Trades of this order only
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // At most 1000, 500 by default
+    pub limit: Option<u16>,
+}
+
+// An order to cancel, by Binance's id or by ours
+#[derive(Debug, Clone)]
+pub enum OrderIdentifier {
+    OrderId(u64),
+    ClientOrderId(String),
+}
+
+// The order placed by cancel_replace_order, price and time_in_force are for LIMIT orders only
+#[derive(Debug, Clone)]
+pub struct ReplacementOrder {
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub qty: Amount,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
+    pub time_in_force: Option<TimeInForce>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl ReplacementOrder {
+    pub fn limit(side: OrderSide, qty: Amount, price: Amount) -> ReplacementOrder {
+        ReplacementOrder {
+            side,
+            order_type: OrderType::Limit,
+            qty,
+            price: Some(price),
+            stop_price: None,
+            time_in_force: Some(TimeInForce::GoodTillCanceled),
+            new_client_order_id: None,
+        }
+    }
+
+    pub fn market(side: OrderSide, qty: Amount) -> ReplacementOrder {
+        ReplacementOrder {
+            side,
+            order_type: OrderType::Market,
+            qty,
+            price: None,
+            stop_price: None,
+            time_in_force: None,
+            new_client_order_id: None,
+        }
+    }
 }
 
-struct OrderRequest {
-    pub symbol: String,
-    pub qty: f64,
-    pub price: f64,
-    pub order_side: String,
-    pub order_type: String,
-    pub time_in_force: String
+// Answer of a cancel-replace that failed at least partly, data is missing when the
+// request itself was invalid
+#[derive(Deserialize)]
+struct CancelReplaceFailure {
+    code: i64,
+    msg: String,
+    data: Option<CancelReplace>,
+}
+
+// An order for Account::send or Account::test, checked before it is sent, e.g.
+// OrderRequest::limit_buy("BTCUSDT", 0.5, 42000.0).time_in_force(TimeInForce::ImmediateOrCancel)
+#[derive(Debug, Clone)]
+pub struct OrderRequest {
+    symbol: String,
+    side: OrderSide,
+    order_type: OrderType,
+    qty: Option<Amount>,
+    quote_order_qty: Option<Amount>,
+    price: Option<Amount>,
+    stop_price: Option<Amount>,
+    time_in_force: Option<TimeInForce>,
+    new_client_order_id: Option<String>,
+    iceberg_qty: Option<Amount>,
+    response_type: Option<OrderResponseType>,
+    self_trade_prevention_mode: Option<SelfTradePreventionMode>,
 }
 
 impl Account {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Copy with another recvWindow for a single call, e.g. account.with_recv_window(1000)?.limit_buy(..)
+    pub fn with_recv_window(&self, recv_window: u64) -> Result<(Account)> {
+        let mut copy = self.clone();
+        copy.set_recv_window(recv_window)?;
+
+        Ok(copy)
+    }
+
     // Account Information
     pub fn get_account(&self) -> Result<(AccountInformation)> {
         let parameters: BTreeMap<String, String> = BTreeMap::new();
 
         let request = build_signed_request(parameters, self.recv_window)?;
         let data = self.client.get_signed("/api/v3/account", &request)?;
-        let account_info: AccountInformation = from_str(data.as_str())?;
+        let account_info: AccountInformation = from_json(&data)?;
 
         Ok(account_info)
     }
 
+    // get_account with the usage headers and server time of the response
+    pub fn get_account_with_meta(&self) -> Result<(ApiResponse<AccountInformation>)> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let answer = self.client.get_signed_with_meta("/api/v3/account", &request)?;
+
+        answer.and_then(|data| from_json(&data))
+    }
+
     // Balance for ONE Asset
     pub fn get_balance<S>(&self, asset: S) -> Result<(Balance)>
         where S: Into<String>
@@ -52,13 +182,39 @@ impl Account {
                         return Ok(balance);
                     }
                 }
-                bail!("Asset not found");
+                bail!(BinanceError::Parameter("Asset not found".into()));
             }
             Err(e) => Err(e),
         }
     }
 
-    // Current open orders for ONE symbol
+    // Commission rates of our account for ONE symbol, including the BNB discount
+    pub fn get_commission_rates<S>(&self, symbol: S) -> Result<(CommissionRates)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_ACCOUNT_COMMISSION, &request)?;
+        let rates: CommissionRates = from_json(&data)?;
+
+        Ok(rates)
+    }
+
+    // Orders placed in the current intervals of the order rate limits (per 10 seconds and per day).
+    // Order placement is locked once a count reaches its limit
+    pub fn get_order_rate_limits(&self) -> Result<(Vec<RateLimitUsage>)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_RATE_LIMIT_ORDER, &request)?;
+        let rate_limits: Vec<RateLimitUsage> = from_json(&data)?;
+
+        Ok(rate_limits)
+    }
+
+    // Current open orders for ONE symbol, weight 3
     pub fn get_open_orders<S>(&self, symbol: S) -> Result<(Vec<Order>)>
         where S: Into<String>
     {
@@ -66,23 +222,72 @@ impl Account {
         parameters.insert("symbol".into(), symbol.into());
 
         let request = build_signed_request(parameters, self.recv_window)?;
-        let data = self.client.get_signed("/api/v3/openOrders", &request)?;
-        let order: Vec<Order> = from_str(data.as_str())?;
+        let data = self.client.get_signed(API_V3_OPEN_ORDERS, &request)?;
+        let order: Vec<Order> = from_json(&data)?;
 
         Ok(order)
     }
 
-    // All current open orders
+    // Current open orders of every symbol. Weight 40 instead of 3, prefer get_open_orders
+    // when the symbols are known
     pub fn get_all_open_orders(&self) -> Result<(Vec<Order>)> {
         let parameters: BTreeMap<String, String> = BTreeMap::new();
 
         let request = build_signed_request(parameters, self.recv_window)?;
-        let data = self.client.get_signed("/api/v3/openOrders", &request)?;
-        let order: Vec<Order> = from_str(data.as_str())?;
+        let data = self.client.get_signed(API_V3_OPEN_ORDERS, &request)?;
+        let order: Vec<Order> = from_json(&data)?;
 
         Ok(order)
     }
 
+    // All orders of ONE symbol, open, canceled or filled
+    pub fn get_all_orders<S>(&self, symbol: S, options: OrderHistoryOptions) -> Result<(Vec<Order>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(order_id) = options.order_id {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(start_time) = options.start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = options.end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = options.limit {
+            if limit == 0 || limit > ALL_ORDERS_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, ALL_ORDERS_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_ALL_ORDERS, &request)?;
+        let orders: Vec<Order> = from_json(&data)?;
+
+        Ok(orders)
+    }
+
+    // Every order of ONE symbol from order id from on (the first order when None), oldest first.
+    // Pages are requested as the iterator advances, a failed request is the last item
+    pub fn iter_all_orders<S>(&self, symbol: S, from: Option<u64>) -> HistoryIter<'_, Order>
+        where S: Into<String>
+    {
+        HistoryIter::new(self, symbol.into(), from, ALL_ORDERS_MAX_LIMIT, fetch_orders, |order| order.order_id)
+    }
+
+    // Every trade of ONE symbol from trade id from on, oldest first
+    pub fn iter_my_trades<S>(&self, symbol: S, from: Option<u64>) -> HistoryIter<'_, TradeHistory>
+        where S: Into<String>
+    {
+        HistoryIter::new(self, symbol.into(), from, MY_TRADES_MAX_LIMIT, fetch_trades, |trade| trade.id)
+    }
+
     // Check an order's status
     pub fn order_status<S>(&self, symbol: S, order_id: u64) -> Result<(Order)>
         where S: Into<String>
@@ -93,91 +298,565 @@ impl Account {
 
         let request = build_signed_request(parameters, self.recv_window)?;
         let data = self.client.get_signed(API_V3_ORDER, &request)?;
-        let order: Order = from_str(data.as_str())?;
+        let order: Order = from_json(&data)?;
 
         Ok(order)
     }
 
     // Place a LIMIT order - BUY
-    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<(Transaction)>
-        where S: Into<String>, F: Into<f64>
+    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
     {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: price,
-            order_side: ORDER_SIDE_BUY.to_string(),
-            order_type: ORDER_TYPE_LIMIT.to_string(),
-            time_in_force: TIME_IN_FORCE_GTC.to_string()
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        let data = self.client.post_signed(API_V3_ORDER, &request)?;
-        let transaction: Transaction = from_str(data.as_str())?;
-
-        Ok(transaction)
+        self.send(OrderRequest::limit_buy(symbol, qty.into(), price))
     }
 
     // Place a LIMIT order - SELL
-    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<(Transaction)>
-        where S: Into<String>, F: Into<f64>
+    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
     {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: price,
-            order_side: ORDER_SIDE_SELL.to_string(),
-            order_type: ORDER_TYPE_LIMIT.to_string(),
-            time_in_force: TIME_IN_FORCE_GTC.to_string()
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        let data = self.client.post_signed(API_V3_ORDER, &request)?;
-        let transaction: Transaction = from_str(data.as_str())?;
-
-        Ok(transaction)
+        self.send(OrderRequest::limit_sell(symbol, qty.into(), price))
     }
 
     // Place a MARKET order - BUY
     pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<(Transaction)>
-        where S: Into<String>, F: Into<f64>
+        where S: Into<String>, F: Into<Amount>
     {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            order_side: ORDER_SIDE_BUY.to_string(),
-            order_type: ORDER_TYPE_MARKET.to_string(),
-            time_in_force: TIME_IN_FORCE_GTC.to_string()
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        let data = self.client.post_signed(API_V3_ORDER, &request)?;
-        let transaction: Transaction = from_str(data.as_str())?;
-
-        Ok(transaction)
+        self.send(OrderRequest::market_buy(symbol, qty.into()))
     }
 
     // Place a MARKET order - SELL
     pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<(Transaction)>
-        where S: Into<String>, F: Into<f64>
+        where S: Into<String>, F: Into<Amount>
     {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            order_side: ORDER_SIDE_SELL.to_string(),
-            order_type: ORDER_TYPE_MARKET.to_string(),
-            time_in_force: TIME_IN_FORCE_GTC.to_string()
-        };
-        let order = self.build_order(sell);
+        self.send(OrderRequest::market_sell(symbol, qty.into()))
+    }
+
+    // Place a LIMIT order - BUY with our own client order id, a retry with the same id
+    // cannot place the order twice
+    pub fn limit_buy_with_client_id<S, F, C>(
+        &self, symbol: S, qty: F, price: Amount, client_order_id: C,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>, C: Into<String>
+    {
+        self.send(OrderRequest::limit_buy(symbol, qty.into(), price).client_order_id(client_order_id))
+    }
+
+    // Place a LIMIT order - SELL with our own client order id, a retry with the same id
+    // cannot place the order twice
+    pub fn limit_sell_with_client_id<S, F, C>(
+        &self, symbol: S, qty: F, price: Amount, client_order_id: C,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>, C: Into<String>
+    {
+        self.send(OrderRequest::limit_sell(symbol, qty.into(), price).client_order_id(client_order_id))
+    }
+
+    // Place a MARKET order - BUY with our own client order id, a retry with the same id
+    // cannot place the order twice
+    pub fn market_buy_with_client_id<S, F, C>(&self, symbol: S, qty: F, client_order_id: C) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>, C: Into<String>
+    {
+        self.send(OrderRequest::market_buy(symbol, qty.into()).client_order_id(client_order_id))
+    }
+
+    // Place a MARKET order - SELL with our own client order id, a retry with the same id
+    // cannot place the order twice
+    pub fn market_sell_with_client_id<S, F, C>(&self, symbol: S, qty: F, client_order_id: C) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>, C: Into<String>
+    {
+        self.send(OrderRequest::market_sell(symbol, qty.into()).client_order_id(client_order_id))
+    }
+
+    // Check an order's status by the client order id it was placed with
+    pub fn order_status_by_client_id<S1, S2>(&self, symbol: S1, orig_client_order_id: S2) -> Result<(Order)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_ORDER, &request)?;
+        let order: Order = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    pub fn cancel_order_by_client_id<S1, S2>(&self, symbol: S1, orig_client_order_id: S2) -> Result<(OrderCanceled)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(API_V3_ORDER, &request)?;
+        let order_canceled: OrderCanceled = from_json(&data)?;
+
+        Ok(order_canceled)
+    }
+
+    // Place a LIMIT order - BUY, e.g. TimeInForce::ImmediateOrCancel to never leave a resting order
+    pub fn limit_buy_with_tif<S, F>(
+        &self, symbol: S, qty: F, price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_buy(symbol, qty.into(), price).time_in_force(time_in_force))
+    }
+
+    // Place a LIMIT order - SELL with the given time in force
+    pub fn limit_sell_with_tif<S, F>(
+        &self, symbol: S, qty: F, price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_sell(symbol, qty.into(), price).time_in_force(time_in_force))
+    }
+
+    // Place a LIMIT order - BUY showing only iceberg_qty of qty on the book
+    pub fn limit_buy_iceberg<S, F>(
+        &self, symbol: S, qty: F, price: Amount, iceberg_qty: Amount,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_buy(symbol, qty.into(), price).iceberg_qty(iceberg_qty))
+    }
+
+    // Place a LIMIT order - SELL showing only iceberg_qty of qty on the book
+    pub fn limit_sell_iceberg<S, F>(
+        &self, symbol: S, qty: F, price: Amount, iceberg_qty: Amount,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_sell(symbol, qty.into(), price).iceberg_qty(iceberg_qty))
+    }
+
+    // Place a MARKET order - BUY for quote_qty of the quote asset, e.g. 100 USDT worth of BTC.
+    // quote_qty is sent as is, round it to the quote_precision of the symbol (General::exchange_info).
+    // executed_qty and cummulative_quote_qty of the answer tell what was bought
+    pub fn market_buy_using_quote_quantity<S, F>(&self, symbol: S, quote_qty: F) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Buy, OrderType::Market).quote_order_qty(quote_qty.into());
+
+        self.send(order)
+    }
+
+    // Place a MARKET order - SELL for quote_qty of the quote asset
+    pub fn market_sell_using_quote_quantity<S, F>(&self, symbol: S, quote_qty: F) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Sell, OrderType::Market).quote_order_qty(quote_qty.into());
+
+        self.send(order)
+    }
+
+    // Test a LIMIT order - BUY, validated by Binance like limit_buy but never placed
+    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.test(OrderRequest::limit_buy(symbol, qty.into(), price))
+    }
+
+    // Test a LIMIT order - SELL
+    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.test(OrderRequest::limit_sell(symbol, qty.into(), price))
+    }
+
+    // Test a MARKET order - BUY
+    pub fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.test(OrderRequest::market_buy(symbol, qty.into()))
+    }
+
+    // Test a MARKET order - SELL
+    pub fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.test(OrderRequest::market_sell(symbol, qty.into()))
+    }
+
+    // Place a STOP_LOSS_LIMIT order - BUY, a LIMIT order at price once stop_price is reached
+    pub fn stop_limit_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Buy, OrderType::StopLossLimit)
+            .qty(qty.into())
+            .price(price)
+            .stop_price(stop_price)
+            .time_in_force(time_in_force);
+
+        self.send(order)
+    }
+
+    // Place a STOP_LOSS_LIMIT order - SELL
+    pub fn stop_limit_sell_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Sell, OrderType::StopLossLimit)
+            .qty(qty.into())
+            .price(price)
+            .stop_price(stop_price)
+            .time_in_force(time_in_force);
+
+        self.send(order)
+    }
+
+    // Place a TAKE_PROFIT_LIMIT order - BUY
+    pub fn take_profit_limit_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Buy, OrderType::TakeProfitLimit)
+            .qty(qty.into())
+            .price(price)
+            .stop_price(stop_price)
+            .time_in_force(time_in_force);
+
+        self.send(order)
+    }
+
+    // Place a TAKE_PROFIT_LIMIT order - SELL
+    pub fn take_profit_limit_sell_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Sell, OrderType::TakeProfitLimit)
+            .qty(qty.into())
+            .price(price)
+            .stop_price(stop_price)
+            .time_in_force(time_in_force);
+
+        self.send(order)
+    }
+
+    // Place a STOP_LOSS order - BUY, a MARKET order once stop_price is reached
+    pub fn stop_loss_buy_order<S, F>(&self, symbol: S, qty: F, stop_price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Buy, OrderType::StopLoss)
+            .qty(qty.into())
+            .stop_price(stop_price);
+
+        self.send(order)
+    }
+
+    // Place a STOP_LOSS order - SELL
+    pub fn stop_loss_sell_order<S, F>(&self, symbol: S, qty: F, stop_price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Sell, OrderType::StopLoss)
+            .qty(qty.into())
+            .stop_price(stop_price);
+
+        self.send(order)
+    }
+
+    // Place a TAKE_PROFIT order - BUY
+    pub fn take_profit_buy_order<S, F>(&self, symbol: S, qty: F, stop_price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Buy, OrderType::TakeProfit)
+            .qty(qty.into())
+            .stop_price(stop_price);
+
+        self.send(order)
+    }
+
+    // Place a TAKE_PROFIT order - SELL
+    pub fn take_profit_sell_order<S, F>(&self, symbol: S, qty: F, stop_price: Amount) -> Result<(Transaction)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        let order = OrderRequest::new(symbol, OrderSide::Sell, OrderType::TakeProfit)
+            .qty(qty.into())
+            .stop_price(stop_price);
+
+        self.send(order)
+    }
+
+    // Place an order built with OrderRequest
+    pub fn send(&self, order: OrderRequest) -> Result<(Transaction)> {
+        let order = self.build_order(order)?;
         let request = build_signed_request(order, self.recv_window)?;
         let data = self.client.post_signed(API_V3_ORDER, &request)?;
-        let transaction: Transaction = from_str(data.as_str())?;
+        let transaction: Transaction = from_json(&data)?;
 
         Ok(transaction)
     }
 
+    // send with the order counts of the response, to pace orders on them
+    pub fn send_with_meta(&self, order: OrderRequest) -> Result<(ApiResponse<Transaction>)> {
+        let order = self.build_order(order)?;
+        let request = build_signed_request(order, self.recv_window)?;
+        let answer = self.client.post_signed_with_meta(API_V3_ORDER, &request)?;
+
+        answer.and_then(|data| from_json(&data))
+    }
+
+    // Like send, but checks price, quantity and notional against the exchange filters of the symbol
+    // first. A violation is BinanceError::Filter with the nearest valid value instead of Binance's -1013
+    pub fn place_order_validated(&self, order: OrderRequest) -> Result<(Transaction)> {
+        let filters = self.symbol_filters(&order.symbol)?;
+        let is_market = order.order_type == OrderType::Market;
+
+        if let Some(price) = order.price {
+            filters.check_price(price)?;
+        }
+        if let Some(stop_price) = order.stop_price {
+            filters.check_price(stop_price)?;
+        }
+        if let Some(qty) = order.qty {
+            filters.check_qty(qty, is_market)?;
+            // The notional of MARKET orders depends on the average price, left to Binance
+            if let Some(price) = order.price {
+                filters.check_notional(price * qty, is_market)?;
+            }
+        }
+
+        self.send(order)
+    }
+
+    // price snapped to the tick size and qty rounded down to the step size of the symbol
+    pub fn round_to_filters(&self, symbol: &str, price: Amount, qty: Amount) -> Result<((Amount, Amount))> {
+        let filters = self.symbol_filters(symbol)?;
+
+        Ok((filters.round_price(price), filters.round_qty(qty)))
+    }
+
+    // Exchange filters of symbol, fetched again once older than filters_max_age
+    pub fn symbol_filters(&self, symbol: &str) -> Result<(SymbolFilters)> {
+        if let Ok(cache) = self.filters.lock() {
+            if let Some(&(fetched, ref filters)) = cache.get(symbol) {
+                if fetched.elapsed() < self.filters_max_age {
+                    return Ok(filters.clone());
+                }
+            }
+        }
+
+        let data = self.client.get(API_V3_EXCHANGE_INFO, &format!("symbol={}", symbol))?;
+        let info: ExchangeInformation = from_json(&data)?;
+        let filters = match info.symbol_filters(symbol) {
+            Some(filters) => filters,
+            None => bail!(BinanceError::Parameter(format!("Symbol {} not found in exchange info", symbol))),
+        };
+
+        if let Ok(mut cache) = self.filters.lock() {
+            cache.insert(symbol.to_string(), (Instant::now(), filters.clone()));
+        }
+
+        Ok(filters)
+    }
+
+    // Fills the filter cache from exchange info fetched elsewhere, whether for all symbols, some
+    // symbols or some permissions, instead of one request per symbol
+    pub fn cache_symbol_filters(&self, info: &ExchangeInformation) {
+        if let Ok(mut cache) = self.filters.lock() {
+            for symbol in &info.symbols {
+                cache.insert(symbol.symbol.clone(), (Instant::now(), SymbolFilters::new(symbol)));
+            }
+        }
+    }
+
+    // Same signed payload as send, Binance answers {} when it would accept the order
+    pub fn test(&self, order: OrderRequest) -> Result<()> {
+        let order = self.build_order(order)?;
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API_V3_ORDER_TEST, &request)?;
+
+        Ok(())
+    }
+
+    // Cancels an order and places another one in the same request. A failed leg is reported
+    // in the answer instead of an error, check cancel_result and new_order_result
+    pub fn cancel_replace_order<S>(
+        &self, symbol: S, cancel: OrderIdentifier, order: ReplacementOrder, mode: CancelReplaceMode,
+    ) -> Result<(CancelReplace)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("cancelReplaceMode".into(), mode.as_str().into());
+        match cancel {
+            OrderIdentifier::OrderId(order_id) => {
+                parameters.insert("cancelOrderId".into(), order_id.to_string());
+            }
+            OrderIdentifier::ClientOrderId(client_order_id) => {
+                parameters.insert("cancelOrigClientOrderId".into(), client_order_id);
+            }
+        }
+
+        parameters.insert("side".into(), order.side.as_str().into());
+        parameters.insert("type".into(), order.order_type.as_str().into());
+        parameters.insert("quantity".into(), order.qty.to_string());
+        if let Some(price) = order.price {
+            parameters.insert("price".into(), price.to_string());
+        }
+        if let Some(stop_price) = order.stop_price {
+            parameters.insert("stopPrice".into(), stop_price.to_string());
+        }
+        if let Some(time_in_force) = order.time_in_force {
+            parameters.insert("timeInForce".into(), time_in_force.as_str().into());
+        }
+        if let Some(client_order_id) = order.new_client_order_id {
+            validate_client_order_id(&client_order_id)?;
+            parameters.insert("newClientOrderId".into(), client_order_id);
+        }
+        if let Some(response_type) = self.order_response_type {
+            parameters.insert("newOrderRespType".into(), response_type.as_str().into());
+        }
+        if let Some(ref mode) = self.self_trade_prevention_mode {
+            parameters.insert("selfTradePreventionMode".into(), mode.as_str().into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let (status, data) = self.client.post_signed_detailed(API_V3_ORDER_CANCEL_REPLACE, &request)?;
+
+        if let Ok(result) = from_str::<CancelReplace>(data.as_str()) {
+            return Ok(result);
+        }
+        match from_str::<CancelReplaceFailure>(data.as_str()) {
+            Ok(CancelReplaceFailure { data: Some(result), .. }) => Ok(result),
+            Ok(failure) => bail!(BinanceContentError {
+                status,
+                code: failure.code,
+                msg: failure.msg,
+            }),
+            Err(_) => bail!(BinanceError::Status { status, body: data }),
+        }
+    }
+
+    // Place an OCO - SELL: a LIMIT order at price above the market and a STOP_LOSS_LIMIT order
+    // at stop_limit_price, triggered at stop_price below it
+    pub fn place_oco_sell<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, stop_limit_price: Amount,
+    ) -> Result<(OrderList)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        if price <= stop_price {
+            bail!(BinanceError::Parameter(format!(
+                "OCO sell price {} must be above the stop price {}",
+                price, stop_price
+            )));
+        }
+        if stop_limit_price > stop_price {
+            bail!(BinanceError::Parameter(format!(
+                "OCO sell stop limit price {} must not be above the stop price {}",
+                stop_limit_price, stop_price
+            )));
+        }
+
+        self.place_oco(symbol.into(), OrderSide::Sell, qty.into(), price, stop_price, stop_limit_price)
+    }
+
+    // Place an OCO - BUY: a LIMIT order at price below the market and a STOP_LOSS_LIMIT order
+    // at stop_limit_price, triggered at stop_price above it
+    pub fn place_oco_buy<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, stop_limit_price: Amount,
+    ) -> Result<(OrderList)>
+        where S: Into<String>, F: Into<Amount>
+    {
+        if price >= stop_price {
+            bail!(BinanceError::Parameter(format!(
+                "OCO buy price {} must be below the stop price {}",
+                price, stop_price
+            )));
+        }
+        if stop_limit_price < stop_price {
+            bail!(BinanceError::Parameter(format!(
+                "OCO buy stop limit price {} must not be below the stop price {}",
+                stop_limit_price, stop_price
+            )));
+        }
+
+        self.place_oco(symbol.into(), OrderSide::Buy, qty.into(), price, stop_price, stop_limit_price)
+    }
+
+    fn place_oco(
+        &self, symbol: String, side: OrderSide, qty: Amount, price: Amount, stop_price: Amount,
+        stop_limit_price: Amount,
+    ) -> Result<(OrderList)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol);
+        parameters.insert("side".into(), side.as_str().into());
+        parameters.insert("quantity".into(), qty.to_string());
+        parameters.insert("price".into(), price.to_string());
+        parameters.insert("stopPrice".into(), stop_price.to_string());
+        // Binance requires the stop limit price and its time in force together
+        parameters.insert("stopLimitPrice".into(), stop_limit_price.to_string());
+        parameters.insert("stopLimitTimeInForce".into(), TimeInForce::GoodTillCanceled.as_str().into());
+        if let Some(ref mode) = self.self_trade_prevention_mode {
+            parameters.insert("selfTradePreventionMode".into(), mode.as_str().into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(API_V3_ORDER_OCO, &request)?;
+        let order_list: OrderList = from_json(&data)?;
+
+        Ok(order_list)
+    }
+
+    // Cancel both orders of an OCO
+    pub fn cancel_order_list<S>(&self, symbol: S, order_list_id: u64) -> Result<(OrderList)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(API_V3_ORDER_LIST, &request)?;
+        let order_list: OrderList = from_json(&data)?;
+
+        Ok(order_list)
+    }
+
+    pub fn cancel_order_list_by_client_id<S1, S2>(&self, symbol: S1, list_client_order_id: S2) -> Result<(OrderList)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("listClientOrderId".into(), list_client_order_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(API_V3_ORDER_LIST, &request)?;
+        let order_list: OrderList = from_json(&data)?;
+
+        Ok(order_list)
+    }
+
+    // Check an OCO's status
+    pub fn order_list_status(&self, order_list_id: u64) -> Result<(OrderList)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_ORDER_LIST, &request)?;
+        let order_list: OrderList = from_json(&data)?;
+
+        Ok(order_list)
+    }
+
+    // All open OCOs
+    pub fn get_open_order_lists(&self) -> Result<(Vec<OrderList>)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(API_V3_OPEN_ORDER_LIST, &request)?;
+        let order_lists: Vec<OrderList> = from_json(&data)?;
+
+        Ok(order_lists)
+    }
+
     // Check an order's status
     pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<(OrderCanceled)>
         where S: Into<String>
@@ -188,38 +867,634 @@ impl Account {
 
         let request = build_signed_request(parameters, self.recv_window)?;
         let data = self.client.delete_signed(API_V3_ORDER, &request)?;
-        let order_canceled: OrderCanceled = from_str(data.as_str())?;
+        let order_canceled: OrderCanceled = from_json(&data)?;
 
         Ok(order_canceled)
     }
 
+    pub fn cancel_order_with_meta<S>(&self, symbol: S, order_id: u64) -> Result<(ApiResponse<OrderCanceled>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let answer = self.client.delete_signed_with_meta(API_V3_ORDER, &request)?;
+
+        answer.and_then(|data| from_json(&data))
+    }
+
+    // Cancel every open order on a symbol at once, OCOs included. Nothing to cancel is not an error
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<(Vec<CanceledOrder>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = match self.client.delete_signed(API_V3_OPEN_ORDERS, &request) {
+            Ok(data) => data,
+            Err(BinanceError::Api { code, .. }) if code == UNKNOWN_ORDER => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let canceled_orders: Vec<CanceledOrder> = from_json(&data)?;
+
+        Ok(canceled_orders)
+    }
+
     // Trade history
     pub fn trade_history<S>(&self, symbol: S) -> Result<(Vec<TradeHistory>)>
         where S: Into<String>
+    {
+        self.get_my_trades(symbol, TradeHistoryOptions::default())
+    }
+
+    // Fills of ONE symbol, with price, commission and maker side
+    pub fn get_my_trades<S>(&self, symbol: S, options: TradeHistoryOptions) -> Result<(Vec<TradeHistory>)>
+        where S: Into<String>
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
         parameters.insert("symbol".into(), symbol.into());
 
+        if let Some(from_id) = options.from_id {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let Some(order_id) = options.order_id {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(start_time) = options.start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = options.end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = options.limit {
+            if limit == 0 || limit > MY_TRADES_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, MY_TRADES_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
         let request = build_signed_request(parameters, self.recv_window)?;
-        let data = self.client.get_signed("/api/v3/myTrades", &request)?;
-        let trade_history: Vec<TradeHistory> = from_str(data.as_str())?;
+        let data = self.client.get_signed(API_V3_MY_TRADES, &request)?;
+        let trade_history: Vec<TradeHistory> = from_json(&data)?;
 
         Ok(trade_history)
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
+    pub(crate) fn build_order(&self, order: OrderRequest) -> Result<(BTreeMap<String, String>)> {
+        order.validate()?;
         let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
 
+        if let Some(response_type) = order.response_type.or(self.order_response_type) {
+            order_parameters.insert("newOrderRespType".into(), response_type.as_str().into());
+        }
+        if let Some(mode) = order.self_trade_prevention_mode.or_else(|| self.self_trade_prevention_mode.clone()) {
+            order_parameters.insert("selfTradePreventionMode".into(), mode.as_str().into());
+        }
         order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side);
-        order_parameters.insert("type".into(), order.order_type);
-        order_parameters.insert("quantity".into(), order.qty.to_string());
+        order_parameters.insert("side".into(), order.side.as_str().into());
+        order_parameters.insert("type".into(), order.order_type.as_str().into());
+
+        if let Some(qty) = order.qty {
+            order_parameters.insert("quantity".into(), qty.to_string());
+        }
+        if let Some(quote_order_qty) = order.quote_order_qty {
+            order_parameters.insert("quoteOrderQty".into(), quote_order_qty.to_string());
+        }
+        if let Some(price) = order.price {
+            order_parameters.insert("price".into(), price.to_string());
+        }
+        if let Some(time_in_force) = order.time_in_force {
+            order_parameters.insert("timeInForce".into(), time_in_force.as_str().into());
+        }
+        if let Some(stop_price) = order.stop_price {
+            order_parameters.insert("stopPrice".into(), stop_price.to_string());
+        }
+        if let Some(client_order_id) = order.new_client_order_id {
+            order_parameters.insert("newClientOrderId".into(), client_order_id);
+        }
+        if let Some(iceberg_qty) = order.iceberg_qty {
+            order_parameters.insert("icebergQty".into(), iceberg_qty.to_string());
+        }
+
+        Ok(order_parameters)
+    }
+}
+
+// Iterator of Account::iter_all_orders and Account::iter_my_trades
+pub struct HistoryIter<'a, T> {
+    account: &'a Account,
+    symbol: String,
+    // Id from which the next page starts
+    next_id: u64,
+    limit: u16,
+    delay: Option<Duration>,
+    page: VecDeque<T>,
+    requested: bool,
+    done: bool,
+    fetch: fn(&Account, &str, u64, u16) -> Result<(Vec<T>)>,
+    id_of: fn(&T) -> u64,
+}
+
+impl<'a, T> HistoryIter<'a, T> {
+    fn new(
+        account: &'a Account, symbol: String, from: Option<u64>, limit: u16,
+        fetch: fn(&Account, &str, u64, u16) -> Result<(Vec<T>)>, id_of: fn(&T) -> u64,
+    ) -> HistoryIter<'a, T> {
+        HistoryIter {
+            account,
+            symbol,
+            next_id: from.unwrap_or(0),
+            limit,
+            delay: None,
+            page: VecDeque::new(),
+            requested: false,
+            done: false,
+            fetch,
+            id_of,
+        }
+    }
+
+    // Records per request, 1 to 1000 (the default)
+    pub fn page_limit(mut self, limit: u16) -> HistoryIter<'a, T> {
+        self.limit = limit;
+        self
+    }
+
+    // Pause between two requests, to spare the request weight
+    pub fn delay(mut self, delay: Duration) -> HistoryIter<'a, T> {
+        self.delay = Some(delay);
+        self
+    }
+
+    fn next_page(&mut self) -> Result<()> {
+        if self.requested {
+            if let Some(delay) = self.delay {
+                thread::sleep(delay);
+            }
+        }
+        self.requested = true;
+
+        let records = (self.fetch)(self.account, &self.symbol, self.next_id, self.limit)?;
+        // A short page is the last one
+        self.done = records.len() < self.limit as usize;
+
+        let first_id = self.next_id;
+        let id_of = self.id_of;
+        for record in records {
+            let id = id_of(&record);
+            // Never hand out a record twice, even if a page repeats the previous boundary
+            if id < first_id {
+                continue;
+            }
+            self.next_id = id + 1;
+            self.page.push_back(record);
+        }
+        // Nothing new, asking again would answer the same page
+        if self.page.is_empty() {
+            self.done = true;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T> Iterator for HistoryIter<'a, T> {
+    type Item = Result<(T)>;
+
+    fn next(&mut self) -> Option<Result<(T)>> {
+        while self.page.is_empty() {
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        self.page.pop_front().map(Ok)
+    }
+}
+
+fn fetch_orders(account: &Account, symbol: &str, from: u64, limit: u16) -> Result<(Vec<Order>)> {
+    let options = OrderHistoryOptions {
+        order_id: Some(from),
+        limit: Some(limit),
+        ..OrderHistoryOptions::default()
+    };
+
+    account.get_all_orders(symbol, options)
+}
+
+fn fetch_trades(account: &Account, symbol: &str, from: u64, limit: u16) -> Result<(Vec<TradeHistory>)> {
+    let options = TradeHistoryOptions {
+        from_id: Some(from),
+        limit: Some(limit),
+        ..TradeHistoryOptions::default()
+    };
+
+    account.get_my_trades(symbol, options)
+}
+
+impl OrderRequest {
+    // Any side and type, set the rest with the builder methods
+    pub fn new<S>(symbol: S, side: OrderSide, order_type: OrderType) -> OrderRequest
+        where S: Into<String>
+    {
+        OrderRequest {
+            symbol: symbol.into(),
+            side,
+            order_type,
+            qty: None,
+            quote_order_qty: None,
+            price: None,
+            stop_price: None,
+            time_in_force: None,
+            new_client_order_id: None,
+            iceberg_qty: None,
+            response_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    // LIMIT GTC order - BUY
+    pub fn limit_buy<S>(symbol: S, qty: Amount, price: Amount) -> OrderRequest
+        where S: Into<String>
+    {
+        OrderRequest::new(symbol, OrderSide::Buy, OrderType::Limit)
+            .qty(qty)
+            .price(price)
+            .time_in_force(TimeInForce::GoodTillCanceled)
+    }
+
+    // LIMIT GTC order - SELL
+    pub fn limit_sell<S>(symbol: S, qty: Amount, price: Amount) -> OrderRequest
+        where S: Into<String>
+    {
+        OrderRequest::new(symbol, OrderSide::Sell, OrderType::Limit)
+            .qty(qty)
+            .price(price)
+            .time_in_force(TimeInForce::GoodTillCanceled)
+    }
+
+    pub fn market_buy<S>(symbol: S, qty: Amount) -> OrderRequest
+        where S: Into<String>
+    {
+        OrderRequest::new(symbol, OrderSide::Buy, OrderType::Market).qty(qty)
+    }
+
+    pub fn market_sell<S>(symbol: S, qty: Amount) -> OrderRequest
+        where S: Into<String>
+    {
+        OrderRequest::new(symbol, OrderSide::Sell, OrderType::Market).qty(qty)
+    }
+
+    pub fn qty(mut self, qty: Amount) -> OrderRequest {
+        self.qty = Some(qty);
+        self
+    }
+
+    // Amount of the quote asset to spend or receive, MARKET orders only and never together with qty
+    pub fn quote_order_qty(mut self, quote_order_qty: Amount) -> OrderRequest {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn price(mut self, price: Amount) -> OrderRequest {
+        self.price = Some(price);
+        self
+    }
+
+    // Trigger price of the STOP_LOSS and TAKE_PROFIT types
+    pub fn stop_price(mut self, stop_price: Amount) -> OrderRequest {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> OrderRequest {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    // Our own id of the order, a retry with the same id cannot place the order twice
+    pub fn client_order_id<S>(mut self, client_order_id: S) -> OrderRequest
+        where S: Into<String>
+    {
+        self.new_client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    // Visible part of a LIMIT GTC order
+    pub fn iceberg_qty(mut self, iceberg_qty: Amount) -> OrderRequest {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    // Instead of Account::order_response_type
+    pub fn response_type(mut self, response_type: OrderResponseType) -> OrderRequest {
+        self.response_type = Some(response_type);
+        self
+    }
+
+    // Instead of Account::self_trade_prevention_mode
+    pub fn self_trade_prevention_mode(mut self, mode: SelfTradePreventionMode) -> OrderRequest {
+        self.self_trade_prevention_mode = Some(mode);
+        self
+    }
+
+    // Catches locally what Binance would reject
+    fn validate(&self) -> Result<()> {
+        let order_type = &self.order_type;
+
+        match (self.qty, self.quote_order_qty) {
+            (Some(_), Some(_)) => {
+                bail!(BinanceError::Parameter(
+                    "An order takes either a quantity or a quote order quantity, not both".into(),
+                ))
+            }
+            (None, None) => {
+                bail!(BinanceError::Parameter(
+                    "An order needs a quantity or a quote order quantity".into(),
+                ))
+            }
+            (None, Some(_)) if *order_type != OrderType::Market => {
+                bail!(BinanceError::Parameter(format!(
+                    "Quote order quantities need a MARKET order, not {}",
+                    order_type
+                )))
+            }
+            _ => {}
+        }
+
+        if order_type.is_stop() && self.stop_price.is_none() {
+            bail!(BinanceError::Parameter(format!("{} orders need a stop price", order_type)));
+        }
+
+        if order_type.is_limit() && (self.price.is_none() || self.time_in_force.is_none()) {
+            bail!(BinanceError::Parameter(format!("{} orders need a price and a time in force", order_type)));
+        }
+
+        if let Some(ref client_order_id) = self.new_client_order_id {
+            validate_client_order_id(client_order_id)?;
+        }
+
+        if let Some(iceberg_qty) = self.iceberg_qty {
+            let qty = self.qty.unwrap_or(ZERO);
+            if *order_type != OrderType::Limit {
+                bail!(BinanceError::Parameter("Iceberg quantities need a LIMIT order".into()));
+            }
+            if self.time_in_force != Some(TimeInForce::GoodTillCanceled) {
+                bail!(BinanceError::Parameter(format!("Iceberg orders must be GTC, not {:?}", self.time_in_force)));
+            }
+            if iceberg_qty <= ZERO || iceberg_qty >= qty {
+                bail!(BinanceError::Parameter(format!(
+                    "Iceberg quantity {} must be above 0 and below the quantity {}",
+                    iceberg_qty, qty
+                )));
+            }
+        }
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force);
+        Ok(())
+    }
+}
+
+// Binance accepts 1 to 36 characters of ^[.A-Z:/a-z0-9_-]$, checked here instead of failing with -1100
+fn validate_client_order_id(client_order_id: &str) -> Result<()> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || ".:/_-".contains(c);
+
+    if client_order_id.is_empty() || client_order_id.len() > 36 || !client_order_id.chars().all(valid_char) {
+        bail!(BinanceError::Parameter(format!(
+            "Invalid client order id {:?}, use 1 to 36 of A-Z a-z 0-9 . : / _ -",
+            client_order_id
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Binance, Config};
+    use client::fake::{answer, client, FakeTransport};
+    use reqwest::Method;
+    use url::Url;
+
+    static ACCOUNT: &'static str = r#"{"makerCommission":15,"takerCommission":15,"buyerCommission":0,"sellerCommission":0,"commissionRates":{"maker":"0.00150000","taker":"0.00150000","buyer":"0.00000000","seller":"0.00000000"},"canTrade":true,"canWithdraw":true,"canDeposit":true,"brokered":false,"requireSelfTradePrevention":false,"preventSor":false,"updateTime":123456789,"accountType":"SPOT","balances":[{"asset":"BTC","free":"4723846.89208129","locked":"0.00000000"},{"asset":"LTC","free":"4763368.68006011","locked":"0.00000000"}],"permissions":["SPOT"],"uid":354937868}"#;
+
+    static ORDER: &'static str = r#"{"symbol":"LTCBTC","orderId":1,"orderListId":-1,"clientOrderId":"myOrder1","price":"0.1","origQty":"1.0","executedQty":"0.0","cummulativeQuoteQty":"0.0","status":"NEW","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0.0","icebergQty":"0.0","time":1499827319559,"updateTime":1499827319559,"isWorking":true,"workingTime":1499827319559,"origQuoteOrderQty":"0.000000","selfTradePreventionMode":"NONE"}"#;
+
+    static ACK: &'static str = r#"{"symbol":"LTCBTC","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595}"#;
+
+    // Filled MARKET leg of an OCO placed with quoteOrderQty, GET /api/v3/order
+    static FILLED_ORDER: &'static str = r#"{"symbol":"BNBUSDT","orderId":5935139,"orderListId":1271304,"clientOrderId":"x-A6SIDXVS16828374157341","price":"0.00000000","origQty":"0.03700000","executedQty":"0.03700000","cummulativeQuoteQty":"9.99510000","status":"FILLED","timeInForce":"GTC","type":"MARKET","side":"BUY","stopPrice":"0.00000000","icebergQty":"0.00000000","time":1682837415750,"updateTime":1682837415751,"isWorking":true,"workingTime":1682837415750,"origQuoteOrderQty":"10.00000000","selfTradePreventionMode":"EXPIRE_MAKER"}"#;
+
+    // Order of the 2018 API, before orderListId, cummulativeQuoteQty and origQuoteOrderQty
+    static ORDER_2018: &'static str = r#"{"symbol":"LTCBTC","orderId":1,"clientOrderId":"myOrder1","price":"0.1","origQty":"1.0","executedQty":"0.0","status":"NEW","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0.0","icebergQty":"0.0","time":1499827319559,"isWorking":true}"#;
+
+    static MY_TRADES: &'static str = r#"[{"symbol":"BNBBTC","id":28457,"orderId":100234,"orderListId":-1,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","commission":"10.10000000","commissionAsset":"BNB","time":1499865549590,"isBuyer":true,"isMaker":false,"isBestMatch":true}]"#;
+
+    fn account(transport: &Arc<FakeTransport>) -> Account {
+        Account::new_with_client(client(transport), &Config::default())
+    }
+
+    #[test]
+    fn get_account() {
+        let transport = FakeTransport::new(vec![answer(200, ACCOUNT)]);
+
+        let info = account(&transport).get_account().unwrap();
+        assert_eq!(info.maker_commission, 15.0);
+        assert!(info.can_trade && info.can_withdraw && info.can_deposit);
+        assert_eq!(info.balances.len(), 2);
+        assert_eq!(info.balances[1].asset, "LTC");
+        assert_eq!(info.balances[1].free, amount("4763368.68006011"));
+        assert_eq!(info.balances[1].locked, ZERO);
+
+        let (method, url) = transport.requests().remove(0);
+        assert_eq!(method, Method::Get);
+        assert!(url.starts_with("https://api.binance.com/api/v3/account?"));
+        assert!(url.contains("timestamp=") && url.contains("&signature="));
+    }
+
+    #[test]
+    fn order_status() {
+        let transport = FakeTransport::new(vec![answer(200, ORDER)]);
+
+        let order = account(&transport).order_status("LTCBTC", 1).unwrap();
+        assert_eq!((order.symbol.as_str(), order.order_id, order.order_list_id), ("LTCBTC", 1, -1));
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.price, amount("0.1"));
+        assert_eq!(order.orig_qty, amount("1.0"));
+        assert_eq!(order.is_working, Some(true));
+        assert_eq!(order.self_trade_prevention_mode, Some(SelfTradePreventionMode::None));
+        assert!(transport.requests()[0].1.contains("/api/v3/order?orderId=1&recvWindow=5000&symbol=LTCBTC&"));
+    }
+
+    #[test]
+    fn limit_buy_acknowledged() {
+        let transport = FakeTransport::new(vec![answer(200, ACK)]);
+
+        let transaction = account(&transport).limit_buy("LTCBTC", amount("1"), amount("0.1")).unwrap();
+        assert_eq!((transaction.order_id, transaction.transact_time), (28, 1507725176595));
+        assert_eq!(transaction.price, None);
+        assert_eq!(transport.requests()[0].0, Method::Post);
+    }
+
+    #[test]
+    fn limit_buy_rejected() {
+        let rejected = r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#;
+        let transport = FakeTransport::new(vec![answer(400, rejected)]);
+
+        match account(&transport).limit_buy("LTCBTC", amount("1"), amount("0.1")) {
+            Err(ref error) if error.binance_error().map_or(false, |error| error.is_insufficient_balance()) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn order_lost_in_a_server_error() {
+        let transport = FakeTransport::new(vec![answer(502, "Bad Gateway")]);
+
+        match account(&transport).limit_buy("LTCBTC", amount("1"), amount("0.1")) {
+            Err(BinanceError::OutcomeUnknown(ref error)) => match **error {
+                BinanceError::Server { status: 502, .. } => {}
+                ref other => panic!("unexpected {:?}", other),
+            },
+            other => panic!("unexpected {:?}", other),
         }
+    }
+
+    #[test]
+    fn order_fields_of_current_answers() {
+        let transport = FakeTransport::new(vec![answer(200, FILLED_ORDER), answer(200, ORDER_2018)]);
+        let account = account(&transport);
+
+        let order = account.order_status("BNBUSDT", 5935139).unwrap();
+        assert_eq!(order.order_list_id, 1271304);
+        assert_eq!(order.orig_quote_order_qty, Some(amount("10")));
+        assert_eq!(order.cummulative_quote_qty, Some(amount("9.9951")));
+        assert_eq!(order.update_time, Some(1682837415751));
+        assert_eq!(order.is_working, Some(true));
+        assert_eq!(order.status, OrderStatus::Filled);
+
+        let old = account.order_status("LTCBTC", 1).unwrap();
+        assert_eq!(old.order_list_id, -1);
+        assert_eq!((old.orig_quote_order_qty, old.cummulative_quote_qty, old.update_time), (None, None, None));
+    }
+
+    #[test]
+    fn trade_history_with_quote_qty() {
+        let transport = FakeTransport::new(vec![answer(200, MY_TRADES)]);
+
+        let trades = account(&transport).trade_history("BNBBTC").unwrap();
+        assert_eq!((trades[0].id, trades[0].order_id, trades[0].order_list_id), (28457, Some(100234), -1));
+        assert_eq!(trades[0].quote_qty, amount("48.000012"));
+        assert_eq!(trades[0].commission, amount("10.1"));
+    }
+
+    fn rejection(order: OrderRequest) -> String {
+        match order.validate() {
+            Err(BinanceError::Parameter(msg)) => msg,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn order_takes_a_quantity_or_a_quote_quantity() {
+        let both = OrderRequest::market_buy("BNBUSDT", amount("0.5")).quote_order_qty(amount("10"));
+        assert!(rejection(both).contains("not both"));
+        let neither = OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Market);
+        assert!(rejection(neither).contains("needs a quantity"));
+        let limit = OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Limit)
+            .quote_order_qty(amount("10"))
+            .price(amount("300"))
+            .time_in_force(TimeInForce::GoodTillCanceled);
+        assert!(rejection(limit).contains("need a MARKET order"));
+
+        let quoted = OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Market).quote_order_qty(amount("10"));
+        assert!(quoted.validate().is_ok());
+        assert!(OrderRequest::market_sell("BNBUSDT", amount("0.5")).validate().is_ok());
+    }
+
+    #[test]
+    fn stop_orders_need_a_stop_price() {
+        let stop = OrderRequest::new("BNBUSDT", OrderSide::Sell, OrderType::StopLoss).qty(amount("1"));
+        assert_eq!(rejection(stop.clone()), "STOP_LOSS orders need a stop price");
+
+        assert!(stop.stop_price(amount("250")).validate().is_ok());
+    }
+
+    #[test]
+    fn limit_orders_need_a_price_and_a_time_in_force() {
+        let without_price = OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Limit)
+            .qty(amount("1"))
+            .time_in_force(TimeInForce::GoodTillCanceled);
+        assert_eq!(rejection(without_price), "LIMIT orders need a price and a time in force");
+        let without_time_in_force =
+            OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Limit).qty(amount("1")).price(amount("300"));
+        assert_eq!(rejection(without_time_in_force), "LIMIT orders need a price and a time in force");
+        let stop_limit = OrderRequest::new("BNBUSDT", OrderSide::Sell, OrderType::StopLossLimit)
+            .qty(amount("1"))
+            .stop_price(amount("250"));
+        assert_eq!(rejection(stop_limit), "STOP_LOSS_LIMIT orders need a price and a time in force");
+
+        assert!(OrderRequest::limit_buy("BNBUSDT", amount("1"), amount("300")).validate().is_ok());
+    }
+
+    #[test]
+    fn iceberg_orders_must_be_limit_gtc_below_the_quantity() {
+        let ioc = OrderRequest::new("BNBUSDT", OrderSide::Buy, OrderType::Limit)
+            .qty(amount("10"))
+            .price(amount("300"))
+            .time_in_force(TimeInForce::ImmediateOrCancel)
+            .iceberg_qty(amount("1"));
+        assert!(rejection(ioc).starts_with("Iceberg orders must be GTC"));
+        let market = OrderRequest::market_buy("BNBUSDT", amount("10")).iceberg_qty(amount("1"));
+        assert_eq!(rejection(market), "Iceberg quantities need a LIMIT order");
+        let whole = OrderRequest::limit_buy("BNBUSDT", amount("10"), amount("300")).iceberg_qty(amount("10"));
+        assert!(rejection(whole).contains("below the quantity"));
+
+        let iceberg = OrderRequest::limit_buy("BNBUSDT", amount("10"), amount("300")).iceberg_qty(amount("1"));
+        assert!(iceberg.validate().is_ok());
+    }
+
+    // Query of a signed url without the parts that change with every request
+    fn without_signature(url: &str) -> (String, Vec<(String, String)>) {
+        let url = Url::parse(url).unwrap();
+        let pairs = url
+            .query_pairs()
+            .into_owned()
+            .filter(|&(ref key, _)| key != "timestamp" && key != "signature")
+            .collect();
+
+        (url.path().to_string(), pairs)
+    }
+
+    #[test]
+    fn test_order_posts_the_payload_of_send() {
+        let transport = FakeTransport::new(vec![answer(200, "{}"), answer(200, ACK)]);
+        let account = account(&transport);
+        let order = || {
+            OrderRequest::limit_buy("LTCBTC", amount("1"), amount("0.1"))
+                .client_order_id("myOrder1")
+                .self_trade_prevention_mode(SelfTradePreventionMode::ExpireMaker)
+        };
 
-        order_parameters
+        account.test(order()).unwrap();
+        account.send(order()).unwrap();
+        let requests = transport.requests();
+        assert!(requests.iter().all(|&(ref method, _)| *method == Method::Post));
+        let (test_path, test_query) = without_signature(&requests[0].1);
+        let (path, query) = without_signature(&requests[1].1);
+        assert_eq!((test_path.as_str(), path.as_str()), ("/api/v3/order/test", "/api/v3/order"));
+        assert_eq!(test_query, query);
+        assert!(query.contains(&("newClientOrderId".to_string(), "myOrder1".to_string())));
     }
 }
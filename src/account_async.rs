@@ -0,0 +1,119 @@
+use util::*;
+use model::*;
+use errors::*;
+use account::{Account, OrderRequest};
+use client_async::*;
+use futures::{future, Future};
+use reqwest::Method;
+use std::collections::BTreeMap;
+use tokio_core::reactor::Handle;
+use serde::de::DeserializeOwned;
+
+static API_V3_ACCOUNT: &'static str = "/api/v3/account";
+static API_V3_ORDER: &'static str = "/api/v3/order";
+static API_V3_OPEN_ORDERS: &'static str = "/api/v3/openOrders";
+
+// Futures based counterpart of Account. Orders are built and checked by the Account it is made
+// from, with its recvWindow, order response type and self trade prevention mode
+#[derive(Clone)]
+pub struct AsyncAccount {
+    pub client: AsyncClient,
+    account: Account,
+}
+
+impl AsyncAccount {
+    pub fn new(account: &Account, handle: &Handle) -> Result<(AsyncAccount)> {
+        Ok(AsyncAccount {
+            client: AsyncClient::new(&account.client, handle)?,
+            account: account.clone(),
+        })
+    }
+
+    // Account Information
+    pub fn get_account(&self) -> ResponseFuture<AccountInformation> {
+        self.signed(Method::Get, API_V3_ACCOUNT, BTreeMap::new())
+    }
+
+    // Current open orders for ONE symbol
+    pub fn get_open_orders<S>(&self, symbol: S) -> ResponseFuture<Vec<Order>>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        self.signed(Method::Get, API_V3_OPEN_ORDERS, parameters)
+    }
+
+    // Check an order's status
+    pub fn order_status<S>(&self, symbol: S, order_id: u64) -> ResponseFuture<Order>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        self.signed(Method::Get, API_V3_ORDER, parameters)
+    }
+
+    // Place an order built with OrderRequest
+    pub fn send(&self, order: OrderRequest) -> ResponseFuture<Transaction> {
+        match self.account.build_order(order) {
+            Ok(parameters) => self.signed(Method::Post, API_V3_ORDER, parameters),
+            Err(e) => Box::new(future::err(e)),
+        }
+    }
+
+    // Place a LIMIT order - BUY
+    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> ResponseFuture<Transaction>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_buy(symbol, qty.into(), price))
+    }
+
+    // Place a LIMIT order - SELL
+    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> ResponseFuture<Transaction>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::limit_sell(symbol, qty.into(), price))
+    }
+
+    // Place a MARKET order - BUY
+    pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> ResponseFuture<Transaction>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::market_buy(symbol, qty.into()))
+    }
+
+    // Place a MARKET order - SELL
+    pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> ResponseFuture<Transaction>
+        where S: Into<String>, F: Into<Amount>
+    {
+        self.send(OrderRequest::market_sell(symbol, qty.into()))
+    }
+
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> ResponseFuture<OrderCanceled>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        self.signed(Method::Delete, API_V3_ORDER, parameters)
+    }
+
+    fn signed<T>(&self, method: Method, endpoint: &str, parameters: BTreeMap<String, String>) -> ResponseFuture<T>
+        where T: DeserializeOwned + 'static
+    {
+        let request = match build_signed_request(parameters, self.account.recv_window) {
+            Ok(request) => request,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let data = match method {
+            Method::Post => self.client.post_signed(endpoint, &request),
+            Method::Delete => self.client.delete_signed(endpoint, &request),
+            _ => self.client.get_signed(endpoint, &request),
+        };
+
+        Box::new(data.and_then(|data| from_json(&data)))
+    }
+}
@@ -2,44 +2,308 @@ use account::*;
 use market::*;
 use general::*;
 use userstream::*;
+use margin::*;
+use wallet::*;
+use sub_account::*;
+use savings::*;
+use staking::*;
+use convert::*;
+use usdm_futures::*;
+use coinm_futures::*;
 use client::*;
+use websockets::WebsocketHost;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+static REST_API_URL: &'static str = "https://api.binance.com";
+static REST_API1_URL: &'static str = "https://api1.binance.com";
+static REST_API2_URL: &'static str = "https://api2.binance.com";
+static REST_API3_URL: &'static str = "https://api3.binance.com";
+static REST_API_US_URL: &'static str = "https://api.binance.us";
+static REST_API_TESTNET_URL: &'static str = "https://testnet.binance.vision";
+static FUTURES_API_URL: &'static str = "https://fapi.binance.com";
+static FUTURES_API_TESTNET_URL: &'static str = "https://testnet.binancefuture.com";
+static COIN_FUTURES_API_URL: &'static str = "https://dapi.binance.com";
+
+// Official REST hosts, the testnet has the same paths and signing with its own keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestHost {
+    Binance,
+    // Fallback hosts of Binance, possibly faster but less stable
+    Binance1,
+    Binance2,
+    Binance3,
+    BinanceUs,
+    Testnet,
+    // USD-M futures, see FuturesAccount
+    Futures,
+    // Both kinds of futures, fapi and dapi paths
+    FuturesTestnet,
+    // COIN-M futures, see CoinFutures
+    CoinFutures,
+}
+
+impl RestHost {
+    pub fn base_url(&self) -> &'static str {
+        match *self {
+            RestHost::Binance => REST_API_URL,
+            RestHost::Binance1 => REST_API1_URL,
+            RestHost::Binance2 => REST_API2_URL,
+            RestHost::Binance3 => REST_API3_URL,
+            RestHost::BinanceUs => REST_API_US_URL,
+            RestHost::Testnet => REST_API_TESTNET_URL,
+            RestHost::Futures => FUTURES_API_URL,
+            RestHost::FuturesTestnet => FUTURES_API_TESTNET_URL,
+            RestHost::CoinFutures => COIN_FUTURES_API_URL,
+        }
+    }
+}
+
+// Hosts and defaults of the clients made by Binance::new_with_config. ws_endpoint is for
+// WebSockets::new_with_endpoint, which isn't made through Binance
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub rest_api_endpoint: String,
+    // Host of FuturesAccount, futures have their own
+    pub futures_api_endpoint: String,
+    // Host of CoinFutures
+    pub coin_futures_api_endpoint: String,
+    pub ws_endpoint: String,
+    pub recv_window: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            rest_api_endpoint: RestHost::Binance.base_url().into(),
+            futures_api_endpoint: RestHost::Futures.base_url().into(),
+            coin_futures_api_endpoint: RestHost::CoinFutures.base_url().into(),
+            ws_endpoint: WebsocketHost::Binance.base_url().into(),
+            recv_window: 5000,
+        }
+    }
+}
+
+impl Config {
+    pub fn testnet() -> Config {
+        Config {
+            rest_api_endpoint: RestHost::Testnet.base_url().into(),
+            futures_api_endpoint: RestHost::FuturesTestnet.base_url().into(),
+            coin_futures_api_endpoint: RestHost::FuturesTestnet.base_url().into(),
+            ws_endpoint: WebsocketHost::Testnet.base_url().into(),
+            ..Config::default()
+        }
+    }
+
+    pub fn rest_api_endpoint<S>(mut self, rest_api_endpoint: S) -> Self
+        where S: Into<String>
+    {
+        self.rest_api_endpoint = rest_api_endpoint.into();
+        self
+    }
+
+    pub fn futures_api_endpoint<S>(mut self, futures_api_endpoint: S) -> Self
+        where S: Into<String>
+    {
+        self.futures_api_endpoint = futures_api_endpoint.into();
+        self
+    }
+
+    pub fn coin_futures_api_endpoint<S>(mut self, coin_futures_api_endpoint: S) -> Self
+        where S: Into<String>
+    {
+        self.coin_futures_api_endpoint = coin_futures_api_endpoint.into();
+        self
+    }
+
+    pub fn ws_endpoint<S>(mut self, ws_endpoint: S) -> Self
+        where S: Into<String>
+    {
+        self.ws_endpoint = ws_endpoint.into();
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+}
 
 //#[derive(Clone)]
-pub trait Binance {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Self;
+pub trait Binance: Sized {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
+        Self::new_with_client(Client::new_with_endpoint(api_key, secret_key, &config.rest_api_endpoint), config)
+    }
+
+    // Shares the connection pool, time offset and settings of client, e.g. a Market and an Account
+    // built from clones of one client reuse each other's connections
+    fn new_with_client(client: Client, config: &Config) -> Self;
 }
 
 impl Binance for General {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> General {
+    fn new_with_client(client: Client, _config: &Config) -> General {
         General {
-            client: Client::new(api_key, secret_key),
+            client,
         }
     }
 }
 
 impl Binance for Account {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Account {
+    fn new_with_client(client: Client, config: &Config) -> Account {
         Account {
-            client: Client::new(api_key, secret_key),
-            recv_window: 5000,
+            client,
+            recv_window: config.recv_window,
+            order_response_type: None,
+            self_trade_prevention_mode: None,
+            filters_max_age: Duration::from_secs(DEFAULT_FILTERS_MAX_AGE_SECS),
+            filters: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
 
 impl Binance for Market {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Market {
+    fn new_with_client(client: Client, config: &Config) -> Market {
         Market {
-            client: Client::new(api_key, secret_key),
-            recv_window: 5000,
+            client,
+            recv_window: config.recv_window,
         }
     }
 }
 
 impl Binance for UserStream {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> UserStream {
+    fn new_with_client(client: Client, config: &Config) -> UserStream {
         UserStream {
-            client: Client::new(api_key, secret_key),
-            recv_window: 5000,
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for Margin {
+    fn new_with_client(client: Client, config: &Config) -> Margin {
+        Margin {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for Wallet {
+    fn new_with_client(client: Client, config: &Config) -> Wallet {
+        Wallet {
+            client,
+            recv_window: config.recv_window,
         }
     }
 }
+
+impl Binance for SubAccount {
+    fn new_with_client(client: Client, config: &Config) -> SubAccount {
+        SubAccount {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for Savings {
+    fn new_with_client(client: Client, config: &Config) -> Savings {
+        Savings {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for Staking {
+    fn new_with_client(client: Client, config: &Config) -> Staking {
+        Staking {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for Convert {
+    fn new_with_client(client: Client, config: &Config) -> Convert {
+        Convert {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for FuturesAccount {
+    // Against config.futures_api_endpoint instead of the spot host
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> FuturesAccount {
+        let client = Client::new_with_endpoint(api_key, secret_key, &config.futures_api_endpoint);
+
+        FuturesAccount::new_with_client(client, config)
+    }
+
+    // client has to be made for the futures host, a spot client's requests would fail
+    fn new_with_client(client: Client, config: &Config) -> FuturesAccount {
+        FuturesAccount {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for CoinFutures {
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> CoinFutures {
+        let client = Client::new_with_endpoint(api_key, secret_key, &config.coin_futures_api_endpoint);
+
+        CoinFutures::new_with_client(client, config)
+    }
+
+    // client has to be made for the COIN-M futures host
+    fn new_with_client(client: Client, config: &Config) -> CoinFutures {
+        CoinFutures {
+            client,
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for FuturesMarket {
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> FuturesMarket {
+        let client = Client::new_with_endpoint(api_key, secret_key, &config.futures_api_endpoint);
+
+        FuturesMarket::new_with_client(client, config)
+    }
+
+    fn new_with_client(client: Client, _config: &Config) -> FuturesMarket {
+        FuturesMarket {
+            client,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use client::fake::{answer, FakeTransport};
+
+    #[test]
+    fn config_hosts_are_the_hosts_requested() {
+        let config = Config::testnet();
+        let transport = FakeTransport::new(vec![answer(200, "{}"), answer(200, "{}")]);
+
+        let mut general = General::new_with_config(None, None, &config);
+        general.client.set_transport(Some(transport.clone()));
+        general.ping().unwrap();
+        let mut futures = FuturesAccount::new_with_config(Some("api-key".into()), None, &config);
+        futures.client.set_transport(Some(transport.clone()));
+        futures.keep_alive_user_stream("listen-key").unwrap();
+
+        let urls: Vec<String> = transport.requests().into_iter().map(|(_, url)| url).collect();
+        assert_eq!(urls[0], format!("{}/api/v1/ping", REST_API_TESTNET_URL));
+        assert!(urls[1].starts_with(&format!("{}/fapi/v1/listenKey", FUTURES_API_TESTNET_URL)), "{}", urls[1]);
+    }
+}
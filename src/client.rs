@@ -1,123 +1,843 @@
 use hex::encode as hex_encode;
 use errors::*;
+use model::{ExchangeInformation, RateLimitInterval, RateLimitType, ServerTime};
+use proxy::{Proxy, TunnelTransport};
+use util::{build_request, build_signed_request, check_recv_window, get_timestamp};
 use reqwest;
-use reqwest::{Response, StatusCode};
-use reqwest::header::{ContentType, Headers, UserAgent};
-use std::io::Read;
+use reqwest::{Method, Response, StatusCode};
+use reqwest::header::{ContentType, Date, Headers, UserAgent};
+use std::io::{self, ErrorKind as IoErrorKind, Read};
+use std::collections::BTreeMap;
+use std::str;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use url::Url;
+use url::form_urlencoded::byte_serialize;
+use serde::de::DeserializeOwned;
+use serde_json::from_str;
 use ring::{digest, hmac};
 
-static API1_HOST: &'static str = "https://www.binance.com";
+static API1_HOST: &'static str = "https://api.binance.com";
+static API_V3_TIME: &'static str = "/api/v3/time";
+
+static DEFAULT_TIMEOUT_SECS: u64 = 10;
+static DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+
+// "Timestamp for this request is outside of the recvWindow"
+static TIMESTAMP_ERROR_CODE: i64 = -1021;
+
+static USED_WEIGHT_1M_HEADER: &'static str = "X-MBX-USED-WEIGHT-1M";
+static ORDER_COUNT_10S_HEADER: &'static str = "X-MBX-ORDER-COUNT-10S";
+static ORDER_COUNT_1D_HEADER: &'static str = "X-MBX-ORDER-COUNT-1D";
+// The rate limits of Binance reset on the minutes of the server clock
+static MINUTE_MS: u64 = 60_000;
+// Value of a usage counter before any response carried its header
+static UNKNOWN: u64 = u64::MAX;
+// Target of the request logs
+static LOG_TARGET: &'static str = "binance";
+static REDACTED: &'static str = "<redacted>";
+
+// Opt-in retries of rate limited (429) requests and, for queries, of server errors (5xx).
+// IP bans (418) and the 4xx errors of Binance are never retried
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    // Backoff of the first retry when there is no Retry-After, doubled on each retry
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_server_errors: bool,
+    on_retry: Option<Arc<Fn(u32, Duration, &Error) + Send + Sync>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            retry_server_errors: true,
+            on_retry: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Called before each retry with its number, the delay and the error retried
+    pub fn on_retry<F>(mut self, on_retry: F) -> Self
+        where F: Fn(u32, Duration, &Error) + Send + Sync + 'static
+    {
+        self.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    fn delay(&self, retries: u32, error: &BinanceError, idempotent: bool) -> Option<Duration> {
+        match *error {
+            BinanceError::TooManyRequests { retry_after: Some(retry_after) } => Some(retry_after),
+            BinanceError::TooManyRequests { retry_after: None } => Some(self.backoff(retries)),
+            BinanceError::Server { .. } | BinanceError::ServiceUnavailable { .. } | BinanceError::Timeout { .. }
+                if idempotent && self.retry_server_errors =>
+            {
+                Some(self.backoff(retries))
+            }
+            _ => None,
+        }
+    }
+
+    // Exponential backoff, jittered between half and all of it so clients don't retry in step
+    fn backoff(&self, retries: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .checked_mul(1 << retries.min(16))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        let jitter = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.subsec_nanos() % 1000)
+            .unwrap_or(0);
+
+        delay / 2 + delay / 2 * jitter / 1000
+    }
+}
+
+// Opt-in pacing of requests on the X-MBX-USED-WEIGHT-1M header. Once the weight used in the
+// current minute reaches fraction of weight_limit, requests wait for the next minute
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throttle {
+    pub weight_limit: u32,
+    pub fraction: f64,
+}
+
+impl Throttle {
+    pub fn new(weight_limit: u32, fraction: f64) -> Throttle {
+        Throttle { weight_limit, fraction }
+    }
+
+    // The REQUEST_WEIGHT limit per minute of exchangeInfo, None when it lists none
+    pub fn from_exchange_info(info: &ExchangeInformation, fraction: f64) -> Option<Throttle> {
+        info.rate_limits
+            .iter()
+            .find(|limit| {
+                limit.rate_limit_type == RateLimitType::RequestWeight
+                    && limit.interval == RateLimitInterval::Minute
+                    && limit.interval_num == 1
+            })
+            .map(|limit| Throttle::new(limit.limit as u32, fraction))
+    }
+
+    fn is_exceeded(&self, used_weight: u64) -> bool {
+        used_weight as f64 >= f64::from(self.weight_limit) * self.fraction
+    }
+}
+
+// Latest usage headers of the responses, UNKNOWN until one carried them
+struct RateLimitUsage {
+    used_weight_1m: AtomicU64,
+    // Server minute the weight was reported in
+    weight_minute: AtomicU64,
+    order_count_10s: AtomicU64,
+    order_count_1d: AtomicU64,
+}
+
+impl RateLimitUsage {
+    fn new() -> RateLimitUsage {
+        RateLimitUsage {
+            used_weight_1m: AtomicU64::new(UNKNOWN),
+            weight_minute: AtomicU64::new(0),
+            order_count_10s: AtomicU64::new(UNKNOWN),
+            order_count_1d: AtomicU64::new(UNKNOWN),
+        }
+    }
+}
+
+// An answer with the usage headers of its response and the server time of its Date header, in ms
+// (to the second). The weight and order counts are those of the request, unlike Client::used_weight_1m
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    pub data: T,
+    pub used_weight_1m: Option<u32>,
+    pub order_count_10s: Option<u32>,
+    pub order_count_1d: Option<u32>,
+    pub server_time: Option<u64>,
+    pub status: u16,
+}
+
+impl<T> ApiResponse<T> {
+    // Same metadata, for data turned into something else, e.g. parsed
+    pub fn map<U, F>(self, f: F) -> ApiResponse<U>
+        where F: FnOnce(T) -> U
+    {
+        ApiResponse {
+            data: f(self.data),
+            used_weight_1m: self.used_weight_1m,
+            order_count_10s: self.order_count_10s,
+            order_count_1d: self.order_count_1d,
+            server_time: self.server_time,
+            status: self.status,
+        }
+    }
+
+    // map, for a fallible f such as from_str
+    pub fn and_then<U, F>(self, f: F) -> Result<(ApiResponse<U>)>
+        where F: FnOnce(T) -> Result<(U)>
+    {
+        let data = f(self.data)?;
+
+        Ok(ApiResponse {
+            data,
+            used_weight_1m: self.used_weight_1m,
+            order_count_10s: self.order_count_10s,
+            order_count_1d: self.order_count_1d,
+            server_time: self.server_time,
+            status: self.status,
+        })
+    }
+}
+
+// Answer of an HttpTransport, with its body read in full
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: Headers,
+    pub body: String,
+}
+
+// What a Client sends its requests with, reqwest unless Client::set_transport replaces it. url holds
+// the host, the query and, for signed requests, the signature, headers the API key
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, method: Method, url: &str, headers: Headers, body: Option<String>) -> Result<(HttpResponse)>;
+}
 
 #[derive(Clone)]
 pub struct Client {
+    host: String,
     api_key: String,
     secret_key: String,
+    // Server time minus local time in ms, shared by the clones of a client
+    time_offset: Arc<AtomicI64>,
+    last_time_sync: Arc<Mutex<Option<Instant>>>,
+    time_resync_interval: Option<Duration>,
+    resync_on_timestamp_error: bool,
+    retry_policy: Option<RetryPolicy>,
+    retries: Arc<AtomicUsize>,
+    proxy: Option<reqwest::Proxy>,
+    // Sends the requests instead of http when the proxy needs credentials
+    tunnel: Option<TunnelTransport>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    // Keeps the connections alive between requests, the clones of a client share its pool
+    http: reqwest::Client,
+    usage: Arc<RateLimitUsage>,
+    throttle: Option<Throttle>,
+    logging: bool,
+    // Replaces http when set
+    transport: Option<Arc<HttpTransport>>,
 }
 
 impl Client {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Client::new_with_endpoint(api_key, secret_key, API1_HOST)
+    }
+
+    // endpoint is the REST host, e.g. "https://testnet.binance.vision" (see api::RestHost)
+    pub fn new_with_endpoint(api_key: Option<String>, secret_key: Option<String>, endpoint: &str) -> Self {
+        let timeout = Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+
         Client {
+            host: endpoint.trim_end_matches('/').into(),
             api_key: api_key.unwrap_or_else(|| "".into()),
             secret_key: secret_key.unwrap_or_else(|| "".into()),
+            time_offset: Arc::new(AtomicI64::new(0)),
+            last_time_sync: Arc::new(Mutex::new(None)),
+            time_resync_interval: None,
+            resync_on_timestamp_error: false,
+            retry_policy: None,
+            retries: Arc::new(AtomicUsize::new(0)),
+            proxy: None,
+            tunnel: None,
+            timeout,
+            connect_timeout: Some(Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)),
+            // Like reqwest::Client::new, only fails when the TLS backend cannot be initialized
+            http: http_client(timeout, None).expect("Client::new()"),
+            usage: Arc::new(RateLimitUsage::new()),
+            throttle: None,
+            logging: false,
+            transport: None,
         }
     }
 
-    pub fn get_signed(&self, endpoint: &str, request: &str) -> Result<(String)> {
-        let url = self.sign_request(endpoint, request);
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url.as_str())
-            .headers(self.build_headers(true))
-            .send()?;
+    // Deadline of a whole request, connecting included (reqwest 0.8 has no separate connect
+    // timeout, see set_connect_timeout), 10 seconds by default. A clone of the module with its own
+    // timeout overrides it for some calls, e.g. a tight one for placing orders next to a loose one
+    // for depth snapshots. The clone then gets a connection pool of its own
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.http = http_client(timeout, self.proxy.clone())?;
+        self.tunnel = self.tunnel.as_ref().map(|tunnel| tunnel.with_timeouts(timeout, self.connect_timeout));
+        self.timeout = timeout;
+
+        Ok(())
+    }
+
+    // Deadline of connecting, 5 seconds by default. Only the requests through a proxy with
+    // credentials (see set_proxy) apply it, reqwest 0.8 connects within the timeout of set_timeout
+    // alone
+    pub fn set_connect_timeout(&mut self, connect_timeout: Option<Duration>) {
+        self.tunnel = self.tunnel.as_ref().map(|tunnel| tunnel.with_timeouts(self.timeout, connect_timeout));
+        self.connect_timeout = connect_timeout;
+    }
+
+    // HTTP proxy of every request, signed or not. reqwest tunnels HTTPS without credentials, so
+    // the requests through a proxy with a username go over a CONNECT tunnel of their own, with
+    // Proxy-Authorization and a new connection each. SOCKS5 proxies (fine for the websockets)
+    // are refused
+    pub fn set_proxy(&mut self, proxy: Option<&Proxy>) -> Result<()> {
+        let (proxy, tunnel) = match proxy {
+            None => (None, None),
+            Some(&Proxy::Http { ref host, port, auth: None }) => {
+                (Some(reqwest::Proxy::all(format!("http://{}:{}", host, port).as_str())?), None)
+            }
+            Some(proxy @ &Proxy::Http { .. }) => {
+                (None, TunnelTransport::new(proxy, self.timeout, self.connect_timeout))
+            }
+            Some(&Proxy::Socks5 { .. }) => {
+                bail!(BinanceError::Parameter(
+                    "SOCKS5 proxies are not supported by the REST client".into(),
+                ))
+            }
+        };
+        self.http = http_client(self.timeout, proxy.clone())?;
+        self.proxy = proxy;
+        self.tunnel = tunnel;
+
+        Ok(())
+    }
+
+    // Proxy of https_proxy / all_proxy unless no_proxy exempts the host, true when one is set
+    pub fn set_proxy_from_env(&mut self) -> Result<(bool)> {
+        let proxy = Proxy::from_env_for(&Url::parse(&self.host)?);
+
+        self.set_proxy(proxy.as_ref())?;
+
+        Ok(self.proxy.is_some() || self.tunnel.is_some())
+    }
+
+    // None, the default, surfaces every failure at once
+    pub fn set_retry_policy(&mut self, retry_policy: Option<RetryPolicy>) {
+        self.retry_policy = retry_policy;
+    }
+
+    // Retries made so far by this client and its clones
+    pub fn retry_count(&self) -> usize {
+        self.retries.load(Ordering::SeqCst)
+    }
+
+    // Pacing of the requests on their weight, None (the default) sends them at once. The clones
+    // of a client share the usage it is based on
+    pub fn set_throttle(&mut self, throttle: Option<Throttle>) {
+        self.throttle = throttle;
+    }
+
+    // Sends the requests through transport instead of reqwest, e.g. canned answers to test code built
+    // on this crate without reaching Binance. Retries, throttling and the usage headers apply as usual,
+    // timeout and proxy are up to the transport. None restores reqwest
+    pub fn set_transport(&mut self, transport: Option<Arc<HttpTransport>>) {
+        self.transport = transport;
+    }
+
+    // Debug logs of every request and response on the "binance" target of the log crate, off by
+    // default. The signature is redacted, neither the API key nor the secret is ever logged
+    pub fn set_logging(&mut self, logging: bool) {
+        self.logging = logging;
+    }
+
+    // Weight used in the current minute, as of the latest response. Shared by the clones of a client
+    pub fn used_weight_1m(&self) -> Option<u32> {
+        usage_value(&self.usage.used_weight_1m)
+    }
+
+    // Orders placed in the last 10 seconds / day, as of the latest order response
+    pub fn order_count_10s(&self) -> Option<u32> {
+        usage_value(&self.usage.order_count_10s)
+    }
 
-        self.handler(response)
+    pub fn order_count_1d(&self) -> Option<u32> {
+        usage_value(&self.usage.order_count_1d)
+    }
+
+    // Measures the offset of the local clock to the server time, taking half the round trip as the
+    // transit of the answer. Signed requests add it to their timestamp from then on
+    pub fn sync_time(&self) -> Result<(i64)> {
+        let sent = get_timestamp()?;
+        let data = self.get(API_V3_TIME, "")?;
+        let received = get_timestamp()?;
+
+        let server_time: ServerTime = from_json(&data)?;
+        let offset = server_time.server_time as i64 - (sent + (received - sent) / 2) as i64;
+
+        self.time_offset.store(offset, Ordering::SeqCst);
+        if let Ok(mut last_time_sync) = self.last_time_sync.lock() {
+            *last_time_sync = Some(Instant::now());
+        }
+
+        Ok(offset)
+    }
+
+    // Offset in ms applied to the timestamp of signed requests, 0 until sync_time
+    pub fn time_offset(&self) -> i64 {
+        self.time_offset.load(Ordering::SeqCst)
+    }
+
+    // Syncs the time again before a signed request once interval has passed since the last sync
+    pub fn set_time_resync_interval(&mut self, interval: Option<Duration>) {
+        self.time_resync_interval = interval;
+    }
+
+    // Syncs the time and retries once when a signed request fails with -1021
+    pub fn set_resync_on_timestamp_error(&mut self, resync: bool) {
+        self.resync_on_timestamp_error = resync;
+    }
+
+    pub fn get_signed(&self, endpoint: &str, request: &str) -> Result<(String)> {
+        Ok(self.send_signed(Method::Get, endpoint, request)?.data)
     }
 
     pub fn post_signed(&self, endpoint: &str, request: &str) -> Result<(String)> {
-        let url = self.sign_request(endpoint, request);
-        let client = reqwest::Client::new();
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(true))
-            .send()?;
+        Ok(self.send_signed(Method::Post, endpoint, request)?.data)
+    }
 
-        self.handler(response)
+    // get_signed, post_signed, delete_signed and get with the usage headers and server time of the
+    // response
+    pub fn get_signed_with_meta(&self, endpoint: &str, request: &str) -> Result<(ApiResponse<String>)> {
+        self.send_signed(Method::Get, endpoint, request)
     }
 
-    pub fn delete_signed(&self, endpoint: &str, request: &str) -> Result<(String)> {
-        let url = self.sign_request(endpoint, request);
-        let client = reqwest::Client::new();
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(true))
-            .send()?;
+    pub fn post_signed_with_meta(&self, endpoint: &str, request: &str) -> Result<(ApiResponse<String>)> {
+        self.send_signed(Method::Post, endpoint, request)
+    }
+
+    pub fn delete_signed_with_meta(&self, endpoint: &str, request: &str) -> Result<(ApiResponse<String>)> {
+        self.send_signed(Method::Delete, endpoint, request)
+    }
 
-        self.handler(response)
+    pub fn get_with_meta(&self, endpoint: &str, request: &str) -> Result<(ApiResponse<String>)> {
+        self.with_retries(true, |_| {
+            let mut url: String = format!("{}{}", self.host, endpoint);
+            if !request.is_empty() {
+                url.push_str(format!("?{}", request).as_str());
+            }
+
+            let response = self.execute(Method::Get, &url, Headers::new(), None)?;
+
+            self.handler_with_meta(response)
+        })
+    }
+
+    // Like post_signed, but answers the status and body of a 400 or 409 too, for endpoints that detail
+    // their failures there
+    pub fn post_signed_detailed(&self, endpoint: &str, request: &str) -> Result<((u16, String))> {
+        self.send_signed_with(Method::Post, endpoint, request, |response| match response.status {
+            // A -1021 stays an error so that send_signed_with can resync and retry
+            StatusCode::BadRequest | StatusCode::Conflict if !self.is_timestamp_error(&response) => {
+                self.record_usage(&response.headers);
+                Ok((response.status.as_u16(), response.body))
+            }
+            _ => Ok((StatusCode::Ok.as_u16(), self.handler(response)?)),
+        })
+    }
+
+    pub fn delete_signed(&self, endpoint: &str, request: &str) -> Result<(String)> {
+        Ok(self.send_signed(Method::Delete, endpoint, request)?.data)
     }
 
     pub fn get(&self, endpoint: &str, request: &str) -> Result<(String)> {
-        let mut url: String = format!("{}{}", API1_HOST, endpoint);
-        if !request.is_empty() {
-            url.push_str(format!("?{}", request).as_str());
-        }
+        Ok(self.get_with_meta(endpoint, request)?.data)
+    }
+
+    // Unsigned, but with the API key header, for MARKET_DATA endpoints such as historicalTrades
+    pub fn get_with_api_key(&self, endpoint: &str, request: &str) -> Result<(String)> {
+        self.with_retries(true, |_| {
+            let mut url: String = format!("{}{}", self.host, endpoint);
+            if !request.is_empty() {
+                url.push_str(format!("?{}", request).as_str());
+            }
 
-        let response = reqwest::get(url.as_str())?;
+            let response = self.execute(Method::Get, &url, self.build_headers(false), None)?;
 
-        self.handler(response)
+            self.handler(response)
+        })
     }
 
     pub fn post(&self, endpoint: &str) -> Result<(String)> {
-        let url: String = format!("{}{}", API1_HOST, endpoint);
+        // Listen key requests, safe to repeat
+        self.with_retries(true, |_| {
+            let url: String = format!("{}{}", self.host, endpoint);
 
-        let client = reqwest::Client::new();
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(false))
-            .send()?;
+            let response = self.execute(Method::Post, &url, self.build_headers(false), None)?;
 
-        self.handler(response)
+            self.handler(response)
+        })
     }
 
     pub fn put(&self, endpoint: &str, listen_key: &str) -> Result<(String)> {
-        let url: String = format!("{}{}", API1_HOST, endpoint);
-        let data: String = format!("listenKey={}", listen_key);
+        self.with_retries(true, |_| {
+            let url: String = format!("{}{}", self.host, endpoint);
+            let data: String = format!("listenKey={}", listen_key);
 
-        let client = reqwest::Client::new();
-        let response = client
-            .put(url.as_str())
-            .headers(self.build_headers(false))
-            .body(data)
-            .send()?;
+            let response = self.execute(Method::Put, &url, self.build_headers(false), Some(data))?;
 
-        self.handler(response)
+            self.handler(response)
+        })
     }
 
     pub fn delete(&self, endpoint: &str, listen_key: &str) -> Result<(String)> {
-        let url: String = format!("{}{}", API1_HOST, endpoint);
-        let data: String = format!("listenKey={}", listen_key);
+        self.with_retries(true, |_| {
+            let url: String = format!("{}{}", self.host, endpoint);
+            let data: String = format!("listenKey={}", listen_key);
+
+            let response = self.execute(Method::Delete, &url, self.build_headers(false), Some(data))?;
+
+            self.handler(response)
+        })
+    }
+
+    // Escape hatch for the endpoints without a method of their own. The values of parameters are
+    // url encoded, the timestamp, recvWindow (unless 0) and signature added and the answer parsed
+    // into T, e.g. client.get_signed_de::<Value>("/sapi/v1/account/status", &[], 5000)
+    pub fn get_signed_de<T>(&self, endpoint: &str, parameters: &[(&str, &str)], recv_window: u64) -> Result<(T)>
+        where T: DeserializeOwned
+    {
+        let request = build_signed_request(encode_parameters(parameters), recv_window)?;
+        let data = self.get_signed(endpoint, &request)?;
+
+        from_json(&data)
+    }
+
+    pub fn post_signed_de<T>(&self, endpoint: &str, parameters: &[(&str, &str)], recv_window: u64) -> Result<(T)>
+        where T: DeserializeOwned
+    {
+        let request = build_signed_request(encode_parameters(parameters), recv_window)?;
+        let data = self.post_signed(endpoint, &request)?;
 
-        let client = reqwest::Client::new();
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(false))
-            .body(data)
-            .send()?;
+        from_json(&data)
+    }
+
+    pub fn delete_signed_de<T>(&self, endpoint: &str, parameters: &[(&str, &str)], recv_window: u64) -> Result<(T)>
+        where T: DeserializeOwned
+    {
+        let request = build_signed_request(encode_parameters(parameters), recv_window)?;
+        let data = self.delete_signed(endpoint, &request)?;
+
+        from_json(&data)
+    }
+
+    // Unsigned counterpart of get_signed_de, e.g. client.get_de::<Value>("/api/v3/depth", &[("symbol", "BNBBTC")])
+    pub fn get_de<T>(&self, endpoint: &str, parameters: &[(&str, &str)]) -> Result<(T)>
+        where T: DeserializeOwned
+    {
+        let data = self.get(endpoint, &build_request(&encode_parameters(parameters)))?;
+
+        from_json(&data)
+    }
+
+    // Unsigned with the API key header, for MARKET_DATA endpoints
+    pub fn get_with_api_key_de<T>(&self, endpoint: &str, parameters: &[(&str, &str)]) -> Result<(T)>
+        where T: DeserializeOwned
+    {
+        let data = self.get_with_api_key(endpoint, &build_request(&encode_parameters(parameters)))?;
+
+        from_json(&data)
+    }
+
+    // The REST host, e.g. "https://api.binance.com"
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) fn proxy(&self) -> Option<&reqwest::Proxy> {
+        self.proxy.as_ref()
+    }
+
+    // Whether the requests go through a proxy with credentials
+    #[cfg(feature = "tokio")]
+    pub(crate) fn is_tunneled(&self) -> bool {
+        self.tunnel.is_some()
+    }
 
-        self.handler(response)
+    pub fn api_key(&self) -> &str {
+        &self.api_key
     }
 
-    // Request must be signed
-    fn sign_request(&self, endpoint: &str, request: &str) -> String {
+    // Hex HMAC-SHA256 of a request payload with the secret key
+    pub fn signature(&self, request: &str) -> String {
         let signed_key = hmac::SigningKey::new(&digest::SHA256, self.secret_key.as_bytes());
-        let signature = hex_encode(hmac::sign(&signed_key, request.as_bytes()).as_ref());
+
+        hex_encode(hmac::sign(&signed_key, request.as_bytes()).as_ref())
+    }
+
+    // request with the time offset added to its timestamp
+    pub fn apply_time_offset(&self, request: &str) -> String {
+        shift_timestamp(request, self.time_offset())
+    }
+
+    fn send_signed(&self, method: Method, endpoint: &str, request: &str) -> Result<(ApiResponse<String>)> {
+        self.send_signed_with(method, endpoint, request, |response| self.handler_with_meta(response))
+    }
+
+    // send_signed with the answer made of the response by answer
+    fn send_signed_with<T, F>(&self, method: Method, endpoint: &str, request: &str, answer: F) -> Result<(T)>
+    where
+        F: Fn(HttpResponse) -> Result<(T)>,
+    {
+        self.resync_time_if_due();
+
+        // Only queries are retried on server errors, an order may have gone through
+        let idempotent = method == Method::Get;
+        let send = |waited: Duration| {
+            let url = self.sign_request(endpoint, request, waited);
+            let response = self.execute(method.clone(), &url, self.build_headers(true), None)?;
+
+            answer(response)
+        };
+
+        match self.with_retries(idempotent, send) {
+            Err(BinanceError::Api { code, .. })
+                if code == TIMESTAMP_ERROR_CODE && self.resync_on_timestamp_error =>
+            {
+                self.sync_time()?;
+                self.with_retries(idempotent, send)
+            }
+            answer => answer,
+        }
+    }
+
+    fn is_timestamp_error(&self, response: &HttpResponse) -> bool {
+        self.resync_on_timestamp_error
+            && binance_error(response.status.as_u16(), &response.body)
+                .map_or(false, |error| error.code == TIMESTAMP_ERROR_CODE)
+    }
+
+    fn execute(&self, method: Method, url: &str, headers: Headers, body: Option<String>) -> Result<(HttpResponse)> {
+        let sent = self.log_request(&method, url);
+        let response = match self.transport {
+            Some(ref transport) => transport.send(method.clone(), url, headers, body)?,
+            None => match self.tunnel {
+                Some(ref tunnel) => tunnel.send(method.clone(), url, headers, body)?,
+                None => self.send_http(method.clone(), url, headers, body)?,
+            },
+        };
+        self.log_response(&method, url, response.status, &response.headers, sent);
+
+        Ok(response)
+    }
+
+    fn send_http(&self, method: Method, url: &str, headers: Headers, body: Option<String>) -> Result<(HttpResponse)> {
+        let mut request = self.http.request(method, url);
+        request.headers(headers);
+        if let Some(body) = body {
+            request.body(body);
+        }
+
+        let mut response = request.send().map_err(|e| self.request_error(e))?;
+        // Rate limit answers are told apart by their status and Retry-After alone, a ban by its message too
+        let body = match response.status() {
+            StatusCode::TooManyRequests => String::new(),
+            _ => self.read_body(&mut response)?,
+        };
+
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body,
+        })
+    }
+
+    // When the request was sent, for the latency of its response
+    pub(crate) fn log_request(&self, method: &Method, url: &str) -> Instant {
+        if self.logging {
+            let (endpoint, query) = self.loggable(url);
+            debug!(
+                target: LOG_TARGET,
+                method = method.as_ref(), endpoint = endpoint, query = query.as_str();
+                "request"
+            );
+        }
+
+        Instant::now()
+    }
+
+    pub(crate) fn log_response(
+        &self, method: &Method, url: &str, status: StatusCode, headers: &Headers, sent: Instant,
+    ) {
+        if self.logging {
+            let (endpoint, _) = self.loggable(url);
+            let latency = sent.elapsed();
+            let latency_ms = latency.as_secs() * 1000 + u64::from(latency.subsec_nanos()) / 1_000_000;
+            let used_weight_1m = header_value(headers, USED_WEIGHT_1M_HEADER).unwrap_or(0);
+            debug!(
+                target: LOG_TARGET,
+                method = method.as_ref(), endpoint = endpoint, status = status.as_u16(),
+                latency_ms = latency_ms, used_weight_1m = used_weight_1m;
+                "response"
+            );
+        }
+    }
+
+    // For failures nobody can be told about otherwise, e.g. in Drop
+    pub(crate) fn log_failure(&self, action: &str, error: &Error) {
+        if self.logging {
+            warn!(target: LOG_TARGET, action = action, error = error.to_string().as_str(); "failure");
+        }
+    }
+
+    // Endpoint and query of url, with the signature redacted
+    fn loggable<'a>(&self, url: &'a str) -> (&'a str, String) {
+        let url = url.trim_start_matches(self.host.as_str());
+        let mut parts = url.splitn(2, '?');
+        let endpoint = parts.next().unwrap_or("");
+        let query: Vec<String> = parts
+            .next()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                if pair.starts_with("signature=") {
+                    format!("signature={}", REDACTED)
+                } else {
+                    pair.to_string()
+                }
+            })
+            .collect();
+
+        (endpoint, query.join("&"))
+    }
+
+    // Sends until the answer is not worth a retry under the retry policy. send gets the time
+    // waited since the first attempt, signed requests move their timestamp by it
+    fn with_retries<T, F>(&self, idempotent: bool, send: F) -> Result<(T)>
+        where F: Fn(Duration) -> Result<(T)>
+    {
+        let started = Instant::now();
+        let mut retries = 0;
+
+        loop {
+            self.wait_for_weight();
+            let error = match send(started.elapsed()) {
+                Err(error) => error,
+                answer => return answer,
+            };
+
+            let delay = match self.retry_policy {
+                Some(ref policy) if retries < policy.max_retries => policy.delay(retries, &error, idempotent),
+                _ => None,
+            };
+            match delay {
+                Some(delay) => {
+                    retries += 1;
+                    self.retries.fetch_add(1, Ordering::SeqCst);
+                    if let Some(ref on_retry) = self.retry_policy.as_ref().and_then(|policy| policy.on_retry.clone()) {
+                        on_retry(retries, delay, &error);
+                    }
+                    thread::sleep(delay);
+                }
+                None if !idempotent && error.is_unanswered() => {
+                    return Err(BinanceError::OutcomeUnknown(Box::new(error)));
+                }
+                None => return Err(error),
+            }
+        }
+    }
+
+    fn record_usage(&self, headers: &Headers) {
+        if let Some(weight) = header_value(headers, USED_WEIGHT_1M_HEADER) {
+            self.usage.used_weight_1m.store(weight, Ordering::SeqCst);
+            self.usage.weight_minute.store(self.server_minute(), Ordering::SeqCst);
+        }
+        if let Some(count) = header_value(headers, ORDER_COUNT_10S_HEADER) {
+            self.usage.order_count_10s.store(count, Ordering::SeqCst);
+        }
+        if let Some(count) = header_value(headers, ORDER_COUNT_1D_HEADER) {
+            self.usage.order_count_1d.store(count, Ordering::SeqCst);
+        }
+    }
+
+    // Sleeps until the next minute when the throttle is on and the weight of this minute is spent
+    fn wait_for_weight(&self) {
+        let throttle = match self.throttle {
+            Some(throttle) => throttle,
+            None => return,
+        };
+
+        let used_weight = self.usage.used_weight_1m.load(Ordering::SeqCst);
+        let now = self.server_time();
+        if used_weight != UNKNOWN
+            && self.usage.weight_minute.load(Ordering::SeqCst) == now / MINUTE_MS
+            && throttle.is_exceeded(used_weight)
+        {
+            thread::sleep(Duration::from_millis(MINUTE_MS - now % MINUTE_MS));
+        }
+    }
+
+    // Local time corrected by the offset of sync_time, in ms
+    fn server_time(&self) -> u64 {
+        (get_timestamp().unwrap_or(0) as i64 + self.time_offset()) as u64
+    }
+
+    fn server_minute(&self) -> u64 {
+        self.server_time() / MINUTE_MS
+    }
+
+    fn read_body(&self, response: &mut Response) -> Result<(String)> {
+        let mut body = String::new();
+        match response.read_to_string(&mut body) {
+            Ok(_) => Ok(body),
+            Err(ref e) if e.kind() == IoErrorKind::TimedOut => bail!(BinanceError::Timeout { timeout: self.timeout }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub(crate) fn request_error(&self, error: reqwest::Error) -> Error {
+        if is_timeout(&error) {
+            BinanceError::Timeout { timeout: self.timeout }
+        } else {
+            error.into()
+        }
+    }
+
+    fn resync_time_if_due(&self) {
+        if let Some(interval) = self.time_resync_interval {
+            let due = match self.last_time_sync.lock() {
+                Ok(last_time_sync) => last_time_sync.map_or(true, |last| last.elapsed() >= interval),
+                Err(_) => false,
+            };
+            // A failed sync keeps the previous offset, the request itself reports the trouble
+            if due {
+                let _ = self.sync_time();
+            }
+        }
+    }
+
+    // Request must be signed, the time offset and the time waited for retries apply to its timestamp
+    pub(crate) fn sign_request(&self, endpoint: &str, request: &str, waited: Duration) -> String {
+        let waited = waited.as_secs() as i64 * 1000 + i64::from(waited.subsec_nanos()) / 1_000_000;
+        let request = shift_timestamp(request, self.time_offset() + waited);
+        let signature = self.signature(&request);
 
         let request_body: String = format!("{}&signature={}", request, signature);
-        let url: String = format!("{}{}?{}", API1_HOST, endpoint, request_body);
+        let url: String = format!("{}{}?{}", self.host, endpoint, request_body);
 
         url
     }
 
-    fn build_headers(&self, content_type: bool) -> Headers {
+    pub(crate) fn build_headers(&self, content_type: bool) -> Headers {
         let mut custon_headers = Headers::new();
 
         custon_headers.set(UserAgent::new("binance-rs"));
@@ -129,28 +849,459 @@ impl Client {
         custon_headers
     }
 
-    fn handler(&self, mut response: Response) -> Result<(String)> {
-        match response.status() {
-            StatusCode::Ok => {
-                let mut body = String::new();
-                response.read_to_string(&mut body)?;
-                Ok(body)
+    fn handler(&self, response: HttpResponse) -> Result<(String)> {
+        self.answer(response.status, &response.headers, response.body)
+    }
+
+    fn handler_with_meta(&self, response: HttpResponse) -> Result<(ApiResponse<String>)> {
+        let HttpResponse { status, headers, body } = response;
+        let data = self.answer(status, &headers, body)?;
+
+        Ok(ApiResponse {
+            data,
+            used_weight_1m: header_value(&headers, USED_WEIGHT_1M_HEADER).map(|weight| weight as u32),
+            order_count_10s: header_value(&headers, ORDER_COUNT_10S_HEADER).map(|count| count as u32),
+            order_count_1d: header_value(&headers, ORDER_COUNT_1D_HEADER).map(|count| count as u32),
+            server_time: server_time(&headers),
+            status: status.as_u16(),
+        })
+    }
+
+    // Body of a response, or the error its status stands for. Shared with the async client
+    pub(crate) fn answer(&self, status: StatusCode, headers: &Headers, body: String) -> Result<(String)> {
+        self.record_usage(headers);
+        if self.logging && status != StatusCode::Ok {
+            debug!(target: LOG_TARGET, status = status.as_u16(), body = body.as_str(); "error body");
+        }
+
+        match status {
+            StatusCode::Ok => Ok(body),
+            StatusCode::TooManyRequests => {
+                bail!(BinanceError::TooManyRequests { retry_after: retry_after(headers) });
             }
-            StatusCode::InternalServerError => {
-                bail!("Internal Server Error");
+            StatusCode::ImATeapot => {
+                let retry_after = retry_after(headers)
+                    .or_else(|| binance_error(status.as_u16(), &body).and_then(|error| ban_expiry(&error.msg)));
+                bail!(BinanceError::IpBanned { retry_after });
             }
             StatusCode::ServiceUnavailable => {
-                bail!("Service Unavailable");
+                bail!(BinanceError::ServiceUnavailable { body });
             }
-            StatusCode::Unauthorized => {
-                bail!("Unauthorized");
-            }
-            StatusCode::BadRequest => {
-                bail!(format!("Bad Request: {:?}", response));
+            s if s.is_server_error() => {
+                bail!(BinanceError::Server { status: s.as_u16(), body });
             }
             s => {
-                bail!(format!("Received response: {:?}", s));
+                if let Some(error) = binance_error(s.as_u16(), &body) {
+                    bail!(error);
+                }
+                bail!(BinanceError::Status { status: s.as_u16(), body })
+            }
+        }
+    }
+}
+
+// Binance details the 4xx failures in a {"code":-2010,"msg":"..."} body
+pub fn binance_error(status: u16, body: &str) -> Option<BinanceContentError> {
+    from_str::<BinanceContentError>(body).ok().map(|error| BinanceContentError { status, ..error })
+}
+
+fn http_client(timeout: Option<Duration>, proxy: Option<reqwest::Proxy>) -> Result<(reqwest::Client)> {
+    let mut builder = reqwest::Client::builder();
+    builder.timeout(timeout);
+    if let Some(proxy) = proxy {
+        builder.proxy(proxy);
+    }
+
+    Ok(builder.build()?)
+}
+
+// reqwest reports its timeout as an io::Error of kind TimedOut
+fn is_timeout(error: &reqwest::Error) -> bool {
+    error
+        .get_ref()
+        .and_then(|error| error.downcast_ref::<io::Error>())
+        .map_or(false, |error| error.kind() == IoErrorKind::TimedOut)
+}
+
+// Seconds of the Retry-After header of 429 and 418 answers
+fn retry_after(headers: &Headers) -> Option<Duration> {
+    headers
+        .get_raw("Retry-After")
+        .and_then(|raw| raw.one())
+        .and_then(|value| str::from_utf8(value).ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+fn header_value(headers: &Headers, name: &str) -> Option<u64> {
+    headers
+        .get_raw(name)
+        .and_then(|raw| raw.one())
+        .and_then(|value| str::from_utf8(value).ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+fn encode_parameters(parameters: &[(&str, &str)]) -> BTreeMap<String, String> {
+    parameters
+        .iter()
+        .map(|&(key, value)| (key.to_string(), byte_serialize(value.as_bytes()).collect()))
+        .collect()
+}
+
+fn server_time(headers: &Headers) -> Option<u64> {
+    let date = SystemTime::from(headers.get::<Date>()?.0);
+
+    date.duration_since(UNIX_EPOCH).ok().map(|since_epoch| since_epoch.as_secs() * 1000)
+}
+
+fn usage_value(value: &AtomicU64) -> Option<u32> {
+    match value.load(Ordering::SeqCst) {
+        value if value == UNKNOWN => None,
+        value => Some(value as u32),
+    }
+}
+
+// How long after its timestamp a signed request is accepted, at most 60000 ms. The set_recv_window
+// of Account, Market, Margin and the others keep it in their recv_window through this
+pub(crate) fn update_recv_window(recv_window: &mut u64, value: u64) -> Result<()> {
+    check_recv_window(value)?;
+    *recv_window = value;
+
+    Ok(())
+}
+
+fn shift_timestamp(request: &str, shift: i64) -> String {
+    if shift == 0 {
+        return request.into();
+    }
+
+    let pairs: Vec<String> = request
+        .split('&')
+        .map(|pair| match pair.split_at(pair.find('=').unwrap_or(0)) {
+            ("timestamp", timestamp) => match timestamp[1..].parse::<i64>() {
+                Ok(timestamp) => format!("timestamp={}", timestamp + shift),
+                Err(_) => pair.into(),
+            },
+            _ => pair.into(),
+        })
+        .collect();
+
+    pairs.join("&")
+}
+
+#[cfg(test)]
+pub(crate) mod fake;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use self::fake::{answer, client, FakeTransport};
+
+    // Example of the Binance API documentation, SIGNED endpoint security
+    static DOC_SECRET: &'static str = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+    static DOC_QUERY: &'static str = concat!(
+        "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1",
+        "&recvWindow=5000&timestamp=1499827319559"
+    );
+    static DOC_SIGNATURE: &'static str = "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71";
+
+    #[test]
+    fn signature_of_the_documentation_example() {
+        let client = Client::new(None, Some(DOC_SECRET.into()));
+
+        assert_eq!(client.signature(DOC_QUERY), DOC_SIGNATURE);
+    }
+
+    #[test]
+    fn signed_url_ends_with_the_signature() {
+        let client = Client::new(None, Some(DOC_SECRET.into()));
+
+        assert_eq!(
+            client.sign_request("/api/v3/order", DOC_QUERY, Duration::from_secs(0)),
+            format!("https://api.binance.com/api/v3/order?{}&signature={}", DOC_QUERY, DOC_SIGNATURE)
+        );
+    }
+
+    fn error_of(answer: HttpResponse) -> BinanceError {
+        let transport = FakeTransport::new(vec![answer]);
+
+        match client(&transport).get("/api/v3/depth", "symbol=LTCBTC") {
+            Err(error) => error,
+            Ok(data) => panic!("unexpected answer {}", data),
+        }
+    }
+
+    #[test]
+    fn binance_error_of_a_4xx() {
+        match error_of(answer(400, r#"{"code":-1121,"msg":"Invalid symbol."}"#)) {
+            BinanceError::Api { status: 400, code: -1121, ref msg } => assert_eq!(msg, "Invalid symbol."),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rate_limited_with_retry_after() {
+        let mut rate_limited = answer(429, "");
+        rate_limited.headers.set_raw("Retry-After", "7");
+
+        match error_of(rate_limited) {
+            BinanceError::TooManyRequests { retry_after } => assert_eq!(retry_after, Some(Duration::from_secs(7))),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ip_ban_until_the_time_of_its_message() {
+        let until = get_timestamp().unwrap() + 120_000;
+        let body = format!(r#"{{"code":-1003,"msg":"Way too many requests; IP banned until {}."}}"#, until);
+
+        match error_of(answer(418, &body)) {
+            BinanceError::IpBanned { retry_after: Some(retry_after) } => {
+                assert!(retry_after > Duration::from_secs(100) && retry_after <= Duration::from_secs(120));
             }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn server_errors() {
+        match error_of(answer(503, "Service Unavailable")) {
+            BinanceError::ServiceUnavailable { ref body } => assert_eq!(body, "Service Unavailable"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match error_of(answer(502, "Bad Gateway")) {
+            BinanceError::Server { status: 502, .. } => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn status_without_a_binance_error() {
+        match error_of(answer(404, "<html>Not Found</html>")) {
+            BinanceError::Status { status: 404, ref body } => assert_eq!(body, "<html>Not Found</html>"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn usage_headers_of_the_answer() {
+        let mut ok = answer(200, "{}");
+        ok.headers.set_raw(USED_WEIGHT_1M_HEADER, "42");
+        let transport = FakeTransport::new(vec![ok]);
+        let client = client(&transport);
+
+        let answer = client.get_with_meta("/api/v3/ping", "").unwrap();
+        assert_eq!(answer.used_weight_1m, Some(42));
+        assert_eq!(client.used_weight_1m(), Some(42));
+        assert_eq!(transport.requests(), vec![(Method::Get, "https://api.binance.com/api/v3/ping".to_string())]);
+    }
+
+    #[test]
+    fn detailed_post_resyncs_on_a_timestamp_error() {
+        let transport = FakeTransport::new(vec![
+            answer(400, r#"{"code":-1021,"msg":"Timestamp for this request is outside of the recvWindow."}"#),
+            answer(200, r#"{"serverTime":1499827319559}"#),
+            answer(409, r#"{"code":-2021,"msg":"Order cancel-replace partially failed."}"#),
+        ]);
+        let mut client = client(&transport);
+        client.set_resync_on_timestamp_error(true);
+
+        let (status, body) = client.post_signed_detailed("/api/v3/order/cancelReplace", "symbol=BNBBTC").unwrap();
+        assert_eq!(status, 409);
+        assert!(body.contains("-2021"));
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1], (Method::Get, "https://api.binance.com/api/v3/time".to_string()));
+        assert!(requests[2].1.starts_with("https://api.binance.com/api/v3/order/cancelReplace?symbol=BNBBTC"));
+    }
+
+    fn retrying(base_delay: Duration) -> (RetryPolicy, Arc<Mutex<Vec<(u32, Duration)>>>) {
+        let retries = Arc::new(Mutex::new(Vec::new()));
+        let recorded = retries.clone();
+        let policy = RetryPolicy { base_delay, max_delay: base_delay * 2, ..RetryPolicy::default() }
+            .on_retry(move |retry, delay, _| recorded.lock().unwrap().push((retry, delay)));
+
+        (policy, retries)
+    }
+
+    #[test]
+    fn rate_limited_requests_wait_for_retry_after_or_back_off() {
+        let mut told = answer(429, "");
+        told.headers.set_raw("Retry-After", "0");
+        let transport = FakeTransport::new(vec![told, answer(429, ""), answer(200, "{}")]);
+        let mut client = client(&transport);
+        let (policy, retries) = retrying(Duration::from_millis(4));
+        client.set_retry_policy(Some(policy));
+
+        assert_eq!(client.get("/api/v3/ping", "").unwrap(), "{}");
+        let retries = retries.lock().unwrap().clone();
+        assert_eq!(retries.len(), 2);
+        assert_eq!(retries[0], (1, Duration::from_secs(0)));
+        // Second retry: twice the base delay, capped at max_delay and jittered down to half of it
+        assert_eq!(retries[1].0, 2);
+        assert!(retries[1].1 >= Duration::from_millis(4) && retries[1].1 <= Duration::from_millis(8));
+        assert_eq!(client.retry_count(), 2);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn ip_ban_is_not_retried() {
+        let transport = FakeTransport::new(vec![answer(418, r#"{"code":-1003,"msg":"Way too many requests"}"#)]);
+        let mut client = client(&transport);
+        let (policy, retries) = retrying(Duration::from_millis(1));
+        client.set_retry_policy(Some(policy));
+
+        match client.get("/api/v3/ping", "") {
+            Err(BinanceError::IpBanned { .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(retries.lock().unwrap().is_empty());
+        assert_eq!(client.retry_count(), 0);
+    }
+
+    #[test]
+    fn server_errors_are_retried_for_queries_only() {
+        let transport =
+            FakeTransport::new(vec![answer(502, "Bad Gateway"), answer(200, "{}"), answer(502, "Bad Gateway")]);
+        let mut client = client(&transport);
+        let (policy, retries) = retrying(Duration::from_millis(1));
+        client.set_retry_policy(Some(policy));
+
+        assert_eq!(client.get("/api/v3/ping", "").unwrap(), "{}");
+        assert_eq!(retries.lock().unwrap().len(), 1);
+
+        // The order may have gone through
+        match client.post_signed("/api/v3/order", "symbol=LTCBTC") {
+            Err(BinanceError::OutcomeUnknown(ref error)) => match **error {
+                BinanceError::Server { status: 502, .. } => {}
+                ref other => panic!("unexpected {:?}", other),
+            },
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(retries.lock().unwrap().len(), 1);
+        assert_eq!(client.retry_count(), 1);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    static TIMESTAMP_ERROR: &'static str = r#"{"code":-1021,"msg":"Timestamp for this request was 1000ms ahead of the server's time."}"#;
+
+    #[test]
+    fn shift_timestamp_only_moves_the_timestamp() {
+        assert_eq!(
+            shift_timestamp("symbol=LTCBTC&timestamp=1000&recvWindow=5000", -250),
+            "symbol=LTCBTC&timestamp=750&recvWindow=5000"
+        );
+        assert_eq!(shift_timestamp("limit=1000&timestamp=1000", 0), "limit=1000&timestamp=1000");
+        assert_eq!(shift_timestamp("limit=1000", 42), "limit=1000");
+    }
+
+    #[test]
+    fn signed_requests_carry_the_synced_offset() {
+        let ahead = get_timestamp().unwrap() + 600_000;
+        let transport = FakeTransport::new(vec![
+            answer(200, &format!(r#"{{"serverTime":{}}}"#, ahead)),
+            answer(200, "{}"),
+        ]);
+        let client = client(&transport);
+        let offset = client.sync_time().unwrap();
+        assert!(offset > 590_000 && offset <= 600_000);
+
+        client.get_signed("/api/v3/account", "timestamp=1000").unwrap();
+        let url = Url::parse(&transport.requests()[1].1).unwrap();
+        let pairs: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+        let timestamp: i64 = pairs["timestamp"].parse().unwrap();
+        // Plus the milliseconds spent before sending, none here
+        assert!(timestamp - 1000 - offset >= 0 && timestamp - 1000 - offset < 50);
+        assert_eq!(pairs["signature"], client.signature(&format!("timestamp={}", timestamp)));
+    }
+
+    #[test]
+    fn timestamp_error_is_resynced_and_retried_once() {
+        let time = || answer(200, r#"{"serverTime":1499827319559}"#);
+        let transport = FakeTransport::new(vec![
+            answer(400, TIMESTAMP_ERROR),
+            time(),
+            answer(200, "{}"),
+            answer(400, TIMESTAMP_ERROR),
+            time(),
+            answer(400, TIMESTAMP_ERROR),
+        ]);
+        let mut client = client(&transport);
+        client.set_resync_on_timestamp_error(true);
+
+        assert_eq!(client.get_signed("/api/v3/account", "timestamp=1000").unwrap(), "{}");
+        match client.get_signed("/api/v3/account", "timestamp=1000") {
+            Err(BinanceError::Api { code: -1021, .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        let paths: Vec<String> = transport
+            .requests()
+            .into_iter()
+            .map(|(_, url)| Url::parse(&url).unwrap().path().to_string())
+            .collect();
+        let account = "/api/v3/account".to_string();
+        let time = "/api/v3/time".to_string();
+        assert_eq!(paths, vec![account.clone(), time.clone(), account.clone(), account.clone(), time, account]);
+    }
+
+    #[test]
+    fn timestamp_error_without_resync() {
+        let transport = FakeTransport::new(vec![answer(400, TIMESTAMP_ERROR)]);
+        let client = client(&transport);
+
+        match client.get_signed("/api/v3/account", "timestamp=1000") {
+            Err(BinanceError::Api { code: -1021, .. }) => {}
+            other => panic!("unexpected {:?}", other),
         }
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn order_count_and_weight_headers_are_kept() {
+        let mut placed = answer(200, "{}");
+        placed.headers.set_raw(USED_WEIGHT_1M_HEADER, "12");
+        placed.headers.set_raw(ORDER_COUNT_10S_HEADER, "3");
+        placed.headers.set_raw(ORDER_COUNT_1D_HEADER, "40");
+        let mut queried = answer(200, "{}");
+        queried.headers.set_raw(USED_WEIGHT_1M_HEADER, "14");
+        let transport = FakeTransport::new(vec![placed, queried]);
+        let client = client(&transport);
+        assert_eq!((client.used_weight_1m(), client.order_count_10s(), client.order_count_1d()), (None, None, None));
+
+        let answer = client.post_signed_with_meta("/api/v3/order", "symbol=LTCBTC").unwrap();
+        let counts = (answer.used_weight_1m, answer.order_count_10s, answer.order_count_1d);
+        assert_eq!(counts, (Some(12), Some(3), Some(40)));
+        assert_eq!(client.usage.weight_minute.load(Ordering::SeqCst), client.server_minute());
+
+        // A query carries no order counts, the ones of the last order stay
+        client.get("/api/v3/ping", "").unwrap();
+        assert_eq!(client.usage.used_weight_1m.load(Ordering::SeqCst), 14);
+        assert_eq!(client.usage.order_count_10s.load(Ordering::SeqCst), 3);
+        assert_eq!(client.usage.order_count_1d.load(Ordering::SeqCst), 40);
+    }
+
+    #[test]
+    fn throttle_is_exceeded_at_its_fraction_of_the_limit() {
+        let throttle = Throttle::new(1200, 0.8);
+
+        assert!(!throttle.is_exceeded(0));
+        assert!(!throttle.is_exceeded(959));
+        assert!(throttle.is_exceeded(960));
+        assert!(Throttle::new(1200, 1.0).is_exceeded(1200));
+    }
+
+    #[test]
+    fn throttle_lets_requests_under_the_limit_through() {
+        let mut used = answer(200, "{}");
+        used.headers.set_raw(USED_WEIGHT_1M_HEADER, "959");
+        let transport = FakeTransport::new(vec![used, answer(200, "{}")]);
+        let mut client = client(&transport);
+        client.set_throttle(Some(Throttle::new(1200, 0.8)));
+
+        let started = Instant::now();
+        client.get("/api/v3/ping", "").unwrap();
+        client.get("/api/v3/ping", "").unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(client.used_weight_1m(), Some(959));
     }
 }
@@ -0,0 +1,49 @@
+// Transport of the tests, answering canned responses in turn instead of reaching Binance
+use super::*;
+use std::collections::VecDeque;
+
+pub struct FakeTransport {
+    answers: Mutex<VecDeque<HttpResponse>>,
+    // Method and url of every request sent
+    requests: Mutex<Vec<(Method, String)>>,
+}
+
+impl FakeTransport {
+    pub fn new(answers: Vec<HttpResponse>) -> Arc<FakeTransport> {
+        Arc::new(FakeTransport {
+            answers: Mutex::new(answers.into_iter().collect()),
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn requests(&self) -> Vec<(Method, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for FakeTransport {
+    fn send(&self, method: Method, url: &str, _headers: Headers, _body: Option<String>) -> Result<(HttpResponse)> {
+        self.requests.lock().unwrap().push((method, url.into()));
+
+        match self.answers.lock().unwrap().pop_front() {
+            Some(answer) => Ok(answer),
+            None => panic!("no answer left for {}", url),
+        }
+    }
+}
+
+pub fn answer(status: u16, body: &str) -> HttpResponse {
+    HttpResponse {
+        status: StatusCode::try_from(status).unwrap(),
+        headers: Headers::new(),
+        body: body.into(),
+    }
+}
+
+// Client with keys sending through transport
+pub fn client(transport: &Arc<FakeTransport>) -> Client {
+    let mut client = Client::new(Some("api-key".into()), Some("secret-key".into()));
+    client.set_transport(Some(transport.clone()));
+
+    client
+}
@@ -0,0 +1,134 @@
+use errors::*;
+use client::Client;
+use futures::{future, Future, Stream};
+use reqwest::Method;
+use reqwest::unstable::async::{Client as HttpClient, Response};
+use std::io;
+use std::str;
+use std::time::{Duration, Instant};
+use tokio_core::reactor::Handle;
+use serde::de::DeserializeOwned;
+
+pub type ResponseFuture<T> = Box<Future<Item = T, Error = Error>>;
+
+// Futures based counterpart of Client on a tokio-core reactor. Keys, host, timeout, proxy, time
+// offset and usage headers are those of the Client it is made from. Its clones share one
+// connection pool and run their requests concurrently, nothing is locked between them.
+// Retries, throttling and time resyncs of the blocking Client do not apply
+#[derive(Clone)]
+pub struct AsyncClient {
+    client: Client,
+    http: HttpClient,
+}
+
+impl AsyncClient {
+    // Fails on a client behind a proxy with credentials, the async reqwest client cannot send them
+    pub fn new(client: &Client, handle: &Handle) -> Result<(AsyncClient)> {
+        if client.is_tunneled() {
+            bail!(BinanceError::Parameter(
+                "Proxy credentials are not supported by the async REST client".into(),
+            ));
+        }
+
+        let mut builder = HttpClient::builder();
+        if let Some(timeout) = client.timeout() {
+            builder.timeout(timeout);
+        }
+        if let Some(proxy) = client.proxy() {
+            builder.proxy(proxy.clone());
+        }
+
+        Ok(AsyncClient {
+            client: client.clone(),
+            http: builder.build(handle)?,
+        })
+    }
+
+    // The blocking Client this one shares its settings with
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn get_signed(&self, endpoint: &str, request: &str) -> ResponseFuture<String> {
+        self.send_signed(Method::Get, endpoint, request)
+    }
+
+    pub fn post_signed(&self, endpoint: &str, request: &str) -> ResponseFuture<String> {
+        self.send_signed(Method::Post, endpoint, request)
+    }
+
+    pub fn delete_signed(&self, endpoint: &str, request: &str) -> ResponseFuture<String> {
+        self.send_signed(Method::Delete, endpoint, request)
+    }
+
+    pub fn get(&self, endpoint: &str, request: &str) -> ResponseFuture<String> {
+        let mut url: String = format!("{}{}", self.client.host(), endpoint);
+        if !request.is_empty() {
+            url.push_str(format!("?{}", request).as_str());
+        }
+
+        let sent = self.client.log_request(&Method::Get, &url);
+        let response = self.http.get(url.as_str()).send();
+
+        self.handler(Method::Get, url, sent, response)
+    }
+
+    // get, parsed into T
+    pub fn get_json<T>(&self, endpoint: &str, request: &str) -> ResponseFuture<T>
+        where T: DeserializeOwned + 'static
+    {
+        Box::new(self.get(endpoint, request).and_then(|data| from_json(&data)))
+    }
+
+    // get_signed, parsed into T
+    pub fn get_signed_json<T>(&self, endpoint: &str, request: &str) -> ResponseFuture<T>
+        where T: DeserializeOwned + 'static
+    {
+        Box::new(self.get_signed(endpoint, request).and_then(|data| from_json(&data)))
+    }
+
+    fn send_signed(&self, method: Method, endpoint: &str, request: &str) -> ResponseFuture<String> {
+        let url = self.client.sign_request(endpoint, request, Duration::from_secs(0));
+        let sent = self.client.log_request(&method, &url);
+        let response = self
+            .http
+            .request(method.clone(), url.as_str())
+            .headers(self.client.build_headers(true))
+            .send();
+
+        self.handler(method, url, sent, response)
+    }
+
+    fn handler<F>(&self, method: Method, url: String, sent: Instant, response: F) -> ResponseFuture<String>
+        where F: Future<Item = Response, Error = ::reqwest::Error> + 'static
+    {
+        let client = self.client.clone();
+        let body_client = self.client.clone();
+
+        Box::new(
+            response
+                .map_err(move |e| client.request_error(e))
+                .and_then(move |response| {
+                    let status = response.status();
+                    let headers = response.headers().clone();
+                    body_client.log_response(&method, &url, status, &headers, sent);
+
+                    response
+                        .into_body()
+                        .concat2()
+                        .map_err(|e| e.into())
+                        .and_then(move |body| {
+                            let body = match str::from_utf8(&body) {
+                                Ok(body) => body.to_string(),
+                                Err(e) => {
+                                    let e = io::Error::new(io::ErrorKind::InvalidData, e);
+                                    return future::err(e.into());
+                                }
+                            };
+
+                            future::result(body_client.answer(status, &headers, body))
+                        })
+                }),
+        )
+    }
+}
@@ -0,0 +1,160 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use usdm_futures::{order_parameters, FuturesMarginType, FuturesOrderRequest};
+use std::collections::BTreeMap;
+
+static DAPI_V1_EXCHANGE_INFO: &'static str = "/dapi/v1/exchangeInfo";
+static DAPI_V1_PREMIUM_INDEX: &'static str = "/dapi/v1/premiumIndex";
+static DAPI_V1_ACCOUNT: &'static str = "/dapi/v1/account";
+static DAPI_V1_POSITION_RISK: &'static str = "/dapi/v1/positionRisk";
+static DAPI_V1_LEVERAGE: &'static str = "/dapi/v1/leverage";
+static DAPI_V1_MARGIN_TYPE: &'static str = "/dapi/v1/marginType";
+static DAPI_V1_ORDER: &'static str = "/dapi/v1/order";
+static DAPI_V1_OPEN_ORDERS: &'static str = "/dapi/v1/openOrders";
+
+// COIN-M (delivery) futures endpoints, on dapi.binance.com (see Config::coin_futures_api_endpoint).
+// Contracts are margined in their base asset and quantities count contracts, see
+// CoinFuturesSymbol::contract_size
+#[derive(Clone)]
+pub struct CoinFutures {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl CoinFutures {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    pub fn get_exchange_info(&self) -> Result<(CoinFuturesExchangeInformation)> {
+        let data = self.client.get(DAPI_V1_EXCHANGE_INFO, "")?;
+        let info: CoinFuturesExchangeInformation = from_json(&data)?;
+
+        Ok(info)
+    }
+
+    // Mark prices of every contract, or of the contracts of one pair (e.g. "BTCUSD")
+    pub fn get_mark_prices(&self, pair: Option<&str>) -> Result<(Vec<CoinFuturesMarkPrice>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(pair) = pair {
+            parameters.insert("pair".into(), pair.into());
+        }
+
+        let request = build_request(&parameters);
+        let data = self.client.get(DAPI_V1_PREMIUM_INDEX, &request)?;
+        let mark_prices: Vec<CoinFuturesMarkPrice> = from_json(&data)?;
+
+        Ok(mark_prices)
+    }
+
+    pub fn get_account(&self) -> Result<(CoinFuturesAccountInformation)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(DAPI_V1_ACCOUNT, &request)?;
+        let account: CoinFuturesAccountInformation = from_json(&data)?;
+
+        Ok(account)
+    }
+
+    // Positions of every contract, or of the contracts of one pair
+    pub fn get_position_risk(&self, pair: Option<&str>) -> Result<(Vec<CoinFuturesPosition>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(pair) = pair {
+            parameters.insert("pair".into(), pair.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(DAPI_V1_POSITION_RISK, &request)?;
+        let positions: Vec<CoinFuturesPosition> = from_json(&data)?;
+
+        Ok(positions)
+    }
+
+    pub fn change_leverage<S>(&self, symbol: S, leverage: u32) -> Result<(FuturesLeverage)>
+        where S: Into<String>
+    {
+        if !(1..=125).contains(&leverage) {
+            bail!(BinanceError::Parameter(format!("Futures leverage must be between 1 and 125, got {}", leverage)));
+        }
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("leverage".into(), leverage.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(DAPI_V1_LEVERAGE, &request)?;
+        let leverage: FuturesLeverage = from_json(&data)?;
+
+        Ok(leverage)
+    }
+
+    pub fn change_margin_type<S>(&self, symbol: S, margin_type: FuturesMarginType) -> Result<(FuturesStatus)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("marginType".into(), margin_type.as_str().into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(DAPI_V1_MARGIN_TYPE, &request)?;
+        let status: FuturesStatus = from_json(&data)?;
+
+        Ok(status)
+    }
+
+    // order.qty counts contracts
+    pub fn place_order(&self, order: FuturesOrderRequest) -> Result<(CoinFuturesOrder)> {
+        let parameters = order_parameters(order)?;
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(DAPI_V1_ORDER, &request)?;
+        let order: CoinFuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<(CoinFuturesOrder)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(DAPI_V1_ORDER, &request)?;
+        let order: CoinFuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    pub fn order_status<S>(&self, symbol: S, order_id: u64) -> Result<(CoinFuturesOrder)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(DAPI_V1_ORDER, &request)?;
+        let order: CoinFuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    // Open orders of every contract, or of one
+    pub fn get_open_orders(&self, symbol: Option<&str>) -> Result<(Vec<CoinFuturesOrder>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(DAPI_V1_OPEN_ORDERS, &request)?;
+        let orders: Vec<CoinFuturesOrder> = from_json(&data)?;
+
+        Ok(orders)
+    }
+}
@@ -0,0 +1,157 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::BTreeMap;
+
+static SAPI_V1_CONVERT_GET_QUOTE: &'static str = "/sapi/v1/convert/getQuote";
+static SAPI_V1_CONVERT_ACCEPT_QUOTE: &'static str = "/sapi/v1/convert/acceptQuote";
+static SAPI_V1_CONVERT_ORDER_STATUS: &'static str = "/sapi/v1/convert/orderStatus";
+static SAPI_V1_CONVERT_TRADE_FLOW: &'static str = "/sapi/v1/convert/tradeFlow";
+
+static TRADE_FLOW_MAX_LIMIT: u16 = 1000;
+// tradeFlow spans at most 30 days
+static TRADE_FLOW_MAX_RANGE_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+// Side of a conversion the amount is given for, Binance quotes the other one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvertAmount {
+    // Amount of from_asset to spend
+    From(Amount),
+    // Amount of to_asset to receive
+    To(Amount),
+}
+
+// How long a quote can be accepted, 10 seconds by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertValidTime {
+    TenSeconds,
+    ThirtySeconds,
+    OneMinute,
+    TwoMinutes,
+}
+
+impl ConvertValidTime {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ConvertValidTime::TenSeconds => "10s",
+            ConvertValidTime::ThirtySeconds => "30s",
+            ConvertValidTime::OneMinute => "1m",
+            ConvertValidTime::TwoMinutes => "2m",
+        }
+    }
+}
+
+// Binance Convert, swaps between two assets at a quoted ratio instead of through the order book.
+// A quote accepted after its valid_timestamp fails with BinanceErrorCode::QuoteExpired
+#[derive(Clone)]
+pub struct Convert {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl Convert {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Quote converting from_asset to to_asset of the spot wallet, nothing is converted until
+    // accept_convert_quote
+    pub fn get_convert_quote<S1, S2>(
+        &self, from_asset: S1, to_asset: S2, amount: ConvertAmount, valid_time: Option<ConvertValidTime>,
+    ) -> Result<(ConvertQuote)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("fromAsset".into(), from_asset.into());
+        parameters.insert("toAsset".into(), to_asset.into());
+        match amount {
+            ConvertAmount::From(amount) => {
+                parameters.insert("fromAmount".into(), amount.to_string());
+            }
+            ConvertAmount::To(amount) => {
+                parameters.insert("toAmount".into(), amount.to_string());
+            }
+        }
+        if let Some(valid_time) = valid_time {
+            parameters.insert("validTime".into(), valid_time.as_str().into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_CONVERT_GET_QUOTE, &request)?;
+        let quote: ConvertQuote = from_json(&data)?;
+
+        Ok(quote)
+    }
+
+    // Converts at the quote, check ConvertQuote::is_expired_at first. The conversion may still be
+    // processing, get_convert_order_status tells when it is done
+    pub fn accept_convert_quote<S>(&self, quote_id: S) -> Result<(ConvertAcceptance)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("quoteId".into(), quote_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_CONVERT_ACCEPT_QUOTE, &request)?;
+        let acceptance: ConvertAcceptance = from_json(&data)?;
+
+        Ok(acceptance)
+    }
+
+    pub fn get_convert_order_status(&self, order_id: u64) -> Result<(ConvertOrder)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        self.order_status(parameters)
+    }
+
+    // Status of the conversion of an accepted quote, before its order id is known
+    pub fn get_convert_order_status_by_quote<S>(&self, quote_id: S) -> Result<(ConvertOrder)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("quoteId".into(), quote_id.into());
+
+        self.order_status(parameters)
+    }
+
+    // Conversions between start_time and end_time in ms, at most 30 days apart. limit is 100
+    // when None, at most 1000
+    pub fn get_convert_trade_flow(
+        &self, start_time: u64, end_time: u64, limit: Option<u16>,
+    ) -> Result<(ConvertTradeFlow)> {
+        if start_time > end_time || end_time - start_time > TRADE_FLOW_MAX_RANGE_MS {
+            bail!(BinanceError::Parameter(format!(
+                "Time range {} to {} must not be reversed or longer than 30 days",
+                start_time, end_time
+            )));
+        }
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("startTime".into(), start_time.to_string());
+        parameters.insert("endTime".into(), end_time.to_string());
+        if let Some(limit) = limit {
+            if limit == 0 || limit > TRADE_FLOW_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, TRADE_FLOW_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_CONVERT_TRADE_FLOW, &request)?;
+        let trade_flow: ConvertTradeFlow = from_json(&data)?;
+
+        Ok(trade_flow)
+    }
+
+    fn order_status(&self, parameters: BTreeMap<String, String>) -> Result<(ConvertOrder)> {
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_CONVERT_ORDER_STATUS, &request)?;
+        let order: ConvertOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+}
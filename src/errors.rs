@@ -1,22 +1,703 @@
 use std;
+use std::fmt;
+use std::error::Error as StdError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use native_tls;
 use reqwest;
 use url;
+use serde::de::DeserializeOwned;
 use serde_json;
+use tungstenite;
+use tungstenite::protocol::CloseFrame;
+use model::Amount;
 
-error_chain! {
-    types {
-        Error, ErrorKind, ResultExt, Result;
+// How much of a payload that failed to deserialize is kept in BinanceError::Json
+static PAYLOAD_SNIPPET_LEN: usize = 256;
+
+// Returns from the function with an error, anything that converts into a BinanceError
+macro_rules! bail {
+    ($e:expr) => {
+        return Err(::std::convert::From::from($e))
+    };
+}
+
+pub type Error = BinanceError;
+
+pub type Result<T> = std::result::Result<T, BinanceError>;
+
+// Every failure of the crate. Transport and parsing errors keep the error they wrap as their source()
+#[derive(Debug)]
+pub enum BinanceError {
+    // The HTTP request could not be sent or its answer read
+    Http(reqwest::Error),
+    Io(std::io::Error),
+    Websocket(tungstenite::Error),
+    // TLS connector or handshake failure of a websocket behind a proxy
+    Tls(native_tls::Error),
+    // The proxy refused or broke the CONNECT
+    Proxy(String),
+
+    WebsocketClosed {
+        frame: Option<CloseFrame<'static>>,
+    },
+
+    // No answer within the timeout of the client, the execution status of the request is unknown:
+    // query an order before placing it again
+    Timeout {
+        timeout: Option<Duration>,
+    },
+
+    WebsocketApiTimeout {
+        id: u64,
+    },
+
+    // An answer that does not deserialize into the model, payload is the start of it
+    Json {
+        error: serde_json::Error,
+        payload: Option<String>,
+    },
+
+    // {"code":-2010,"msg":"..."} answer of a REST or websocket API request, see BinanceErrorCode
+    Api {
+        status: u16,
+        code: i64,
+        msg: String,
+    },
+
+    // 429 answer, retry_after is the Retry-After header
+    TooManyRequests {
+        retry_after: Option<Duration>,
+    },
+
+    // 418 answer, the IP is banned for retry_after after ignoring 429s
+    IpBanned {
+        retry_after: Option<Duration>,
+    },
+
+    // 503 answer, Binance is in maintenance or overloaded; worth backing off for minutes
+    ServiceUnavailable {
+        body: String,
+    },
+
+    // 5xx answer other than 503, the execution status of the request is unknown
+    Server {
+        status: u16,
+        body: String,
+    },
+
+    // 4xx answer without a Binance error body
+    Status {
+        status: u16,
+        body: String,
+    },
+
+    Url(url::ParseError),
+
+    // A request the API would refuse, caught before sending it
+    Parameter(String),
+
+    // An order outside the exchange filters of its symbol, nearest is the closest valid value
+    Filter {
+        filter: String,
+        value: Amount,
+        nearest: Amount,
+    },
+
+    ParseFloat(std::num::ParseFloatError),
+
+    // The system clock is before the epoch
+    Clock(std::time::SystemTimeError),
+
+    // An API key without the permissions it needs (missing) or with more (excess), by the field names
+    // of apiRestrictions
+    ApiKeyPermissions {
+        missing: Vec<&'static str>,
+        excess: Vec<&'static str>,
+    },
+
+    // A broken invariant of the crate, a panicked thread or a socket in an unexpected state
+    Internal(String),
+
+    // A POST or DELETE that failed once sent: a timeout, a lost connection or a 5xx answer. The order
+    // may have gone through, query its status before placing it again
+    OutcomeUnknown(Box<BinanceError>),
+}
+
+impl BinanceError {
+    // The {"code":...,"msg":...} answer, when that is what failed
+    pub fn binance_error(&self) -> Option<BinanceContentError> {
+        match *self {
+            BinanceError::Api { status, code, ref msg } => Some(BinanceContentError {
+                status,
+                code,
+                msg: msg.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn error_code(&self) -> Option<BinanceErrorCode> {
+        match *self {
+            BinanceError::Api { code, .. } => Some(BinanceErrorCode::from(code)),
+            _ => None,
+        }
+    }
+
+    // Safe to send again as is: a query that timed out or lost its connection, a 5xx answer or -1001.
+    // The same failures of a POST or DELETE are OutcomeUnknown instead
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            BinanceError::Http(ref e) => is_connection_failure(e),
+            BinanceError::Io(_)
+            | BinanceError::Timeout { .. }
+            | BinanceError::ServiceUnavailable { .. }
+            | BinanceError::Server { .. } => true,
+            BinanceError::Api { code, .. } => BinanceErrorCode::from(code).is_transient(),
+            _ => false,
+        }
+    }
+
+    // 429, 418, -1003 or -1015, wait for retry_after before the next request
+    pub fn is_rate_limited(&self) -> bool {
+        match *self {
+            BinanceError::TooManyRequests { .. } | BinanceError::IpBanned { .. } => true,
+            _ => match self.error_code() {
+                Some(BinanceErrorCode::TooManyRequests) | Some(BinanceErrorCode::TooManyOrders) => true,
+                _ => false,
+            },
+        }
+    }
+
+    // The Retry-After header of a 429 or 418, or the end of the ban a -1003 message gives
+    pub fn retry_after(&self) -> Option<Duration> {
+        match *self {
+            BinanceError::TooManyRequests { retry_after } | BinanceError::IpBanned { retry_after } => retry_after,
+            BinanceError::Api { ref msg, .. } if self.is_rate_limited() => ban_expiry(msg),
+            _ => None,
+        }
+    }
+
+    // Rejected for what it asks, by Binance or by the checks before sending: it fails the same way again
+    pub fn is_invalid_request(&self) -> bool {
+        match *self {
+            BinanceError::Status { .. }
+            | BinanceError::ApiKeyPermissions { .. }
+            | BinanceError::Url(_)
+            | BinanceError::Parameter(_)
+            | BinanceError::Filter { .. } => true,
+            BinanceError::Api { .. } => !self.is_rate_limited() && !self.is_retryable(),
+            _ => false,
+        }
+    }
+
+    // The request was sent and its effect is unknown, see OutcomeUnknown
+    pub fn is_outcome_unknown(&self) -> bool {
+        match *self {
+            BinanceError::OutcomeUnknown(_) | BinanceError::WebsocketApiTimeout { .. } => true,
+            _ => false,
+        }
+    }
+
+    // Whether the request may have reached Binance without its answer coming back
+    pub(crate) fn is_unanswered(&self) -> bool {
+        match *self {
+            // An HTTP error of hyper is a broken answer, e.g. one cut short. A refused connection
+            // never carried the request
+            BinanceError::Http(ref e) => (e.is_http() || is_connection_failure(e)) && !is_refused(e),
+            BinanceError::Io(ref e) => e.kind() != std::io::ErrorKind::ConnectionRefused,
+            BinanceError::Timeout { .. }
+            | BinanceError::ServiceUnavailable { .. }
+            | BinanceError::Server { .. } => true,
+            BinanceError::Api { code, .. } => BinanceErrorCode::from(code).is_execution_unknown(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for BinanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BinanceError::Http(ref e) => write!(f, "HTTP error: {}", e),
+            BinanceError::Io(ref e) => write!(f, "I/O error: {}", e),
+            BinanceError::Websocket(ref e) => write!(f, "websocket error: {}", e),
+            BinanceError::Tls(ref e) => write!(f, "TLS error: {}", e),
+            BinanceError::Proxy(ref msg) => write!(f, "proxy error: {}", msg),
+            BinanceError::WebsocketClosed { ref frame } => write!(f, "websocket connection closed: {:?}", frame),
+            BinanceError::Timeout { timeout } => write!(f, "Request timed out after {:?}", timeout),
+            BinanceError::WebsocketApiTimeout { id } => write!(f, "websocket API request {} timed out", id),
+            BinanceError::Json { ref error, payload: Some(ref payload) } => {
+                write!(f, "JSON error: {} in {}", error, payload)
+            }
+            BinanceError::Json { ref error, payload: None } => write!(f, "JSON error: {}", error),
+            BinanceError::Api { code, ref msg, .. } => write!(f, "Binance error {}: {}", code, msg),
+            BinanceError::TooManyRequests { retry_after } => {
+                write!(f, "Too many requests, retry after {:?}", retry_after)
+            }
+            BinanceError::IpBanned { retry_after } => write!(f, "IP banned, retry after {:?}", retry_after),
+            BinanceError::ServiceUnavailable { ref body } => write!(f, "Service Unavailable: {}", body),
+            BinanceError::Server { status, ref body } => write!(f, "Server error {}: {}", status, body),
+            BinanceError::Status { status, ref body } => write!(f, "Received response {}: {}", status, body),
+            BinanceError::Url(ref e) => write!(f, "URL error: {}", e),
+            BinanceError::Parameter(ref msg) => write!(f, "{}", msg),
+            BinanceError::Filter { ref filter, value, nearest } => {
+                write!(f, "{} rejects {}, nearest valid value is {}", filter, value, nearest)
+            }
+            BinanceError::ApiKeyPermissions { ref missing, ref excess } => write!(
+                f,
+                "API key permissions: missing {}, excess {}",
+                list_or_none(missing),
+                list_or_none(excess)
+            ),
+            BinanceError::ParseFloat(ref e) => write!(f, "invalid number: {}", e),
+            BinanceError::Clock(ref e) => write!(f, "system clock error: {}", e),
+            BinanceError::Internal(ref msg) => write!(f, "{}", msg),
+            BinanceError::OutcomeUnknown(ref e) => write!(f, "Outcome unknown, query before retrying: {}", e),
+        }
+    }
+}
+
+impl StdError for BinanceError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            BinanceError::Http(ref e) => Some(e),
+            BinanceError::Io(ref e) => Some(e),
+            BinanceError::Websocket(ref e) => Some(e),
+            BinanceError::Tls(ref e) => Some(e),
+            BinanceError::Json { ref error, .. } => Some(error),
+            BinanceError::Url(ref e) => Some(e),
+            BinanceError::ParseFloat(ref e) => Some(e),
+            BinanceError::Clock(ref e) => Some(e),
+            BinanceError::OutcomeUnknown(ref e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for BinanceError {
+    fn from(error: reqwest::Error) -> BinanceError {
+        BinanceError::Http(error)
+    }
+}
+
+impl From<std::io::Error> for BinanceError {
+    fn from(error: std::io::Error) -> BinanceError {
+        BinanceError::Io(error)
+    }
+}
+
+// tungstenite 0.5 has no AlreadyClosed, a socket used after its close answers ConnectionClosed(None)
+impl From<tungstenite::Error> for BinanceError {
+    fn from(error: tungstenite::Error) -> BinanceError {
+        match error {
+            tungstenite::Error::ConnectionClosed(frame) => BinanceError::WebsocketClosed { frame },
+            error => BinanceError::Websocket(error),
+        }
+    }
+}
+
+impl From<native_tls::Error> for BinanceError {
+    fn from(error: native_tls::Error) -> BinanceError {
+        BinanceError::Tls(error)
+    }
+}
+
+// Without the payload, from_json keeps it
+impl From<serde_json::Error> for BinanceError {
+    fn from(error: serde_json::Error) -> BinanceError {
+        BinanceError::Json { error, payload: None }
+    }
+}
+
+impl From<url::ParseError> for BinanceError {
+    fn from(error: url::ParseError) -> BinanceError {
+        BinanceError::Url(error)
+    }
+}
+
+impl From<std::num::ParseFloatError> for BinanceError {
+    fn from(error: std::num::ParseFloatError) -> BinanceError {
+        BinanceError::ParseFloat(error)
+    }
+}
+
+impl From<std::time::SystemTimeError> for BinanceError {
+    fn from(error: std::time::SystemTimeError) -> BinanceError {
+        BinanceError::Clock(error)
+    }
+}
+
+impl From<BinanceContentError> for BinanceError {
+    fn from(error: BinanceContentError) -> BinanceError {
+        BinanceError::Api {
+            status: error.status,
+            code: error.code,
+            msg: error.msg,
+        }
+    }
+}
+
+fn list_or_none(names: &[&str]) -> String {
+    if names.is_empty() {
+        "none".into()
+    } else {
+        names.join(", ")
+    }
+}
+
+// "Way too many requests; IP banned until 1507706516417." ends its ban in ms since the epoch
+pub(crate) fn ban_expiry(msg: &str) -> Option<Duration> {
+    let until: String = msg
+        .split("banned until ")
+        .nth(1)?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    let until = UNIX_EPOCH + Duration::from_millis(until.parse().ok()?);
+
+    Some(until.duration_since(SystemTime::now()).unwrap_or_else(|_| Duration::from_secs(0)))
+}
+
+// from_str of an answer, a failure keeps the start of the payload
+pub(crate) fn from_json<T>(payload: &str) -> Result<T>
+    where T: DeserializeOwned
+{
+    serde_json::from_str(payload).map_err(|error| {
+        let end = payload
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(PAYLOAD_SNIPPET_LEN)
+            .unwrap_or(payload.len());
+
+        BinanceError::Json {
+            error,
+            payload: Some(payload[..end].to_string()),
+        }
+    })
+}
+
+// A timeout or a lost connection, reqwest reports them as an io::Error. Its other errors (TLS, a
+// malformed answer, redirects) fail again the same way
+fn is_connection_failure(error: &reqwest::Error) -> bool {
+    use std::io::ErrorKind::*;
+
+    match error.get_ref().and_then(|error| error.downcast_ref::<std::io::Error>()) {
+        Some(error) => match error.kind() {
+            TimedOut | WouldBlock | ConnectionRefused | ConnectionReset | ConnectionAborted | NotConnected
+            | BrokenPipe | UnexpectedEof => true,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+fn is_refused(error: &reqwest::Error) -> bool {
+    error
+        .get_ref()
+        .and_then(|error| error.downcast_ref::<std::io::Error>())
+        .map_or(false, |error| error.kind() == std::io::ErrorKind::ConnectionRefused)
+}
+
+// {"code":-2010,"msg":"..."} body of a failed REST request, with its HTTP status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BinanceContentError {
+    #[serde(skip)]
+    pub status: u16,
+    pub code: i64,
+    pub msg: String,
+}
+
+impl BinanceContentError {
+    pub fn error_code(&self) -> BinanceErrorCode {
+        BinanceErrorCode::from(self.code)
+    }
+
+    pub fn is_timestamp_error(&self) -> bool {
+        self.error_code() == BinanceErrorCode::InvalidTimestamp
+    }
+
+    pub fn is_filter_failure(&self) -> bool {
+        self.error_code() == BinanceErrorCode::FilterFailure
+    }
+
+    // -2010 covers other rejections too (duplicate order, market closed), told apart by msg only
+    pub fn is_insufficient_balance(&self) -> bool {
+        match self.error_code() {
+            BinanceErrorCode::NewOrderRejected => self.msg.contains("insufficient balance"),
+            code => code == BinanceErrorCode::WithdrawalInsufficientBalance,
+        }
+    }
+
+    pub fn is_address_not_whitelisted(&self) -> bool {
+        self.error_code() == BinanceErrorCode::AddressNotWhitelisted
+    }
+
+    pub fn is_withdrawal_disabled(&self) -> bool {
+        self.error_code() == BinanceErrorCode::WithdrawalDisabled
+    }
+
+    // A cancel of an order already filled, canceled or never placed
+    pub fn is_unknown_order(&self) -> bool {
+        self.error_code() == BinanceErrorCode::CancelRejected
     }
 
-    errors { FooError }
+    pub fn is_too_many_requests(&self) -> bool {
+        self.error_code() == BinanceErrorCode::TooManyRequests
+    }
+
+    pub fn is_sold_out(&self) -> bool {
+        self.error_code() == BinanceErrorCode::ProductSoldOut
+    }
+
+    pub fn is_redemption_locked(&self) -> bool {
+        self.error_code() == BinanceErrorCode::RedemptionLocked
+    }
+
+    pub fn is_quote_expired(&self) -> bool {
+        self.error_code() == BinanceErrorCode::QuoteExpired
+    }
+
+    pub fn is_invalid_symbol(&self) -> bool {
+        self.error_code() == BinanceErrorCode::InvalidSymbol
+    }
+}
+
+// The error codes worth telling apart, the others are Other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinanceErrorCode {
+    // -1001, the backend lost the request
+    Disconnected,
+    // -1003
+    TooManyRequests,
+    // -1015, the order count limit of the account
+    TooManyOrders,
+    // -1006 and -1007, no answer from the backend, the execution status is unknown
+    UnexpectedResponse,
+    BackendTimeout,
+    // -1013, outside the exchange filters of the symbol
+    FilterFailure,
+    // -1021, timestamp ahead of the server time or outside recvWindow
+    InvalidTimestamp,
+    // -1121, e.g. one unknown symbol of exchangeInfo?symbols=
+    InvalidSymbol,
+    // -2010
+    NewOrderRejected,
+    // -2011
+    CancelRejected,
+    // -2013
+    NoSuchOrder,
+    // -2014 and -2015, API key invalid or without the permission
+    RejectedApiKey,
+    // -6004 and -6014, the earn product is not purchasable anymore or its quota is reached
+    ProductSoldOut,
+    // -6007 and -6008, the position is in its lock period or the product does not redeem now
+    RedemptionLocked,
+    // -4026, less free balance than the withdrawal amount
+    WithdrawalInsufficientBalance,
+    // -4035, whitelisting is on and the address is not in it
+    AddressNotWhitelisted,
+    // -4019, withdrawals of the asset (or on the network) are suspended
+    WithdrawalDisabled,
+    // 345103, a Convert quote accepted after its valid_timestamp
+    QuoteExpired,
+    Other(i64),
+}
+
+impl From<i64> for BinanceErrorCode {
+    fn from(code: i64) -> BinanceErrorCode {
+        match code {
+            -1001 => BinanceErrorCode::Disconnected,
+            -1003 => BinanceErrorCode::TooManyRequests,
+            -1015 => BinanceErrorCode::TooManyOrders,
+            -1006 => BinanceErrorCode::UnexpectedResponse,
+            -1007 => BinanceErrorCode::BackendTimeout,
+            -1013 => BinanceErrorCode::FilterFailure,
+            -1021 => BinanceErrorCode::InvalidTimestamp,
+            -1121 => BinanceErrorCode::InvalidSymbol,
+            -2010 => BinanceErrorCode::NewOrderRejected,
+            -2011 => BinanceErrorCode::CancelRejected,
+            -2013 => BinanceErrorCode::NoSuchOrder,
+            -2014 | -2015 => BinanceErrorCode::RejectedApiKey,
+            -6004 | -6014 => BinanceErrorCode::ProductSoldOut,
+            -6007 | -6008 => BinanceErrorCode::RedemptionLocked,
+            -4019 => BinanceErrorCode::WithdrawalDisabled,
+            -4026 => BinanceErrorCode::WithdrawalInsufficientBalance,
+            -4035 => BinanceErrorCode::AddressNotWhitelisted,
+            345103 => BinanceErrorCode::QuoteExpired,
+            other => BinanceErrorCode::Other(other),
+        }
+    }
+}
+
+impl BinanceErrorCode {
+    // Failures of the backend rather than of the request
+    pub fn is_transient(self) -> bool {
+        self == BinanceErrorCode::Disconnected || self.is_execution_unknown()
+    }
+
+    pub fn is_execution_unknown(self) -> bool {
+        self == BinanceErrorCode::UnexpectedResponse || self == BinanceErrorCode::BackendTimeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    foreign_links {
-        ReqError(reqwest::Error);
-        IoError(std::io::Error);
-        ParseFloatError(std::num::ParseFloatError);
-        UrlParserError(url::ParseError);
-        Json(serde_json::Error);
-        TimestampError(std::time::SystemTimeError);
+    #[test]
+    fn closed_websocket_is_websocket_closed() {
+        match BinanceError::from(tungstenite::Error::ConnectionClosed(None)) {
+            BinanceError::WebsocketClosed { frame: None } => {}
+            other => panic!("unexpected {:?}", other),
+        }
     }
 
+    fn content_error(code: i64, msg: &str) -> BinanceContentError {
+        BinanceContentError { status: 400, code, msg: msg.into() }
+    }
+
+    #[test]
+    fn withdrawal_rejections() {
+        let insufficient = content_error(-4026, "User has insufficient balance");
+        assert!(insufficient.is_insufficient_balance());
+        assert!(!insufficient.is_withdrawal_disabled());
+
+        let not_whitelisted = content_error(-4035, "This address is not on the whitelist.");
+        assert!(not_whitelisted.is_address_not_whitelisted());
+        assert!(!not_whitelisted.is_insufficient_balance());
+
+        let disabled = content_error(-4019, "The current currency is not open for withdrawal");
+        assert!(disabled.is_withdrawal_disabled());
+        assert_eq!(disabled.error_code(), BinanceErrorCode::WithdrawalDisabled);
+
+        let order = content_error(-2010, "Account has insufficient balance for requested action.");
+        assert!(order.is_insufficient_balance());
+        assert!(!content_error(-2010, "Duplicate order sent.").is_insufficient_balance());
+    }
+
+    fn api_error(status: u16, code: i64, msg: &str) -> BinanceError {
+        BinanceError::Api { status, code, msg: msg.into() }
+    }
+
+    #[test]
+    fn too_many_requests_code() {
+        let error = api_error(
+            429,
+            -1003,
+            "Too much request weight used; current limit is 6000 request weight per 1 MINUTE.",
+        );
+
+        assert!(error.is_rate_limited());
+        assert!(!error.is_retryable() && !error.is_invalid_request());
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn too_many_requests_code_with_a_ban() {
+        let until = SystemTime::now() + Duration::from_secs(60);
+        let until = until.duration_since(UNIX_EPOCH).unwrap().as_secs() * 1000;
+        let error = api_error(418, -1003, &format!("Way too many requests; IP banned until {}.", until));
+
+        assert!(error.is_rate_limited());
+        assert!(error.retry_after().map_or(false, |retry_after| retry_after <= Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn backend_timeout_code() {
+        let error = api_error(
+            408,
+            -1007,
+            "Timeout waiting for response from backend server. Send status unknown; execution status unknown.",
+        );
+
+        assert!(error.is_retryable());
+        assert!(error.is_unanswered());
+        assert!(!error.is_rate_limited() && !error.is_invalid_request());
+    }
+
+    #[test]
+    fn too_many_orders_code() {
+        let error = api_error(429, -1015, "Too many new orders; current limit is 50 orders per 10 SECOND.");
+
+        assert_eq!(error.error_code(), Some(BinanceErrorCode::TooManyOrders));
+        assert!(error.is_rate_limited());
+        assert!(!error.is_retryable() && !error.is_invalid_request());
+    }
+
+    #[test]
+    fn invalid_timestamp_code() {
+        let error = api_error(400, -1021, "Timestamp for this request is outside of the recvWindow.");
+
+        assert!(error.binance_error().map_or(false, |error| error.is_timestamp_error()));
+        assert!(!error.is_retryable() && !error.is_rate_limited() && !error.is_unanswered());
+    }
+
+    #[test]
+    fn ip_banned_status() {
+        let error = BinanceError::IpBanned { retry_after: Some(Duration::from_secs(120)) };
+
+        assert!(error.is_rate_limited());
+        assert!(!error.is_retryable() && !error.is_invalid_request());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn too_many_requests_status() {
+        let error = BinanceError::TooManyRequests { retry_after: Some(Duration::from_secs(3)) };
+
+        assert!(error.is_rate_limited());
+        assert!(!error.is_retryable() && !error.is_invalid_request());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn server_error_statuses() {
+        let errors = [
+            BinanceError::Server { status: 500, body: "Internal Server Error".into() },
+            BinanceError::Server { status: 502, body: "Bad Gateway".into() },
+            BinanceError::ServiceUnavailable { body: "Service Unavailable".into() },
+            BinanceError::Server { status: 504, body: "Gateway Timeout".into() },
+        ];
+
+        for error in errors.iter() {
+            assert!(error.is_retryable(), "{:?}", error);
+            assert!(error.is_unanswered(), "{:?}", error);
+            assert!(!error.is_rate_limited() && !error.is_invalid_request(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn refused_connection_is_retryable() {
+        let port = ::std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let error = match reqwest::Client::new().get(&format!("http://127.0.0.1:{}/api/v3/ping", port)).send() {
+            Err(error) => BinanceError::Http(error),
+            Ok(response) => panic!("unexpected {:?}", response),
+        };
+
+        assert!(error.is_retryable(), "{:?}", error);
+        assert!(!error.is_unanswered(), "{:?}", error);
+    }
+
+    #[test]
+    fn refused_order_is_a_plain_error() {
+        let port = ::std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let mut client = ::client::Client::new_with_endpoint(None, None, &format!("http://127.0.0.1:{}", port));
+        client.set_proxy(None).unwrap();
+
+        match client.post_signed("/api/v3/order", "symbol=LTCBTC&timestamp=1000") {
+            Err(ref error @ BinanceError::Http(_)) => assert!(error.is_retryable(), "{:?}", error),
+            other => panic!("unexpected {:?}", other),
+        }
+        let refused = BinanceError::Io(std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused"));
+        assert!(!refused.is_unanswered());
+    }
+
+    #[test]
+    fn other_http_errors_are_not_retryable() {
+        let error = match reqwest::Client::new().get("http://").send() {
+            Err(error) => BinanceError::Http(error),
+            Ok(response) => panic!("unexpected {:?}", response),
+        };
+
+        assert!(!error.is_retryable(), "{:?}", error);
+    }
 }
@@ -0,0 +1,309 @@
+use model::*;
+use errors::*;
+
+// Tolerance in steps, prices and quantities are f64 parsed from decimal strings. Decimal amounts
+// are exact and need none
+#[cfg(not(feature = "decimal"))]
+static EPSILON: f64 = 1e-6;
+
+// The exchange filters of one symbol that orders can be checked against locally
+#[derive(Debug, Clone, Default)]
+pub struct SymbolFilters {
+    pub symbol: String,
+    // (min_price, max_price, tick_size), 0.0 disables a bound
+    pub price: Option<(Amount, Amount, Amount)>,
+    // (min_qty, max_qty, step_size)
+    pub lot_size: Option<(Amount, Amount, Amount)>,
+    pub market_lot_size: Option<(Amount, Amount, Amount)>,
+    // (min_notional, applies to MARKET orders)
+    pub min_notional: Option<(Amount, bool)>,
+    pub max_notional: Option<(Amount, bool)>,
+}
+
+impl SymbolFilters {
+    pub fn new(symbol: &Symbol) -> SymbolFilters {
+        let mut filters = SymbolFilters {
+            symbol: symbol.symbol.clone(),
+            ..SymbolFilters::default()
+        };
+
+        for filter in &symbol.filters {
+            match *filter {
+                Filters::PriceFilter { min_price, max_price, tick_size } => {
+                    filters.price = Some((min_price, max_price, tick_size));
+                }
+                Filters::LotSize { min_qty, max_qty, step_size } => {
+                    filters.lot_size = Some((min_qty, max_qty, step_size));
+                }
+                // A zero step size means MARKET orders follow LOT_SIZE
+                Filters::MarketLotSize { min_qty, max_qty, step_size } if step_size > ZERO => {
+                    filters.market_lot_size = Some((min_qty, max_qty, step_size));
+                }
+                Filters::MinNotional { min_notional, apply_to_market } => {
+                    filters.min_notional = Some((min_notional, apply_to_market));
+                }
+                Filters::Notional { min_notional, apply_min_to_market, max_notional, apply_max_to_market } => {
+                    filters.min_notional = Some((min_notional, apply_min_to_market));
+                    filters.max_notional = Some((max_notional, apply_max_to_market));
+                }
+                _ => {}
+            }
+        }
+
+        filters
+    }
+
+    pub fn check_price(&self, price: Amount) -> Result<()> {
+        if let Some((min, max, tick)) = self.price {
+            check_range("PRICE_FILTER", price, min, max, tick)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check_qty(&self, qty: Amount, is_market: bool) -> Result<()> {
+        if is_market {
+            if let Some((min, max, step)) = self.market_lot_size {
+                check_range("MARKET_LOT_SIZE", qty, min, max, step)?;
+            }
+        }
+        if let Some((min, max, step)) = self.lot_size {
+            check_range("LOT_SIZE", qty, min, max, step)?;
+        }
+
+        Ok(())
+    }
+
+    // notional is price * qty
+    pub fn check_notional(&self, notional: Amount, is_market: bool) -> Result<()> {
+        if let Some((min, applies_to_market)) = self.min_notional {
+            if (!is_market || applies_to_market) && notional < min {
+                bail!(BinanceError::Filter { filter: "MIN_NOTIONAL".into(), value: notional, nearest: min });
+            }
+        }
+        if let Some((max, applies_to_market)) = self.max_notional {
+            if (!is_market || applies_to_market) && max > ZERO && notional > max {
+                bail!(BinanceError::Filter { filter: "NOTIONAL".into(), value: notional, nearest: max });
+            }
+        }
+
+        Ok(())
+    }
+
+    // price * qty reaches the minimum notional of LIMIT orders
+    pub fn meets_min_notional(&self, price: Amount, qty: Amount) -> bool {
+        match self.min_notional {
+            Some((min, _)) => at_least(price * qty, min),
+            None => true,
+        }
+    }
+
+    // Smallest quantity on the step size that reaches the minimum notional at price
+    pub fn min_qty_for_notional(&self, price: Amount) -> Amount {
+        let qty = match self.min_notional {
+            Some((min, _)) if price > ZERO => min / price,
+            _ => ZERO,
+        };
+
+        match self.lot_size {
+            Some((min, _, _)) if qty <= min => min,
+            Some((min, _, step)) => snap(qty, min, step, Rounding::Up),
+            None => qty,
+        }
+    }
+
+    // Checks a LIMIT order against PRICE_FILTER, LOT_SIZE and the notional filters, the error
+    // names the violated filter
+    pub fn validate_order(&self, price: Amount, qty: Amount) -> Result<()> {
+        self.check_price(price)?;
+        self.check_qty(qty, false)?;
+        self.check_notional(price * qty, false)
+    }
+
+    // Nearest price on the tick size, within the price bounds
+    pub fn round_price(&self, price: Amount) -> Amount {
+        match self.price {
+            Some((min, max, tick)) => clamp(snap(price, min, tick, Rounding::Nearest), min, max),
+            None => price,
+        }
+    }
+
+    // Quantity rounded down to the step size, so it never exceeds what was asked for.
+    // 0 when below the minimum quantity
+    pub fn round_qty(&self, qty: Amount) -> Amount {
+        match self.lot_size {
+            Some((min, _, _)) if qty < min => ZERO,
+            Some((min, max, step)) => snap(clamp(qty, min, max), min, step, Rounding::Down),
+            None => qty,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Rounding {
+    Nearest,
+    Down,
+    Up,
+}
+
+fn check_range(filter: &str, value: Amount, min: Amount, max: Amount, step: Amount) -> Result<()> {
+    if min > ZERO && value < min {
+        bail!(BinanceError::Filter { filter: filter.into(), value, nearest: min });
+    }
+    if max > ZERO && value > max {
+        bail!(BinanceError::Filter { filter: filter.into(), value, nearest: max });
+    }
+
+    if step > ZERO {
+        let nearest = snap(value, min, step, Rounding::Nearest);
+        if !on_step(value, nearest, step) {
+            bail!(BinanceError::Filter { filter: filter.into(), value, nearest });
+        }
+    }
+
+    Ok(())
+}
+
+// Snaps value to min + n * step. The arithmetic runs on integer multiples of the smallest decimal
+// of step, 0.0749999 floored to a 0.001 step is 0.074 and not 0.07300000000000001
+#[cfg(not(feature = "decimal"))]
+fn snap(value: Amount, min: Amount, step: Amount, rounding: Rounding) -> Amount {
+    if step <= 0.0 {
+        return value;
+    }
+
+    let scale = scale_of(step);
+    let min_units = (min * scale).round();
+    let step_units = (step * scale).round().max(1.0);
+    let steps = (value * scale - min_units) / step_units;
+    let steps = match rounding {
+        Rounding::Nearest => steps.round(),
+        Rounding::Down => (steps + EPSILON).floor(),
+        Rounding::Up => (steps - EPSILON).ceil(),
+    };
+
+    (min_units + steps * step_units) / scale
+}
+
+#[cfg(feature = "decimal")]
+fn snap(value: Amount, min: Amount, step: Amount, rounding: Rounding) -> Amount {
+    if step <= ZERO {
+        return value;
+    }
+
+    let steps = (value - min) / step;
+    let steps = match rounding {
+        Rounding::Nearest => steps.round(),
+        Rounding::Down => steps.floor(),
+        Rounding::Up => steps.ceil(),
+    };
+
+    (min + steps * step).normalize()
+}
+
+// value is nearest, the snapped value, up to the f64 noise
+#[cfg(not(feature = "decimal"))]
+fn on_step(value: Amount, nearest: Amount, step: Amount) -> bool {
+    let scale = scale_of(step);
+
+    (value * scale - nearest * scale).abs() <= EPSILON
+}
+
+#[cfg(feature = "decimal")]
+fn on_step(value: Amount, nearest: Amount, _step: Amount) -> bool {
+    value == nearest
+}
+
+// Tolerates the noise of an f64 product
+#[cfg(not(feature = "decimal"))]
+fn at_least(value: Amount, min: Amount) -> bool {
+    value >= min * (1.0 - EPSILON)
+}
+
+#[cfg(feature = "decimal")]
+fn at_least(value: Amount, min: Amount) -> bool {
+    value >= min
+}
+
+// 10^decimals of step, 1000.0 for 0.001
+#[cfg(not(feature = "decimal"))]
+fn scale_of(step: f64) -> f64 {
+    let decimals = step.to_string().split('.').nth(1).map_or(0, |decimals| decimals.len());
+
+    10f64.powi(decimals as i32)
+}
+
+fn clamp(value: Amount, min: Amount, max: Amount) -> Amount {
+    if min > ZERO && value < min {
+        min
+    } else if max > ZERO && value > max {
+        max
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filters() -> SymbolFilters {
+        SymbolFilters {
+            symbol: "LTCBTC".into(),
+            price: Some((amount("0.01"), amount("1000"), amount("0.01"))),
+            lot_size: Some((amount("0.001"), amount("9000"), amount("0.001"))),
+            ..SymbolFilters::default()
+        }
+    }
+
+    #[test]
+    fn qty_just_below_a_step() {
+        let filters = filters();
+        assert_eq!(filters.round_qty(amount("0.0749999")), amount("0.074"));
+
+        match filters.check_qty(amount("0.0749999"), false) {
+            Err(BinanceError::Filter { ref filter, nearest, .. }) if filter == "LOT_SIZE" => {
+                assert_eq!(nearest, amount("0.075"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(filters.check_qty(amount("0.075"), false).is_ok());
+    }
+
+    #[test]
+    fn qty_at_the_bounds() {
+        let filters = filters();
+        assert_eq!(filters.round_qty(amount("0.0009")), ZERO);
+        assert_eq!(filters.round_qty(amount("0.001")), amount("0.001"));
+        assert_eq!(filters.round_qty(amount("9000.5")), amount("9000"));
+        assert!(filters.check_qty(amount("0.001"), false).is_ok());
+        assert!(filters.check_qty(amount("9000"), false).is_ok());
+        assert!(filters.check_qty(amount("9000.001"), false).is_err());
+    }
+
+    #[test]
+    fn price_rounds_to_the_nearest_tick() {
+        let filters = filters();
+        assert_eq!(filters.round_price(amount("123.454999")), amount("123.45"));
+        assert_eq!(filters.round_price(amount("123.455001")), amount("123.46"));
+        assert_eq!(filters.round_price(amount("0.016")), amount("0.02"));
+        // Clamped to the price bounds
+        assert_eq!(filters.round_price(amount("0.004")), amount("0.01"));
+        assert_eq!(filters.round_price(amount("1000.004")), amount("1000"));
+    }
+
+    #[test]
+    fn price_on_a_tick_up_to_the_float_noise() {
+        let filters = filters();
+        // 0.30000000000000004 as f64
+        assert!(filters.check_price(amount("0.1") + amount("0.2")).is_ok());
+        assert!(filters.check_price(amount("1000")).is_ok());
+
+        match filters.check_price(amount("0.013")) {
+            Err(BinanceError::Filter { ref filter, nearest, .. }) if filter == "PRICE_FILTER" => {
+                assert_eq!(nearest, amount("0.01"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,14 @@
 use model::*;
 use client::*;
 use errors::*;
+use util::*;
 
-use serde_json::from_str;
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+static API_V3_EXCHANGE_INFO: &'static str = "/api/v3/exchangeInfo";
+static SAPI_V1_SYSTEM_STATUS: &'static str = "/sapi/v1/system/status";
 
 #[derive(Clone)]
 pub struct General {
@@ -21,16 +27,109 @@ impl General {
     pub fn get_server_time(&self) -> Result<(ServerTime)> {
         let data: String = self.client.get("/api/v1/time", "")?;
 
-        let server_time: ServerTime = from_str(data.as_str())?;
+        let server_time: ServerTime = from_json(&data)?;
 
         Ok(server_time)
     }
 
+    // Whether Binance is in maintenance
+    pub fn get_system_status(&self) -> Result<(SystemStatus)> {
+        let data: String = self.client.get(SAPI_V1_SYSTEM_STATUS, "")?;
+
+        let status: SystemStatus = from_json(&data)?;
+
+        Ok(status)
+    }
+
+    // Polls the system status every poll_interval until it is Normal. Fails with the last status
+    // (or error) once timeout has passed
+    pub fn wait_until_operational(&self, poll_interval: Duration, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let last = match self.get_system_status() {
+                Ok(ref status) if status.status == SystemState::Normal => return Ok(()),
+                Ok(status) => BinanceError::ServiceUnavailable {
+                    body: format!("Binance still in maintenance: {}", status.msg),
+                },
+                Err(e) => e,
+            };
+
+            if Instant::now() + poll_interval > deadline {
+                bail!(last);
+            }
+            thread::sleep(poll_interval);
+        }
+    }
+
     // Obtain exchange information (rate limits, symbol metadata etc)
     pub fn exchange_info(&self) -> Result<(ExchangeInformation)> {
-        let data: String = self.client.get("/api/v1/exchangeInfo", "")?;
+        self.get_exchange_info()
+    }
+
+    // Exchange information of ALL symbols, weight 20
+    pub fn get_exchange_info(&self) -> Result<(ExchangeInformation)> {
+        let data: String = self.client.get(API_V3_EXCHANGE_INFO, "")?;
+
+        let info: ExchangeInformation = from_json(&data)?;
+
+        Ok(info)
+    }
+
+    // Exchange information with the metadata of ONE symbol only
+    pub fn get_exchange_info_for_symbol<S>(&self, symbol: S) -> Result<(ExchangeInformation)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        let data: String = self.client.get(API_V3_EXCHANGE_INFO, &request)?;
+
+        let info: ExchangeInformation = from_json(&data)?;
+
+        Ok(info)
+    }
+
+    // Exchange information with the metadata of several symbols. One unknown symbol fails the
+    // whole request with BinanceErrorCode::InvalidSymbol
+    pub fn get_exchange_info_for_symbols(&self, symbols: &[&str]) -> Result<(ExchangeInformation)> {
+        self.get_exchange_info_for(symbols)
+    }
+
+    pub fn get_exchange_info_for(&self, symbols: &[&str]) -> Result<(ExchangeInformation)> {
+        if symbols.is_empty() {
+            bail!(BinanceError::Parameter("No symbols given".into()));
+        }
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbols".into(), build_symbols_param(symbols));
+        let request = build_request(&parameters);
+
+        let data: String = self.client.get(API_V3_EXCHANGE_INFO, &request)?;
+
+        let info: ExchangeInformation = from_json(&data)?;
+
+        Ok(info)
+    }
+
+    // Exchange information of the symbols tradable with any of permissions, e.g. Permission::Spot
+    pub fn get_exchange_info_with_permissions(&self, permissions: &[Permission]) -> Result<(ExchangeInformation)> {
+        if permissions.is_empty() {
+            bail!(BinanceError::Parameter("No permissions given".into()));
+        }
+
+        let permissions: Vec<&str> = permissions.iter().map(Permission::as_str).collect();
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("permissions".into(), build_array_param(&permissions));
+        let request = build_request(&parameters);
+
+        let data: String = self.client.get(API_V3_EXCHANGE_INFO, &request)?;
 
-        let info: ExchangeInformation = from_str(data.as_str())?;
+        let info: ExchangeInformation = from_json(&data)?;
 
         Ok(info)
     }
@@ -0,0 +1,39 @@
+use model::*;
+use errors::*;
+use general::General;
+use client_async::*;
+use futures::Future;
+use tokio_core::reactor::Handle;
+
+static API_V3_PING: &'static str = "/api/v3/ping";
+static API_V3_TIME: &'static str = "/api/v3/time";
+static API_V3_EXCHANGE_INFO: &'static str = "/api/v3/exchangeInfo";
+
+// Futures based counterpart of General
+#[derive(Clone)]
+pub struct AsyncGeneral {
+    pub client: AsyncClient,
+}
+
+impl AsyncGeneral {
+    pub fn new(general: &General, handle: &Handle) -> Result<(AsyncGeneral)> {
+        Ok(AsyncGeneral {
+            client: AsyncClient::new(&general.client, handle)?,
+        })
+    }
+
+    // Test connectivity
+    pub fn ping(&self) -> ResponseFuture<String> {
+        Box::new(self.client.get(API_V3_PING, "").map(|_| "pong".into()))
+    }
+
+    // Check server time
+    pub fn get_server_time(&self) -> ResponseFuture<ServerTime> {
+        self.client.get_json(API_V3_TIME, "")
+    }
+
+    // Exchange information of ALL symbols, weight 20
+    pub fn get_exchange_info(&self) -> ResponseFuture<ExchangeInformation> {
+        self.client.get_json(API_V3_EXCHANGE_INFO, "")
+    }
+}
@@ -1,29 +1,66 @@
 #![cfg_attr(feature = "clippy", feature(plugin))]
 #![cfg_attr(feature = "clippy", plugin(clippy))]
-#[macro_use]
-extern crate error_chain;
-
 extern crate hex;
+extern crate native_tls;
 extern crate reqwest;
 extern crate ring;
 extern crate serde;
 extern crate serde_json;
 
 extern crate tungstenite;
+#[cfg(feature = "tokio")]
+extern crate futures;
+#[cfg(feature = "tokio")]
+extern crate tokio_core;
+#[cfg(feature = "tokio")]
+extern crate tokio_tungstenite;
+#[cfg(feature = "decimal")]
+extern crate rust_decimal;
+#[cfg(feature = "chrono")]
+extern crate chrono;
 extern crate url;
+#[macro_use]
+extern crate log;
 
 #[macro_use]
 extern crate serde_derive;
 
-mod util;
-mod client;
+#[macro_use]
 pub mod errors;
+mod util;
+pub mod client;
 
 pub mod model;
+pub mod filters;
 
 pub mod api;
 pub mod general;
 pub mod account;
 pub mod market;
 pub mod userstream;
+pub mod margin;
+pub mod wallet;
+pub mod sub_account;
+pub mod savings;
+pub mod staking;
+pub mod convert;
+pub mod usdm_futures;
+pub mod coinm_futures;
+pub mod proxy;
+pub mod streams;
+pub mod websocket_api;
+pub mod queue;
+pub mod recording;
 pub mod websockets;
+pub mod websockets_pool;
+#[cfg(feature = "tokio")]
+pub mod websockets_async;
+#[cfg(feature = "tokio")]
+pub mod client_async;
+#[cfg(feature = "tokio")]
+pub mod general_async;
+#[cfg(feature = "tokio")]
+pub mod market_async;
+#[cfg(feature = "tokio")]
+pub mod account_async;
+pub mod orderbook;
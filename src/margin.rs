@@ -0,0 +1,311 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::BTreeMap;
+
+static SAPI_V1_MARGIN_TRANSFER: &'static str = "/sapi/v1/margin/transfer";
+static SAPI_V1_MARGIN_LOAN: &'static str = "/sapi/v1/margin/loan";
+static SAPI_V1_MARGIN_REPAY: &'static str = "/sapi/v1/margin/repay";
+static SAPI_V1_MARGIN_ORDER: &'static str = "/sapi/v1/margin/order";
+static SAPI_V1_MARGIN_ACCOUNT: &'static str = "/sapi/v1/margin/account";
+static SAPI_V1_MARGIN_MAX_BORROWABLE: &'static str = "/sapi/v1/margin/maxBorrowable";
+static SAPI_V1_ISOLATED_ACCOUNT: &'static str = "/sapi/v1/margin/isolated/account";
+static SAPI_V1_ISOLATED_TRANSFER: &'static str = "/sapi/v1/margin/isolated/transfer";
+
+// Cross margin endpoints
+#[derive(Clone)]
+pub struct Margin {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginTransferDirection {
+    SpotToMargin,
+    MarginToSpot,
+}
+
+// Side of an isolated margin transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolatedMarginAccount {
+    Spot,
+    IsolatedMargin,
+}
+
+impl IsolatedMarginAccount {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            IsolatedMarginAccount::Spot => "SPOT",
+            IsolatedMarginAccount::IsolatedMargin => "ISOLATED_MARGIN",
+        }
+    }
+}
+
+// Borrowing and repaying done together with a margin order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideEffectType {
+    NoSideEffect,
+    // Borrows what the order needs
+    MarginBuy,
+    // Repays the loan with what the order returns
+    AutoRepay,
+}
+
+impl SideEffectType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            SideEffectType::NoSideEffect => "NO_SIDE_EFFECT",
+            SideEffectType::MarginBuy => "MARGIN_BUY",
+            SideEffectType::AutoRepay => "AUTO_REPAY",
+        }
+    }
+}
+
+// Parameters of a margin order, price and time_in_force are for LIMIT orders only
+#[derive(Debug, Clone)]
+pub struct MarginOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub qty: Amount,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
+    pub time_in_force: Option<TimeInForce>,
+    pub new_client_order_id: Option<String>,
+    pub is_isolated: bool,
+    pub side_effect_type: SideEffectType,
+}
+
+impl MarginOrderRequest {
+    pub fn limit<S>(symbol: S, side: OrderSide, qty: Amount, price: Amount) -> MarginOrderRequest
+        where S: Into<String>
+    {
+        MarginOrderRequest {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            qty,
+            price: Some(price),
+            stop_price: None,
+            time_in_force: Some(TimeInForce::GoodTillCanceled),
+            new_client_order_id: None,
+            is_isolated: false,
+            side_effect_type: SideEffectType::NoSideEffect,
+        }
+    }
+
+    pub fn market<S>(symbol: S, side: OrderSide, qty: Amount) -> MarginOrderRequest
+        where S: Into<String>
+    {
+        MarginOrderRequest {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Market,
+            qty,
+            price: None,
+            stop_price: None,
+            time_in_force: None,
+            new_client_order_id: None,
+            is_isolated: false,
+            side_effect_type: SideEffectType::NoSideEffect,
+        }
+    }
+}
+
+impl Margin {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Move an asset between the spot and the cross margin account
+    pub fn margin_transfer<S>(
+        &self, asset: S, amount: Amount, direction: MarginTransferDirection,
+    ) -> Result<(TransactionId)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("amount".into(), amount.to_string());
+        let transfer_type = match direction {
+            MarginTransferDirection::SpotToMargin => "1",
+            MarginTransferDirection::MarginToSpot => "2",
+        };
+        parameters.insert("type".into(), transfer_type.into());
+
+        self.post_transaction(SAPI_V1_MARGIN_TRANSFER, parameters)
+    }
+
+    // Borrow
+    pub fn margin_loan<S>(&self, asset: S, amount: Amount) -> Result<(TransactionId)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        self.post_transaction(SAPI_V1_MARGIN_LOAN, parameters)
+    }
+
+    pub fn margin_repay<S>(&self, asset: S, amount: Amount) -> Result<(TransactionId)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        self.post_transaction(SAPI_V1_MARGIN_REPAY, parameters)
+    }
+
+    pub fn margin_order(&self, order: MarginOrderRequest) -> Result<(Transaction)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), order.symbol);
+        parameters.insert("side".into(), order.side.as_str().into());
+        parameters.insert("type".into(), order.order_type.as_str().into());
+        parameters.insert("quantity".into(), order.qty.to_string());
+        if let Some(price) = order.price {
+            parameters.insert("price".into(), price.to_string());
+        }
+        if let Some(stop_price) = order.stop_price {
+            parameters.insert("stopPrice".into(), stop_price.to_string());
+        }
+        if let Some(time_in_force) = order.time_in_force {
+            parameters.insert("timeInForce".into(), time_in_force.as_str().into());
+        }
+        if let Some(client_order_id) = order.new_client_order_id {
+            parameters.insert("newClientOrderId".into(), client_order_id);
+        }
+        if order.is_isolated {
+            parameters.insert("isIsolated".into(), "TRUE".into());
+        }
+        parameters.insert("sideEffectType".into(), order.side_effect_type.as_str().into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_MARGIN_ORDER, &request)?;
+        let transaction: Transaction = from_json(&data)?;
+
+        Ok(transaction)
+    }
+
+    pub fn cancel_margin_order<S>(&self, symbol: S, order_id: u64, is_isolated: bool) -> Result<(OrderCanceled)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+        if is_isolated {
+            parameters.insert("isIsolated".into(), "TRUE".into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(SAPI_V1_MARGIN_ORDER, &request)?;
+        let order_canceled: OrderCanceled = from_json(&data)?;
+
+        Ok(order_canceled)
+    }
+
+    // Cross margin account details
+    pub fn get_margin_account(&self) -> Result<(MarginAccount)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_MARGIN_ACCOUNT, &request)?;
+        let margin_account: MarginAccount = from_json(&data)?;
+
+        Ok(margin_account)
+    }
+
+    pub fn get_max_borrowable<S>(&self, asset: S) -> Result<(MaxBorrowable)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), asset.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_MARGIN_MAX_BORROWABLE, &request)?;
+        let max_borrowable: MaxBorrowable = from_json(&data)?;
+
+        Ok(max_borrowable)
+    }
+
+    // Open the isolated margin account of a symbol
+    pub fn enable_isolated_margin<S>(&self, symbol: S) -> Result<(IsolatedMarginStatus)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_ISOLATED_ACCOUNT, &request)?;
+        let status: IsolatedMarginStatus = from_json(&data)?;
+
+        Ok(status)
+    }
+
+    pub fn disable_isolated_margin<S>(&self, symbol: S) -> Result<(IsolatedMarginStatus)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(SAPI_V1_ISOLATED_ACCOUNT, &request)?;
+        let status: IsolatedMarginStatus = from_json(&data)?;
+
+        Ok(status)
+    }
+
+    // Move asset between spot and the isolated margin account of symbol
+    pub fn isolated_margin_transfer<S1, S2>(
+        &self, asset: S1, symbol: S2, from: IsolatedMarginAccount, to: IsolatedMarginAccount, amount: Amount,
+    ) -> Result<(TransactionId)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        if from == to {
+            bail!(BinanceError::Parameter(
+                "Isolated margin transfer needs different source and destination accounts".into(),
+            ));
+        }
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("transFrom".into(), from.as_str().into());
+        parameters.insert("transTo".into(), to.as_str().into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        self.post_transaction(SAPI_V1_ISOLATED_TRANSFER, parameters)
+    }
+
+    // All isolated margin pairs, or up to 5 given symbols
+    pub fn get_isolated_margin_account(&self, symbols: Option<&[&str]>) -> Result<(IsolatedMarginAccountInfo)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbols) = symbols {
+            if symbols.is_empty() || symbols.len() > 5 {
+                bail!(BinanceError::Parameter("Isolated margin account takes 1 to 5 symbols".into()));
+            }
+            parameters.insert("symbols".into(), symbols.join(","));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ISOLATED_ACCOUNT, &request)?;
+        let account: IsolatedMarginAccountInfo = from_json(&data)?;
+
+        Ok(account)
+    }
+
+    // margin_order on the isolated margin account of order.symbol
+    pub fn isolated_margin_order(&self, mut order: MarginOrderRequest) -> Result<(Transaction)> {
+        order.is_isolated = true;
+
+        self.margin_order(order)
+    }
+
+    fn post_transaction(&self, endpoint: &str, parameters: BTreeMap<String, String>) -> Result<(TransactionId)> {
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(endpoint, &request)?;
+        let transaction_id: TransactionId = from_json(&data)?;
+
+        Ok(transaction_id)
+    }
+}
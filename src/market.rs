@@ -2,8 +2,37 @@ use util::*;
 use model::*;
 use client::*;
 use errors::*;
-use std::collections::BTreeMap;
-use serde_json::{Value, from_str};
+use std::collections::{BTreeMap, HashMap};
+use std::thread;
+use std::time::Duration;
+
+static API_V3_DEPTH: &'static str = "/api/v3/depth";
+static DEPTH_LIMITS: [u16; 8] = [5, 10, 20, 50, 100, 500, 1000, 5000];
+static API_V3_TICKER_PRICE: &'static str = "/api/v3/ticker/price";
+static API_V3_BOOK_TICKER: &'static str = "/api/v3/ticker/bookTicker";
+static API_V3_TICKER_24HR: &'static str = "/api/v3/ticker/24hr";
+static API_V3_TICKER: &'static str = "/api/v3/ticker";
+static ROLLING_WINDOW_MAX_SYMBOLS: usize = 100;
+static API_V3_KLINES: &'static str = "/api/v3/klines";
+static KLINES_MAX_LIMIT: u16 = 1000;
+static API_V3_TRADES: &'static str = "/api/v3/trades";
+static RECENT_TRADES_MAX_LIMIT: u16 = 1000;
+static API_V3_HISTORICAL_TRADES: &'static str = "/api/v3/historicalTrades";
+static HISTORICAL_TRADES_MAX_LIMIT: u16 = 1000;
+static API_V3_AVG_PRICE: &'static str = "/api/v3/avgPrice";
+static API_V3_AGG_TRADES: &'static str = "/api/v3/aggTrades";
+static AGG_TRADES_MAX_LIMIT: u16 = 1000;
+// Longest startTime to endTime range aggTrades accepts, in ms
+static AGG_TRADES_MAX_RANGE: u64 = 60 * 60 * 1000;
+
+// Options of get_klines_range
+#[derive(Debug, Clone, Default)]
+pub struct KlineRangeOptions {
+    // Pause between two requests, to spare the request weight of long backfills
+    pub delay: Option<Duration>,
+    // Keep the last kline when it is still open, its values change until close_time
+    pub include_open_kline: bool,
+}
 
 #[derive(Clone)]
 pub struct Market {
@@ -13,6 +42,10 @@ pub struct Market {
 
 // Market Data endpoints
 impl Market {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
     // Order book (Default 100; max 100)
     pub fn get_depth<S>(&self, symbol: S) -> Result<(OrderBook)>
         where S: Into<String>
@@ -24,22 +57,59 @@ impl Market {
 
         let data = self.client.get("/api/v1/depth", &request)?;
 
-        let order_book: OrderBook = from_str(data.as_str())?;
+        let order_book: OrderBook = from_json(&data)?;
+
+        Ok(order_book)
+    }
+
+    // Order book with limit levels, one of 5, 10, 20, 50, 100, 500, 1000 or 5000.
+    // Weight 1 up to 100, 5 for 500, 10 for 1000 and 50 for 5000
+    pub fn get_custom_depth<S>(&self, symbol: S, limit: u16) -> Result<(OrderBook)>
+        where S: Into<String>
+    {
+        check_depth_limit(limit)?;
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), limit.to_string());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_DEPTH, &request)?;
+
+        let order_book: OrderBook = from_json(&data)?;
 
         Ok(order_book)
     }
 
+    // get_custom_depth with the weight used and server time of the response
+    pub fn get_custom_depth_with_meta<S>(&self, symbol: S, limit: u16) -> Result<(ApiResponse<OrderBook>)>
+        where S: Into<String>
+    {
+        check_depth_limit(limit)?;
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), limit.to_string());
+        let request = build_request(&parameters);
+
+        let answer = self.client.get_with_meta(API_V3_DEPTH, &request)?;
+
+        answer.and_then(|data| from_json(&data))
+    }
+
     // Latest price for ALL symbols.
     pub fn get_all_prices(&self) -> Result<(Prices)> {
         let data = self.client.get("/api/v1/ticker/allPrices", "")?;
 
-        let prices: Prices = from_str(data.as_str())?;
+        let prices: Prices = from_json(&data)?;
 
         Ok(prices)
     }
 
     // Latest price for ONE symbol.
-    pub fn get_price<S>(&self, symbol: S) -> Result<(f64)>
+    pub fn get_price<S>(&self, symbol: S) -> Result<(Amount)>
         where S: Into<String>
     {
         match self.get_all_prices() {
@@ -51,45 +121,58 @@ impl Market {
                             return Ok(par.price);
                         }
                     }
-                    bail!("Symbol not found");
+                    bail!(BinanceError::Parameter("Symbol not found".into()));
                 }
             },
             Err(e) => Err(e),
         }
     }
 
+    // Latest price for several symbols, weight 4 (like ALL symbols)
+    pub fn get_prices_for(&self, symbols: &[&str]) -> Result<(Vec<SymbolPrice>)> {
+        let data = self.get_batch(API_V3_TICKER_PRICE, symbols, BTreeMap::new())?;
+
+        let prices: Vec<SymbolPrice> = from_json(&data)?;
+
+        Ok(prices)
+    }
+
     // Symbols order book ticker
     // -> Best price/qty on the order book for ALL symbols.
     pub fn get_all_book_tickers(&self) -> Result<(BookTickers)> {
-        let data = self.client.get("/api/v1/ticker/allBookTickers", "")?;
+        let data = self.client.get(API_V3_BOOK_TICKER, "")?;
 
-        let book_tickers: BookTickers = from_str(data.as_str())?;
+        let book_tickers: BookTickers = from_json(&data)?;
 
         Ok(book_tickers)
     }
 
     // -> Best price/qty on the order book for ONE symbol
-    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<(Tickers)> 
+    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<(Tickers)>
         where S: Into<String>
     {
-        match self.get_all_book_tickers() {
-            Ok(answer) => match answer {
-                BookTickers::AllBookTickers(book_tickers) => {
-                    let cmp_symbol = symbol.into();
-                    for obj in book_tickers {
-                        if obj.symbol == cmp_symbol {
-                            let ticker: Tickers = obj;
-                            return Ok(ticker);
-                        }
-                    }
-                    bail!("Symbol not found");
-                }
-            },
-            Err(e) => Err(e),
-        }
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_BOOK_TICKER, &request)?;
+
+        let ticker: Tickers = from_json(&data)?;
+
+        Ok(ticker)
     }
 
-    // 24hr ticker price change statistics
+    // -> Best price/qty on the order book for several symbols, weight 4 (like ALL symbols)
+    pub fn get_book_tickers_for(&self, symbols: &[&str]) -> Result<(Vec<Tickers>)> {
+        let data = self.get_batch(API_V3_BOOK_TICKER, symbols, BTreeMap::new())?;
+
+        let tickers: Vec<Tickers> = from_json(&data)?;
+
+        Ok(tickers)
+    }
+
+    // 24hr ticker price change statistics, weight 1
     pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<(PriceStats)>
         where S: Into<String>
     {
@@ -98,41 +181,421 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        let data = self.client.get("/api/v1/ticker/24hr", &request)?;
+        let data = self.client.get(API_V3_TICKER_24HR, &request)?;
 
-        let stats: PriceStats = from_str(data.as_str())?;
+        let stats: PriceStats = from_json(&data)?;
 
         Ok(stats)
     }
 
-    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
-    pub fn get_klines<S1,S2>(&self, symbol: S1, interval: S2, limit: i32) -> Result<(KlineSummaries)> 
-        where S1: Into<String>, S2: Into<String>
+    // 24hr ticker price change statistics for ALL symbols, weight 40
+    pub fn get_all_24h_price_stats(&self) -> Result<(Vec<PriceStats>)> {
+        let data = self.client.get(API_V3_TICKER_24HR, "")?;
+
+        let stats: Vec<PriceStats> = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // MINI 24hr ticker, without the bid/ask, weighted average and previous close, weight 1
+    pub fn get_24h_mini_price_stats<S>(&self, symbol: S) -> Result<(MiniPriceStats)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("type".into(), TickerType::Mini.as_str().into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_TICKER_24HR, &request)?;
+
+        let stats: MiniPriceStats = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // MINI 24hr ticker for ALL symbols, about half the size of the FULL one, weight 40
+    pub fn get_all_24h_mini_price_stats(&self) -> Result<(Vec<MiniPriceStats>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("type".into(), TickerType::Mini.as_str().into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_TICKER_24HR, &request)?;
+
+        let stats: Vec<MiniPriceStats> = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // 24hr ticker price change statistics for several symbols, weight 2 up to 20 symbols,
+    // 40 up to 100 and 80 beyond
+    pub fn get_24h_price_stats_for(&self, symbols: &[&str]) -> Result<(Vec<PriceStats>)> {
+        let data = self.get_batch(API_V3_TICKER_24HR, symbols, BTreeMap::new())?;
+
+        let stats: Vec<PriceStats> = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // Price change statistics over a rolling window, weight 4 per symbol
+    pub fn get_rolling_window_stats<S>(&self, symbol: S, window_size: WindowSize) -> Result<(RollingWindowStats)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        check_window_size(window_size)?;
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("windowSize".into(), window_size.to_string());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_TICKER, &request)?;
+
+        let stats: RollingWindowStats = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // Rolling window statistics for up to 100 symbols, the weight is capped at 200
+    pub fn get_rolling_window_stats_for(
+        &self, symbols: &[&str], window_size: WindowSize,
+    ) -> Result<(Vec<RollingWindowStats>)> {
+        if symbols.len() > ROLLING_WINDOW_MAX_SYMBOLS {
+            bail!(BinanceError::Parameter(format!("Use 1 to {} symbols", ROLLING_WINDOW_MAX_SYMBOLS)));
+        }
+        check_window_size(window_size)?;
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("windowSize".into(), window_size.to_string());
+        let data = self.get_batch(API_V3_TICKER, symbols, parameters)?;
+
+        let stats: Vec<RollingWindowStats> = from_json(&data)?;
+
+        Ok(stats)
+    }
+
+    // Returns up to 'limit' klines (500 when None, at most 1000) for given symbol and interval
+    // (KlineInterval::Minutes1, "5m", ...), optionally between start_time and end_time in ms
+    pub fn get_klines<S1, S2, L, T1, T2>(
+        &self, symbol: S1, interval: S2, limit: L, start_time: T1, end_time: T2,
+    ) -> Result<(KlineSummaries)>
+        where S1: Into<String>, S2: Into<String>, L: Into<Option<u16>>, T1: Into<Option<u64>>, T2: Into<Option<u64>>
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
         parameters.insert("symbol".into(), symbol.into());
         parameters.insert("interval".into(), interval.into());
-        parameters.insert("limit".into(), format!("{}", limit));
+        if let Some(limit) = limit.into() {
+            if limit == 0 || limit > KLINES_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!("Limit {} out of range, use 1 to {}", limit, KLINES_MAX_LIMIT)));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        if let Some(start_time) = start_time.into() {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time.into() {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
         let request = build_request(&parameters);
 
-        let data = self.client.get("/api/v1/klines", &request)?;
-        let parsed_data: Vec<Vec<Value>> = from_str(data.as_str())?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            parsed_data.iter().map(|row|KlineSummary{
-                open_time: to_i64(&row[0]),
-                open: to_f64(&row[1]),
-                high: to_f64(&row[2]),
-                low: to_f64(&row[3]),
-                close: to_f64(&row[4]),
-                volume: to_f64(&row[5]),
-                close_time: to_i64(&row[6]),
-                quote_asset_volume: to_f64(&row[7]),
-                number_of_trades: to_i64(&row[8]),
-                taker_buy_base_asset_volume: to_f64(&row[9]),
-                taker_buy_quote_asset_volume: to_f64(&row[10]),
-            }).collect());
+        let data = self.client.get(API_V3_KLINES, &request)?;
+        let klines: KlineSummaries = from_json(&data)?;
+
         Ok(klines)
     }
+
+    // All klines opened between start_time and end_time in ms, requested 1000 at a time
+    pub fn get_klines_range<S1, S2>(
+        &self, symbol: S1, interval: S2, start_time: u64, end_time: u64, options: KlineRangeOptions,
+    ) -> Result<(Vec<KlineSummary>)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let symbol = symbol.into();
+        let interval = interval.into();
+        let mut klines: Vec<KlineSummary> = Vec::new();
+        let mut next_start = start_time;
+
+        while next_start <= end_time {
+            if !klines.is_empty() {
+                if let Some(delay) = options.delay {
+                    thread::sleep(delay);
+                }
+            }
+
+            let KlineSummaries::AllKlineSummaries(page) =
+                self.get_klines(symbol.as_str(), interval.as_str(), KLINES_MAX_LIMIT, next_start, end_time)?;
+            let page_len = page.len();
+            let previous_start = next_start;
+
+            for kline in page {
+                // Never the same kline twice at a page boundary
+                if kline.open_time < next_start as i64 {
+                    continue;
+                }
+                next_start = kline.close_time as u64 + 1;
+                klines.push(kline);
+            }
+
+            // A short page is the last one, a page without new klines would repeat forever
+            if page_len < KLINES_MAX_LIMIT as usize || next_start == previous_start {
+                break;
+            }
+        }
+
+        if !options.include_open_kline {
+            let now = get_timestamp()? as i64;
+            if klines.last().map_or(false, |kline| kline.close_time >= now) {
+                klines.pop();
+            }
+        }
+
+        Ok(klines)
+    }
+
+    // Latest public trades, limit is 500 when None, at most 1000
+    pub fn get_recent_trades<S>(&self, symbol: S, limit: Option<u16>) -> Result<(Vec<MarketTrade>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        if let Some(limit) = limit {
+            if limit == 0 || limit > RECENT_TRADES_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, RECENT_TRADES_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_TRADES, &request)?;
+        let trades: Vec<MarketTrade> = from_json(&data)?;
+
+        Ok(trades)
+    }
+
+    // Older trades than the recent ones, from trade id from_id on (the latest when None).
+    // Needs an API key but no signature; limit is 500 when None, at most 1000
+    pub fn get_historical_trades<S>(
+        &self, symbol: S, from_id: Option<u64>, limit: Option<u16>,
+    ) -> Result<(Vec<MarketTrade>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        if let Some(from_id) = from_id {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let Some(limit) = limit {
+            if limit == 0 || limit > HISTORICAL_TRADES_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, HISTORICAL_TRADES_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        let request = build_request(&parameters);
+
+        let data = self.client.get_with_api_key(API_V3_HISTORICAL_TRADES, &request)?;
+        let trades: Vec<MarketTrade> = from_json(&data)?;
+
+        Ok(trades)
+    }
+
+    // Aggregated trades from agg trade id from_id on, or between start_time and end_time in ms
+    // (at most one hour apart when both are given). The latest ones when all are None
+    pub fn get_agg_trades<S>(
+        &self, symbol: S, from_id: Option<u64>, start_time: Option<u64>, end_time: Option<u64>, limit: Option<u16>,
+    ) -> Result<(Vec<AggTrade>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        if let Some(from_id) = from_id {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+            if end_time < start_time || end_time - start_time > AGG_TRADES_MAX_RANGE {
+                bail!(BinanceError::Parameter(format!(
+                    "Time range {} to {} must not be reversed or longer than one hour",
+                    start_time, end_time
+                )));
+            }
+        }
+        if let Some(start_time) = start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit {
+            if limit == 0 || limit > AGG_TRADES_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, AGG_TRADES_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_AGG_TRADES, &request)?;
+        let trades: Vec<AggTrade> = from_json(&data)?;
+
+        Ok(trades)
+    }
+
+    pub fn get_average_price<S>(&self, symbol: S) -> Result<(AveragePrice)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(API_V3_AVG_PRICE, &request)?;
+        let average_price: AveragePrice = from_json(&data)?;
+
+        Ok(average_price)
+    }
+
+    // Average price of each symbol, one request per symbol
+    pub fn get_average_prices(&self, symbols: &[&str]) -> Result<(HashMap<String, AveragePrice>)> {
+        let mut average_prices = HashMap::new();
+        for symbol in symbols {
+            average_prices.insert(symbol.to_string(), self.get_average_price(*symbol)?);
+        }
+
+        Ok(average_prices)
+    }
+
+    // GET with symbols=[...]. Binance fails the whole request on one invalid symbol, the
+    // BinanceError then tells it came from a batch
+    fn get_batch(
+        &self, endpoint: &str, symbols: &[&str], mut parameters: BTreeMap<String, String>,
+    ) -> Result<(String)> {
+        if symbols.is_empty() {
+            bail!(BinanceError::Parameter("No symbols given".into()));
+        }
+
+        parameters.insert("symbols".into(), build_symbols_param(symbols));
+        let request = build_request(&parameters);
+
+        match self.client.get(endpoint, &request) {
+            Err(BinanceError::Api { status, code, msg }) => bail!(BinanceError::Api {
+                status,
+                code,
+                msg: format!("{} (batch request for {})", msg, symbols.join(",")),
+            }),
+            answer => answer,
+        }
+    }
+}
+
+pub(crate) fn check_depth_limit(limit: u16) -> Result<()> {
+    if !DEPTH_LIMITS.contains(&limit) {
+        bail!(BinanceError::Parameter(format!("Limit {} not supported, use one of {:?}", limit, DEPTH_LIMITS)));
+    }
+
+    Ok(())
+}
+
+fn check_window_size(window_size: WindowSize) -> Result<()> {
+    if !window_size.is_valid() {
+        bail!(BinanceError::Parameter(format!(
+            "Window size {} out of range, use 1m to 59m, 1h to 23h or 1d to 7d",
+            window_size
+        )));
+    }
+
+    Ok(())
+}
+
+// Indexes the answer of get_all_book_tickers by symbol
+pub fn book_tickers_by_symbol(book_tickers: BookTickers) -> HashMap<String, Tickers> {
+    let BookTickers::AllBookTickers(book_tickers) = book_tickers;
+
+    book_tickers.into_iter().map(|ticker| (ticker.symbol.clone(), ticker)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Binance, Config};
+    use client::fake::{answer, client, FakeTransport};
+    use std::sync::Arc;
+
+    static DEPTH: &'static str = r#"{"lastUpdateId":1027024,"bids":[["4.00000000","431.00000000"]],"asks":[["4.00000200","12.00000000"],["4.00000300","3.50000000"]]}"#;
+
+    static ALL_PRICES: &'static str = r#"[{"symbol":"ETHBTC","price":"0.06980000"},{"symbol":"LTCBTC","price":"0.00165300"},{"symbol":"BNBBTC","price":"0.01003600"}]"#;
+
+    static TRADES: &'static str = r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true}]"#;
+
+    fn market(transport: &Arc<FakeTransport>) -> Market {
+        Market::new_with_client(client(transport), &Config::default())
+    }
+
+    #[test]
+    fn get_depth() {
+        let transport = FakeTransport::new(vec![answer(200, DEPTH)]);
+
+        let book = market(&transport).get_depth("BNBBTC").unwrap();
+        assert_eq!(book.last_update_id, 1027024);
+        assert_eq!((book.bids[0].price, book.bids[0].qty), (amount("4.00000000"), amount("431")));
+        assert_eq!(book.asks.len(), 2);
+        assert_eq!(book.asks[1].qty, amount("3.5"));
+        assert_eq!(transport.requests()[0].1, "https://api.binance.com/api/v1/depth?symbol=BNBBTC");
+    }
+
+    #[test]
+    fn get_price() {
+        let transport = FakeTransport::new(vec![answer(200, ALL_PRICES), answer(200, ALL_PRICES)]);
+        let market = market(&transport);
+
+        assert_eq!(market.get_price("LTCBTC").unwrap(), amount("0.001653"));
+        match market.get_price("NOPE") {
+            Err(BinanceError::Parameter(_)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn get_depth_of_an_unknown_symbol() {
+        let transport = FakeTransport::new(vec![answer(400, r#"{"code":-1121,"msg":"Invalid symbol."}"#)]);
+
+        match market(&transport).get_depth("NOPE") {
+            Err(ref error) if error.error_code() == Some(BinanceErrorCode::InvalidSymbol) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn get_depth_of_a_truncated_answer() {
+        let transport = FakeTransport::new(vec![answer(200, r#"{"lastUpdateId":1027024,"bids":[["4.000"#)]);
+
+        match market(&transport).get_depth("BNBBTC") {
+            Err(BinanceError::Json { payload: Some(ref payload), .. }) => {
+                assert!(payload.starts_with("{\"lastUpdateId"))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn recent_trades_with_quote_qty() {
+        let transport = FakeTransport::new(vec![answer(200, TRADES)]);
+
+        let trades = market(&transport).get_recent_trades("BNBBTC", Some(1)).unwrap();
+        assert_eq!((trades[0].id, trades[0].time), (28457, 1499865549590));
+        assert_eq!(trades[0].qty, amount("12"));
+        assert_eq!(trades[0].quote_qty, amount("48.000012"));
+        assert!(trades[0].is_buyer_maker);
+    }
 }
@@ -0,0 +1,109 @@
+use util::*;
+use model::*;
+use errors::*;
+use market::{check_depth_limit, Market};
+use client_async::*;
+use futures::{future, Future};
+use std::collections::BTreeMap;
+use tokio_core::reactor::Handle;
+
+static API_V3_DEPTH: &'static str = "/api/v3/depth";
+static API_V3_TICKER_PRICE: &'static str = "/api/v3/ticker/price";
+static API_V3_BOOK_TICKER: &'static str = "/api/v3/ticker/bookTicker";
+static API_V3_TICKER_24HR: &'static str = "/api/v3/ticker/24hr";
+
+// Futures based counterpart of Market, e.g. several prices fetched at once with
+// get_price("BNBBTC").join(get_price("ETHBTC"))
+#[derive(Clone)]
+pub struct AsyncMarket {
+    pub client: AsyncClient,
+}
+
+impl AsyncMarket {
+    pub fn new(market: &Market, handle: &Handle) -> Result<(AsyncMarket)> {
+        Ok(AsyncMarket {
+            client: AsyncClient::new(&market.client, handle)?,
+        })
+    }
+
+    // Order book, 100 levels
+    pub fn get_depth<S>(&self, symbol: S) -> ResponseFuture<OrderBook>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client.get_json(API_V3_DEPTH, &request)
+    }
+
+    // Order book with limit levels, see Market::get_custom_depth
+    pub fn get_custom_depth<S>(&self, symbol: S, limit: u16) -> ResponseFuture<OrderBook>
+        where S: Into<String>
+    {
+        if let Err(e) = check_depth_limit(limit) {
+            return Box::new(future::err(e));
+        }
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), limit.to_string());
+        let request = build_request(&parameters);
+
+        self.client.get_json(API_V3_DEPTH, &request)
+    }
+
+    // Latest price for ONE symbol
+    pub fn get_price<S>(&self, symbol: S) -> ResponseFuture<Amount>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        Box::new(
+            self.client
+                .get_json(API_V3_TICKER_PRICE, &request)
+                .map(|price: SymbolPrice| price.price),
+        )
+    }
+
+    // Latest price for ALL symbols
+    pub fn get_all_prices(&self) -> ResponseFuture<Vec<SymbolPrice>> {
+        self.client.get_json(API_V3_TICKER_PRICE, "")
+    }
+
+    // Best price/qty on the order book for ONE symbol
+    pub fn get_book_ticker<S>(&self, symbol: S) -> ResponseFuture<Tickers>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client.get_json(API_V3_BOOK_TICKER, &request)
+    }
+
+    // 24hr ticker price change statistics, weight 1
+    pub fn get_24h_price_stats<S>(&self, symbol: S) -> ResponseFuture<PriceStats>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client.get_json(API_V3_TICKER_24HR, &request)
+    }
+
+    // Latest price for several symbols in one request
+    pub fn get_prices_for(&self, symbols: &[&str]) -> ResponseFuture<Vec<SymbolPrice>> {
+        if symbols.is_empty() {
+            return Box::new(future::err(BinanceError::Parameter("No symbols given".into())));
+        }
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbols".into(), build_symbols_param(symbols));
+        let request = build_request(&parameters);
+
+        self.client.get_json(API_V3_TICKER_PRICE, &request)
+    }
+}
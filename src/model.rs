@@ -1,27 +1,141 @@
-#[derive(Serialize, Deserialize, Clone)]
+use std::fmt;
+use std::str::FromStr;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "chrono")]
+use std::time::{Duration, UNIX_EPOCH};
+
+// Prices, quantities, balances and commissions, parsed from the decimal strings Binance sends. f64 by
+// default, rust_decimal::Decimal with the decimal feature so sums and step checks stay exact
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+#[cfg(feature = "decimal")]
+pub type Amount = ::rust_decimal::Decimal;
+
+#[cfg(not(feature = "decimal"))]
+pub(crate) const ZERO: Amount = 0.0;
+#[cfg(feature = "decimal")]
+pub(crate) const ZERO: Amount = ::rust_decimal::Decimal::ZERO;
+
+// "0.001" as an Amount of either kind, for the tests
+#[cfg(test)]
+pub(crate) fn amount(value: &str) -> Amount {
+    string_or_float::parse(value).unwrap()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerTime {
     pub server_time: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// {"status":0,"msg":"normal"} or {"status":1,"msg":"system_maintenance"}
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SystemStatus {
+    pub status: SystemState,
+    pub msg: String,
+}
+
+// Statuses this version doesn't know count as Maintenance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemState {
+    Normal,
+    Maintenance,
+}
+
+impl Serialize for SystemState {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            SystemState::Normal => serializer.serialize_u8(0),
+            SystemState::Maintenance => serializer.serialize_u8(1),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SystemState {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<SystemState, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match u64::deserialize(deserializer)? {
+            0 => Ok(SystemState::Normal),
+            _ => Ok(SystemState::Maintenance),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ExchangeInformation {
     pub timezone: String,
     pub server_time: u64,
     pub rate_limits: Vec<RateLimit>,
+    #[serde(default)] pub exchange_filters: Vec<Filters>,
     pub symbols: Vec<Symbol>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Lookups that work alike on exchange info of all symbols, some symbols or some permissions
+impl ExchangeInformation {
+    pub fn symbol(&self, symbol: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|info| info.symbol == symbol)
+    }
+
+    pub fn symbol_filters(&self, symbol: &str) -> Option<::filters::SymbolFilters> {
+        self.symbol(symbol).map(::filters::SymbolFilters::new)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimit {
-    pub rate_limit_type: String,
-    pub interval: String,
+    pub rate_limit_type: RateLimitType,
+    pub interval: RateLimitInterval,
+    // e.g. 10 with RateLimitInterval::Second for a limit per 10 seconds
+    #[serde(default = "default_interval_num")] pub interval_num: u32,
+    pub limit: u64,
+}
+
+fn default_interval_num() -> u32 {
+    1
+}
+
+// orderListId of orders outside an OCO
+fn no_order_list() -> i64 {
+    -1
+}
+
+// Usage of a rate limit, count is what was used in the current interval
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitUsage {
+    pub rate_limit_type: RateLimitType,
+    pub interval: RateLimitInterval,
+    // e.g. 10 with RateLimitInterval::Second for a limit per 10 seconds
+    pub interval_num: u32,
     pub limit: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitType {
+    #[serde(rename = "REQUEST_WEIGHT")] RequestWeight,
+    #[serde(rename = "ORDERS")] Orders,
+    #[serde(rename = "RAW_REQUESTS")] RawRequests,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitInterval {
+    #[serde(rename = "SECOND")] Second,
+    #[serde(rename = "MINUTE")] Minute,
+    #[serde(rename = "DAY")] Day,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
     pub symbol: String,
@@ -30,10 +144,295 @@ pub struct Symbol {
     pub base_asset_precision: u64,
     pub quote_asset:String,
     pub quote_precision: u64,
-    pub order_types: Vec<String>,
+    #[serde(default)] pub quote_asset_precision: u64,
+    pub order_types: Vec<OrderType>,
+    #[serde(default)] pub iceberg_allowed: bool,
+    #[serde(default)] pub oco_allowed: bool,
+    #[serde(default)] pub quote_order_qty_market_allowed: bool,
+    #[serde(default)] pub is_spot_trading_allowed: bool,
+    #[serde(default)] pub is_margin_trading_allowed: bool,
+    #[serde(default)] pub permissions: Vec<String>,
+    #[serde(default)] pub default_self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    #[serde(default)] pub allowed_self_trade_prevention_modes: Vec<SelfTradePreventionMode>,
+    #[serde(default)] pub filters: Vec<Filters>,
+}
+
+impl Symbol {
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        self.permissions.iter().any(|granted| granted == permission.as_str())
+    }
+
+    // Whether orders of this symbol accept mode, true when exchange info doesn't list the modes
+    pub fn allows_self_trade_prevention_mode(&self, mode: &SelfTradePreventionMode) -> bool {
+        self.allowed_self_trade_prevention_modes.is_empty() || self.allowed_self_trade_prevention_modes.contains(mode)
+    }
+}
+
+// A symbol kept in both cases Binance wants: uppercase in REST parameters (as_rest, also what Into<String>
+// gives, so it passes wherever a &str symbol does) and lowercase in stream names (as_stream)
+#[derive(Debug, Clone)]
+pub struct SymbolName {
+    rest: String,
+    stream: String,
+    // Only when made from exchange info
+    base_asset: Option<String>,
+    quote_asset: Option<String>,
+}
+
+impl SymbolName {
+    // Like parse, but for any string type
+    pub fn new<S>(symbol: S) -> ::errors::Result<SymbolName>
+        where S: AsRef<str>
+    {
+        symbol.as_ref().parse()
+    }
+
+    // "BTCUSDT"
+    pub fn as_rest(&self) -> &str {
+        &self.rest
+    }
+
+    // "btcusdt"
+    pub fn as_stream(&self) -> &str {
+        &self.stream
+    }
+
+    pub fn base_asset(&self) -> Option<&str> {
+        self.base_asset.as_deref()
+    }
+
+    pub fn quote_asset(&self) -> Option<&str> {
+        self.quote_asset.as_deref()
+    }
+}
+
+// Only normalizes the case, parse also rejects empty and non alphanumeric symbols
+impl<'a> From<&'a str> for SymbolName {
+    fn from(symbol: &'a str) -> SymbolName {
+        let symbol = symbol.trim();
+
+        SymbolName {
+            rest: symbol.to_uppercase(),
+            stream: symbol.to_lowercase(),
+            base_asset: None,
+            quote_asset: None,
+        }
+    }
+}
+
+impl<'a> From<&'a Symbol> for SymbolName {
+    fn from(symbol: &'a Symbol) -> SymbolName {
+        SymbolName {
+            base_asset: Some(symbol.base_asset.clone()),
+            quote_asset: Some(symbol.quote_asset.clone()),
+            ..SymbolName::from(symbol.symbol.as_str())
+        }
+    }
+}
+
+impl FromStr for SymbolName {
+    type Err = ::errors::Error;
+
+    fn from_str(s: &str) -> ::errors::Result<SymbolName> {
+        let symbol = s.trim();
+        if symbol.is_empty() {
+            bail!(::errors::BinanceError::Parameter("Empty symbol".into()));
+        }
+        if !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(::errors::BinanceError::Parameter(format!("Invalid symbol {:?}", symbol)));
+        }
+
+        Ok(SymbolName::from(symbol))
+    }
+}
+
+impl From<SymbolName> for String {
+    fn from(symbol: SymbolName) -> String {
+        symbol.rest
+    }
+}
+
+impl<'a> From<&'a SymbolName> for String {
+    fn from(symbol: &'a SymbolName) -> String {
+        symbol.rest.clone()
+    }
+}
+
+impl AsRef<str> for SymbolName {
+    fn as_ref(&self) -> &str {
+        &self.rest
+    }
+}
+
+// For the methods that take a &str symbol, e.g. account.symbol_filters(&symbol)
+impl ::std::ops::Deref for SymbolName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.rest
+    }
+}
+
+impl fmt::Display for SymbolName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.rest)
+    }
+}
+
+// The same symbol whatever it was made from
+impl PartialEq for SymbolName {
+    fn eq(&self, other: &SymbolName) -> bool {
+        self.rest == other.rest
+    }
+}
+
+impl Eq for SymbolName {}
+
+impl ::std::hash::Hash for SymbolName {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self.rest.hash(state);
+    }
+}
+
+impl Serialize for SymbolName {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.rest)
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolName {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<SymbolName, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let symbol = String::deserialize(deserializer)?;
+
+        symbol.parse().map_err(|e: ::errors::Error| de::Error::custom(e.to_string()))
+    }
+}
+
+// Trading rules of a symbol or of the exchange. Filters this version doesn't know keep their
+// raw JSON in Other
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "filterType")]
+pub enum Filters {
+    #[serde(rename = "PRICE_FILTER", rename_all = "camelCase")]
+    PriceFilter {
+        #[serde(with = "string_or_float")] min_price: Amount,
+        #[serde(with = "string_or_float")] max_price: Amount,
+        #[serde(with = "string_or_float")] tick_size: Amount,
+    },
+    #[serde(rename = "LOT_SIZE", rename_all = "camelCase")]
+    LotSize {
+        #[serde(with = "string_or_float")] min_qty: Amount,
+        #[serde(with = "string_or_float")] max_qty: Amount,
+        #[serde(with = "string_or_float")] step_size: Amount,
+    },
+    // LOT_SIZE of MARKET orders
+    #[serde(rename = "MARKET_LOT_SIZE", rename_all = "camelCase")]
+    MarketLotSize {
+        #[serde(with = "string_or_float")] min_qty: Amount,
+        #[serde(with = "string_or_float")] max_qty: Amount,
+        #[serde(with = "string_or_float")] step_size: Amount,
+    },
+    #[serde(rename = "MIN_NOTIONAL", rename_all = "camelCase")]
+    MinNotional {
+        #[serde(with = "string_or_float")] min_notional: Amount,
+        #[serde(default)] apply_to_market: bool,
+    },
+    #[serde(rename = "NOTIONAL", rename_all = "camelCase")]
+    Notional {
+        #[serde(with = "string_or_float")] min_notional: Amount,
+        #[serde(default)] apply_min_to_market: bool,
+        #[serde(with = "string_or_float")] max_notional: Amount,
+        #[serde(default)] apply_max_to_market: bool,
+    },
+    #[serde(rename = "ICEBERG_PARTS", rename_all = "camelCase")]
+    IcebergParts {
+        limit: u64,
+    },
+    #[serde(rename = "MAX_NUM_ORDERS", rename_all = "camelCase")]
+    MaxNumOrders {
+        max_num_orders: u64,
+    },
+    #[serde(rename = "MAX_NUM_ALGO_ORDERS", rename_all = "camelCase")]
+    MaxNumAlgoOrders {
+        max_num_algo_orders: u64,
+    },
+    #[serde(rename = "MAX_NUM_ICEBERG_ORDERS", rename_all = "camelCase")]
+    MaxNumIcebergOrders {
+        max_num_iceberg_orders: u64,
+    },
+    #[serde(rename = "MAX_POSITION", rename_all = "camelCase")]
+    MaxPosition {
+        #[serde(with = "string_or_float")] max_position: Amount,
+    },
+    // Price range around the average price of the last avg_price_mins minutes
+    #[serde(rename = "PERCENT_PRICE", rename_all = "camelCase")]
+    PercentPrice {
+        #[serde(with = "string_or_float")] multiplier_up: Amount,
+        #[serde(with = "string_or_float")] multiplier_down: Amount,
+        avg_price_mins: u64,
+    },
+    #[serde(rename = "PERCENT_PRICE_BY_SIDE", rename_all = "camelCase")]
+    PercentPriceBySide {
+        #[serde(with = "string_or_float")] bid_multiplier_up: Amount,
+        #[serde(with = "string_or_float")] bid_multiplier_down: Amount,
+        #[serde(with = "string_or_float")] ask_multiplier_up: Amount,
+        #[serde(with = "string_or_float")] ask_multiplier_down: Amount,
+        avg_price_mins: u64,
+    },
+    // In basis points
+    #[serde(rename = "TRAILING_DELTA", rename_all = "camelCase")]
+    TrailingDelta {
+        min_trailing_above_delta: u64,
+        max_trailing_above_delta: u64,
+        min_trailing_below_delta: u64,
+        max_trailing_below_delta: u64,
+    },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS", rename_all = "camelCase")]
+    ExchangeMaxNumOrders {
+        max_num_orders: u64,
+    },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS", rename_all = "camelCase")]
+    ExchangeMaxNumAlgoOrders {
+        max_num_algo_orders: u64,
+    },
+    #[serde(skip)]
+    Other(Value),
+}
+
+impl Serialize for Filters {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Filters::Other(ref filter) => filter.serialize(serializer),
+            _ => Filters::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Filters {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let filter = Value::deserialize(deserializer)?;
+
+        // Unknown filter types, or known ones in a shape this version can't read
+        Ok(match Filters::deserialize(filter.clone()) {
+            Ok(filter) => filter,
+            Err(_) => Filters::Other(filter),
+        })
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInformation {
     pub maker_commission: f32,
@@ -46,33 +445,73 @@ pub struct AccountInformation {
     pub balances: Vec<Balance>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
     pub asset: String,
-    pub free: String,
-    pub locked: String,
+    #[serde(with = "string_or_float")] pub free: Amount,
+    #[serde(with = "string_or_float")] pub locked: Amount,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
     pub symbol: String,
     pub order_id: u64,
+    // Id of the OCO the order belongs to, -1 for orders placed on their own
+    #[serde(default = "no_order_list")] pub order_list_id: i64,
     pub client_order_id: String,
-    #[serde(with = "string_or_float")] pub price: f64,
-    pub orig_qty: String,
-    pub executed_qty: String,
-    pub status: String,
-    pub time_in_force: String,
-    #[serde(rename = "type")] pub type_name: String,
-    pub side: String,
-    #[serde(with = "string_or_float")] pub stop_price: f64,
-    pub iceberg_qty: String,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub orig_qty: Amount,
+    #[serde(with = "string_or_float")] pub executed_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")] pub type_name: OrderType,
+    pub side: OrderSide,
+    #[serde(with = "string_or_float")] pub stop_price: Amount,
+    #[serde(with = "string_or_float")] pub iceberg_qty: Amount,
     pub time: u64,
+    pub update_time: Option<u64>,
+    pub is_working: Option<bool>,
+    #[serde(default, with = "string_or_float_opt")] pub cummulative_quote_qty: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub orig_quote_order_qty: Option<Amount>,
+    // When the order went on the book
+    pub working_time: Option<u64>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    // Only when self-trade prevention expired the order
+    pub prevented_match_id: Option<u64>,
+    #[serde(default, with = "string_or_float_opt")] pub prevented_quantity: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRates {
+    pub symbol: String,
+    pub standard_commission: Commission,
+    pub tax_commission: Commission,
+    pub discount: CommissionDiscount,
+}
+
+// Rates with 0.001 for 0.1%
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Commission {
+    #[serde(with = "string_or_float")] pub maker: Amount,
+    #[serde(with = "string_or_float")] pub taker: Amount,
+    #[serde(with = "string_or_float")] pub buyer: Amount,
+    #[serde(with = "string_or_float")] pub seller: Amount,
+}
+
+// Paying the commission in discount_asset multiplies the standard commission by discount
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: String,
+    #[serde(with = "string_or_float")] pub discount: Amount,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderCanceled {
     pub symbol: String,
@@ -81,382 +520,3455 @@ pub struct OrderCanceled {
     pub client_order_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Outcome of both legs of a cancel-replace, also when one or both of them failed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplace {
+    pub cancel_result: CancelReplaceStatus,
+    pub new_order_result: CancelReplaceStatus,
+    pub cancel_response: Option<LegResponse<OrderCanceled>>,
+    // None when the new order was not attempted
+    pub new_order_response: Option<LegResponse<Transaction>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReplaceStatus {
+    #[serde(rename = "SUCCESS")] Success,
+    #[serde(rename = "FAILURE")] Failure,
+    #[serde(rename = "NOT_ATTEMPTED")] NotAttempted,
+}
+
+// Answer of one leg of a cancel-replace
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum LegResponse<T> {
+    Failed(ErrorResponse),
+    Done(T),
+}
+
+// {"code":-2010,"msg":"..."}
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ErrorResponse {
+    pub code: i64,
+    pub msg: String,
+}
+
+// Entry of DELETE /api/v3/openOrders, OCOs are canceled as a whole
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CanceledOrder {
+    OrderList(OrderList),
+    Order(OrderCanceled),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     pub symbol: String,
     pub order_id: u64,
+    #[serde(default = "no_order_list")] pub order_list_id: i64,
     pub client_order_id: String,
     pub transact_time: u64,
+    // Only in RESULT and FULL responses
+    #[serde(default, with = "string_or_float_opt")] pub price: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub orig_qty: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub executed_qty: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub cummulative_quote_qty: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub orig_quote_order_qty: Option<Amount>,
+    pub status: Option<OrderStatus>,
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(rename = "type")] pub type_name: Option<OrderType>,
+    pub side: Option<OrderSide>,
+    pub working_time: Option<u64>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    // Only in FULL responses
+    #[serde(default)] pub fills: Vec<Fill>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub qty: Amount,
+    #[serde(with = "string_or_float")] pub commission: Amount,
+    pub commission_asset: String,
+    pub trade_id: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderBook {
     pub last_update_id: u64,
     pub bids: Vec<Bids>,
     pub asks: Vec<Asks>,
+
+    // Depth of a partial book depth stream, never set on REST snapshots
+    #[serde(skip)]
+    pub levels: Option<u8>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Bids {
-    #[serde(with = "string_or_float")] pub price: f64,
-    #[serde(with = "string_or_float")] pub qty: f64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub qty: Amount,
 
-    // Never serialized.
-    #[serde(skip_serializing)]
+    // Never serialized, missing from the v3 depth answers
+    #[serde(default)]
     ignore: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// [price, qty] like the depth answers
+impl Serialize for Bids {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.price.to_string(), self.qty.to_string()).serialize(serializer)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Asks {
-    #[serde(with = "string_or_float")] pub price: f64,
-    #[serde(with = "string_or_float")] pub qty: f64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub qty: Amount,
 
-    // Never serialized.
-    #[serde(skip_serializing)]
+    // Never serialized, missing from the v3 depth answers
+    #[serde(default)]
     ignore: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// [price, qty] like the depth answers
+impl Serialize for Asks {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.price.to_string(), self.qty.to_string()).serialize(serializer)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct UserDataStream {
     pub listen_key: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Success {}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-#[serde(untagged)]
-pub enum Prices {
-    AllPrices(Vec<SymbolPrice>),
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DepositAddress {
+    pub address: String,
+    pub coin: String,
+    // Memo the deposit must carry (XRP, XLM, BNB BEP2, ...), None for coins without one
+    #[serde(default, with = "empty_string_as_none")] pub tag: Option<String>,
+    pub url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SymbolPrice {
-    pub symbol: String,
-    #[serde(with = "string_or_float")] pub price: f64,
+// Withdrawal fee and minimum of an asset on its default network
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetDetail {
+    #[serde(with = "string_or_float")] pub min_withdraw_amount: Amount,
+    pub deposit_status: bool,
+    #[serde(with = "string_or_float")] pub withdraw_fee: Amount,
+    pub withdraw_status: bool,
+    // Why deposits are suspended, e.g. "Delisted, Deposit Suspended"
+    #[serde(default)]
+    pub deposit_tip: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// A coin of the wallet, with our balance of it. Deposits and withdrawals are enabled per network too
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-#[serde(untagged)]
-pub enum BookTickers {
-    AllBookTickers(Vec<Tickers>),
+pub struct CoinInfo {
+    pub coin: String,
+    pub name: String,
+    pub deposit_all_enable: bool,
+    pub withdraw_all_enable: bool,
+    #[serde(with = "string_or_float")] pub free: Amount,
+    #[serde(with = "string_or_float")] pub locked: Amount,
+    #[serde(with = "string_or_float")] pub freeze: Amount,
+    #[serde(with = "string_or_float")] pub withdrawing: Amount,
+    pub is_legal_money: bool,
+    pub trading: bool,
+    pub network_list: Vec<CoinNetwork>,
 }
 
-#[derive(Debug, Clone)]
-pub enum KlineSummaries {
-    AllKlineSummaries(Vec<KlineSummary>),
-}
+impl CoinInfo {
+    // network as in withdraw's WithdrawOptions::network, e.g. "ETH" or "BSC"
+    pub fn network(&self, network: &str) -> Option<&CoinNetwork> {
+        self.network_list.iter().find(|entry| entry.network == network)
+    }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct Tickers {
-    pub symbol: String,
-    #[serde(with = "string_or_float")] pub bid_price: f64,
-    #[serde(with = "string_or_float")] pub bid_qty: f64,
-    #[serde(with = "string_or_float")] pub ask_price: f64,
-    #[serde(with = "string_or_float")] pub ask_qty: f64,
+    // The network of withdrawals and deposit addresses that do not name one
+    pub fn default_network(&self) -> Option<&CoinNetwork> {
+        self.network_list.iter().find(|entry| entry.is_default)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// A network a coin can be deposited and withdrawn on. A withdrawal is withdraw_min to withdraw_max,
+// a multiple of withdraw_integer_multiple, and costs withdraw_fee of the coin
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct TradeHistory {
-    pub id: u64,
-    #[serde(with = "string_or_float")] pub price: f64,
-    #[serde(with = "string_or_float")] pub qty: f64,
-    pub commission: String,
-    pub commission_asset: String,
-    pub time: u64,
-    pub is_buyer: bool,
-    pub is_maker: bool,
-    pub is_best_match: bool,
+pub struct CoinNetwork {
+    pub network: String,
+    pub coin: String,
+    pub name: String,
+    pub is_default: bool,
+    pub deposit_enable: bool,
+    pub withdraw_enable: bool,
+    #[serde(with = "string_or_float")] pub withdraw_fee: Amount,
+    #[serde(with = "string_or_float")] pub withdraw_min: Amount,
+    #[serde(with = "string_or_float")] pub withdraw_max: Amount,
+    #[serde(default, with = "string_or_float_opt")] pub withdraw_integer_multiple: Option<Amount>,
+    // Confirmations before a deposit is credited, and before it can be withdrawn
+    pub min_confirm: u32,
+    #[serde(default)]
+    pub un_lock_confirm: u32,
+    // Why deposits or withdrawals are suspended
+    #[serde(default)]
+    pub deposit_desc: String,
+    #[serde(default)]
+    pub withdraw_desc: String,
+    #[serde(default)]
+    pub special_tips: Option<String>,
+    #[serde(default)]
+    pub address_regex: String,
+    #[serde(default)]
+    pub memo_regex: String,
+    // Congested, withdrawals are slower than usual
+    #[serde(default)]
+    pub busy: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct PriceStats {
-    pub price_change: String,
-    pub price_change_percent: String,
-    pub weighted_avg_price: String,
-    #[serde(with = "string_or_float")] pub prev_close_price: f64,
-    #[serde(with = "string_or_float")] pub last_price: f64,
-    #[serde(with = "string_or_float")] pub bid_price: f64,
-    #[serde(with = "string_or_float")] pub ask_price: f64,
-    #[serde(with = "string_or_float")] pub open_price: f64,
-    #[serde(with = "string_or_float")] pub high_price: f64,
-    #[serde(with = "string_or_float")] pub low_price: f64,
-    #[serde(with = "string_or_float")] pub volume: f64,
-    pub open_time: u64,
-    pub close_time: u64,
-    pub first_id: u64,
-    pub last_id: u64,
-    pub count: u64,
+pub struct ApiTradingStatus {
+    pub is_locked: bool,
+    // When a locked account is unlocked, 0 when not locked
+    pub planned_recover_time: u64,
+    pub trigger_condition: TriggerCondition,
+    pub update_time: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Restrictions of an API key. trading_authority_expiration_time is when spot and margin trading
+// expires for keys without an IP restriction, 0 when it does not
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct AccountUpdateEvent {
-    #[serde(rename = "e")] pub event_type: String,
+pub struct ApiKeyPermissions {
+    pub ip_restrict: bool,
+    pub create_time: u64,
+    pub enable_reading: bool,
+    pub enable_spot_and_margin_trading: bool,
+    pub enable_withdrawals: bool,
+    #[serde(default)]
+    pub enable_internal_transfer: bool,
+    #[serde(default)]
+    pub enable_margin: bool,
+    #[serde(default)]
+    pub enable_futures: bool,
+    #[serde(default)]
+    pub enable_vanilla_options: bool,
+    #[serde(default)]
+    pub permits_universal_transfer: bool,
+    #[serde(default)]
+    pub trading_authority_expiration_time: u64,
+}
 
-    #[serde(rename = "E")] pub event_time: u64,
+impl ApiKeyPermissions {
+    pub fn permissions(&self) -> Permissions {
+        Permissions {
+            ip_restrict: self.ip_restrict,
+            reading: self.enable_reading,
+            spot_and_margin_trading: self.enable_spot_and_margin_trading,
+            withdrawals: self.enable_withdrawals,
+            internal_transfer: self.enable_internal_transfer,
+            margin: self.enable_margin,
+            futures: self.enable_futures,
+            vanilla_options: self.enable_vanilla_options,
+            universal_transfer: self.permits_universal_transfer,
+        }
+    }
 
-    m: u64,
-    t: u64,
-    b: u64,
-    s: u64,
+    // Err with the permissions required but not granted (missing) and granted but not required
+    // (excess). An IP restriction is never excess
+    pub fn check(&self, required: &Permissions) -> ::errors::Result<()> {
+        let granted = self.permissions().named();
+        let mut missing = Vec::new();
+        let mut excess = Vec::new();
+        for (&(name, required), &(_, granted)) in required.named().iter().zip(granted.iter()) {
+            if required && !granted {
+                missing.push(name);
+            } else if granted && !required && name != IP_RESTRICT {
+                excess.push(name);
+            }
+        }
 
-    #[serde(rename = "T")] t_ignore: bool,
-    #[serde(rename = "W")] w_ignore: bool,
-    #[serde(rename = "D")] d_ignore: bool,
+        if !missing.is_empty() || !excess.is_empty() {
+            bail!(::errors::BinanceError::ApiKeyPermissions { missing, excess });
+        }
 
-    #[serde(rename = "B")] pub balance: Vec<EventBalance>,
+        Ok(())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct EventBalance {
-    #[serde(rename = "a")] pub asset: String,
-    #[serde(rename = "f")] pub free: String,
-    #[serde(rename = "l")] pub locked: String,
+static IP_RESTRICT: &'static str = "ipRestrict";
+
+// Permissions an API key should have, compared with ApiKeyPermissions::check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Permissions {
+    pub ip_restrict: bool,
+    pub reading: bool,
+    pub spot_and_margin_trading: bool,
+    pub withdrawals: bool,
+    pub internal_transfer: bool,
+    pub margin: bool,
+    pub futures: bool,
+    pub vanilla_options: bool,
+    pub universal_transfer: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct OrderTradeEvent {
-    #[serde(rename = "e")] pub event_type: String,
+impl Permissions {
+    // By the field names of apiRestrictions
+    fn named(&self) -> [(&'static str, bool); 9] {
+        [
+            (IP_RESTRICT, self.ip_restrict),
+            ("enableReading", self.reading),
+            ("enableSpotAndMarginTrading", self.spot_and_margin_trading),
+            ("enableWithdrawals", self.withdrawals),
+            ("enableInternalTransfer", self.internal_transfer),
+            ("enableMargin", self.margin),
+            ("enableFutures", self.futures),
+            ("enableVanillaOptions", self.vanilla_options),
+            ("permitsUniversalTransfer", self.universal_transfer),
+        ]
+    }
+}
 
-    #[serde(rename = "E")] pub event_time: u64,
+// Limits of the order-to-trade ratio rules, API trading is locked once one is reached
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TriggerCondition {
+    // Number of GTC orders
+    #[serde(rename = "GCR")] pub gcr: u64,
+    // Number of FOK/IOC orders
+    #[serde(rename = "IFER")] pub ifer: u64,
+    // Number of orders
+    #[serde(rename = "UFR")] pub ufr: u64,
+}
 
-    #[serde(rename = "s")] pub symbol: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UniversalTransferHistory {
+    pub total: u64,
+    // Missing when there is no transfer
+    #[serde(default)]
+    pub rows: Vec<UniversalTransfer>,
+}
 
-    #[serde(rename = "c")] pub new_client_order_id: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UniversalTransfer {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(rename = "type")] pub transfer_type: TransferType,
+    // "CONFIRMED", "FAILED" or "PENDING"
+    pub status: String,
+    pub tran_id: u64,
+    pub timestamp: u64,
+}
 
-    #[serde(rename = "S")] pub side: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeFee {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub maker_commission: Amount,
+    #[serde(with = "string_or_float")] pub taker_commission: Amount,
+}
 
-    #[serde(rename = "o")] pub order_type: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshots {
+    pub code: i32,
+    pub msg: String,
+    #[serde(default)]
+    pub snapshot_vos: Vec<AccountSnapshot>,
+}
 
-    #[serde(rename = "f")] pub time_in_force: String,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountSnapshotType {
+    Spot,
+    Margin,
+    Futures,
+}
 
-    #[serde(rename = "q")] pub qty: String,
+impl AccountSnapshotType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AccountSnapshotType::Spot => "SPOT",
+            AccountSnapshotType::Margin => "MARGIN",
+            AccountSnapshotType::Futures => "FUTURES",
+        }
+    }
+}
 
-    #[serde(rename = "p")] pub price: String,
+// State of the account at the end of one day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    pub update_time: u64,
+    #[serde(flatten)]
+    pub data: SnapshotData,
+}
 
-    #[serde(skip_serializing, rename = "P")] pub p_ignore: String,
+// The shape of data depends on the snapshot type
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+pub enum SnapshotData {
+    Spot(SpotSnapshot),
+    Margin(MarginSnapshot),
+    Futures(FuturesSnapshot),
+}
 
-    #[serde(skip_serializing, rename = "F")] pub f_ignore: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotSnapshot {
+    pub balances: Vec<Balance>,
+    #[serde(with = "string_or_float")] pub total_asset_of_btc: Amount,
+}
 
-    #[serde(skip_serializing)] pub g: i32,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginSnapshot {
+    #[serde(with = "string_or_float")] pub margin_level: Amount,
+    #[serde(with = "string_or_float")] pub total_asset_of_btc: Amount,
+    #[serde(with = "string_or_float")] pub total_liability_of_btc: Amount,
+    #[serde(with = "string_or_float")] pub total_net_asset_of_btc: Amount,
+    pub user_assets: Vec<MarginAsset>,
+}
 
-    #[serde(skip_serializing, rename = "C")] pub c_ignore: Option<String>,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesSnapshot {
+    pub assets: Vec<FuturesSnapshotAsset>,
+    pub position: Vec<FuturesSnapshotPosition>,
+}
 
-    #[serde(rename = "x")] pub execution_type: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesSnapshotAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub margin_balance: Amount,
+    #[serde(with = "string_or_float")] pub wallet_balance: Amount,
+}
 
-    #[serde(rename = "X")] pub order_status: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesSnapshotPosition {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub entry_price: Amount,
+    #[serde(with = "string_or_float")] pub mark_price: Amount,
+    #[serde(with = "string_or_float")] pub position_amt: Amount,
+    #[serde(with = "string_or_float")] pub un_realized_profit: Amount,
+}
 
-    #[serde(rename = "r")] pub order_reject_reason: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccounts {
+    pub sub_accounts: Vec<SubAccountInfo>,
+}
 
-    #[serde(rename = "i")] pub order_id: u64,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountInfo {
+    pub email: String,
+    pub is_freeze: bool,
+    pub create_time: u64,
+    #[serde(default)] pub is_managed_sub_account: bool,
+    #[serde(default)] pub is_asset_management_sub_account: bool,
+}
 
-    #[serde(rename = "l")] pub qty_last_filled_trade: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SubAccountAssets {
+    pub balances: Vec<SubAccountBalance>,
+}
 
-    #[serde(rename = "z")] pub accumulated_qty_filled_trades: String,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SubAccountBalance {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub free: Amount,
+    #[serde(with = "string_or_float")] pub locked: Amount,
+}
 
-    #[serde(rename = "L")] pub price_last_filled_trade: String,
+// Wallet of a sub-account transfer
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SubAccountType {
+    #[serde(rename = "SPOT")] Spot,
+    #[serde(rename = "USDT_FUTURE")] UsdtFuture,
+    #[serde(rename = "COIN_FUTURE")] CoinFuture,
+    #[serde(rename = "MARGIN")] Margin,
+    #[serde(rename = "ISOLATED_MARGIN")] IsolatedMargin,
+}
 
-    #[serde(rename = "n")] pub commission: String,
+impl SubAccountType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            SubAccountType::Spot => "SPOT",
+            SubAccountType::UsdtFuture => "USDT_FUTURE",
+            SubAccountType::CoinFuture => "COIN_FUTURE",
+            SubAccountType::Margin => "MARGIN",
+            SubAccountType::IsolatedMargin => "ISOLATED_MARGIN",
+        }
+    }
+}
 
-    #[serde(skip_serializing, rename = "N")] pub asset_commisioned: Option<String>,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransferHistory {
+    #[serde(default)]
+    pub result: Vec<SubAccountTransfer>,
+    pub total_count: u64,
+}
 
-    #[serde(rename = "T")] pub trade_order_time: u64,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    pub tran_id: u64,
+    pub from_email: String,
+    pub to_email: String,
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    pub create_time_stamp: u64,
+    pub from_account_type: SubAccountType,
+    pub to_account_type: SubAccountType,
+    // "SUCCESS", "PROCESS" or "FAILURE"
+    pub status: String,
+    #[serde(default)] pub client_tran_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FlexibleProducts {
+    #[serde(default)]
+    pub rows: Vec<FlexibleProduct>,
+    pub total: u64,
+}
+
+// Flexible Simple Earn product, rates are yearly with 1.0 for 100%
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleProduct {
+    pub asset: String,
+    pub product_id: String,
+    #[serde(with = "string_or_float")] pub latest_annual_percentage_rate: Amount,
+    pub can_purchase: bool,
+    pub can_redeem: bool,
+    pub is_sold_out: bool,
+    #[serde(with = "string_or_float")] pub min_purchase_amount: Amount,
+    // "PREHEATING", "PURCHASING" or "END"
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FlexiblePositions {
+    #[serde(default)]
+    pub rows: Vec<FlexiblePosition>,
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexiblePosition {
+    pub asset: String,
+    pub product_id: String,
+    // Principal and the rewards added to it
+    #[serde(with = "string_or_float")] pub total_amount: Amount,
+    #[serde(with = "string_or_float")] pub latest_annual_percentage_rate: Amount,
+    #[serde(with = "string_or_float")] pub yesterday_real_time_rewards: Amount,
+    #[serde(with = "string_or_float")] pub cumulative_total_rewards: Amount,
+    pub can_redeem: bool,
+    pub auto_subscribe: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleSubscription {
+    pub purchase_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleRedemption {
+    pub redeem_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedProducts {
+    #[serde(default)]
+    pub rows: Vec<LockedProduct>,
+    pub total: u64,
+}
+
+// Locked Simple Earn product, subscribed with its project_id
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProduct {
+    pub project_id: String,
+    pub detail: LockedProductDetail,
+    pub quota: LockedProductQuota,
+}
+
+impl LockedProduct {
+    // Sold out when listed, it may also sell out before the subscription: BinanceErrorCode::ProductSoldOut
+    pub fn is_sold_out(&self) -> bool {
+        self.detail.is_sold_out
+    }
+}
+
+// Rates are yearly with 1.0 for 100%, duration is in days
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProductDetail {
+    pub asset: String,
+    pub reward_asset: String,
+    pub duration: u32,
+    pub renewable: bool,
+    pub is_sold_out: bool,
+    #[serde(with = "string_or_float")] pub apr: Amount,
+    // "CREATED", "PURCHASING" or "END"
+    pub status: String,
+}
+
+// Purchase limits of one user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProductQuota {
+    #[serde(with = "string_or_float")] pub total_personal_quota: Amount,
+    #[serde(with = "string_or_float")] pub minimum: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedSubscription {
+    pub purchase_id: u64,
+    #[serde(with = "string_or_integer")] pub position_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedRedemption {
+    pub redeem_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedPositions {
+    #[serde(default)]
+    pub rows: Vec<LockedPosition>,
+    pub total: u64,
+}
+
+// A subscription of a locked product. Times are in ms, duration, accrual_days and redeem_period in days
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedPosition {
+    #[serde(with = "string_or_integer")] pub position_id: u64,
+    pub project_id: String,
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(with = "string_or_integer")] pub purchase_time: u64,
+    #[serde(with = "string_or_integer")] pub duration: u32,
+    // Days of rewards so far
+    #[serde(with = "string_or_integer")] pub accrual_days: u32,
+    pub reward_asset: String,
+    #[serde(rename = "APY", with = "string_or_float")] pub apy: Amount,
+    #[serde(with = "string_or_float")] pub reward_amt: Amount,
+    // When the lock period ends and the principal is delivered back
+    #[serde(with = "string_or_integer")] pub deliver_date: u64,
+    // Days between an early redemption and the principal being credited
+    #[serde(with = "string_or_integer")] pub redeem_period: u32,
+    // What an early redemption would credit, the rewards are forfeited
+    #[serde(default, with = "string_or_float_opt")] pub redeem_amount_early: Option<Amount>,
+    #[serde(default)]
+    pub can_redeem_early: bool,
+    #[serde(default)]
+    pub auto_subscribe: bool,
+    // "HOLDING", "REDEEMING" or "REDEEMED"
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedRewards {
+    #[serde(default)]
+    pub rows: Vec<LockedReward>,
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedReward {
+    #[serde(with = "string_or_integer")] pub position_id: u64,
+    pub time: u64,
+    pub asset: String,
+    // Days of the product
+    #[serde(with = "string_or_integer")] pub lock_period: u32,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(rename = "type")]
+    pub reward_type: String,
+}
+
+// ETH staked for WBETH at conversion_ratio WBETH per ETH
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStake {
+    pub success: bool,
+    #[serde(with = "string_or_float")] pub wbeth_amount: Amount,
+    #[serde(with = "string_or_float")] pub conversion_ratio: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthRedemption {
+    pub success: bool,
+    #[serde(with = "string_or_float")] pub eth_amount: Amount,
+    #[serde(with = "string_or_float")] pub conversion_ratio: Amount,
+    pub arrival_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EthStakingRates {
+    #[serde(default)]
+    pub rows: Vec<EthStakingRate>,
+    pub total: u64,
+}
+
+// exchange_rate is ETH per WBETH
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingRate {
+    #[serde(with = "string_or_float")] pub annual_percentage_rate: Amount,
+    #[serde(with = "string_or_float")] pub exchange_rate: Amount,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EthStakingAccount {
+    #[serde(rename = "holdingInETH", with = "string_or_float")] pub holding_in_eth: Amount,
+    pub holdings: EthStakingHoldings,
+    #[serde(rename = "thirtyDaysProfitInETH", with = "string_or_float")] pub thirty_days_profit_in_eth: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingHoldings {
+    #[serde(with = "string_or_float")] pub wbeth_amount: Amount,
+    #[serde(with = "string_or_float")] pub beth_amount: Amount,
+}
+
+// ratio is to_asset per from_asset, inverse_ratio from_asset per to_asset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertQuote {
+    pub quote_id: String,
+    #[serde(with = "string_or_float")] pub ratio: Amount,
+    #[serde(with = "string_or_float")] pub inverse_ratio: Amount,
+    // Last time in ms the quote can be accepted
+    pub valid_timestamp: u64,
+    #[serde(with = "string_or_float")] pub to_amount: Amount,
+    #[serde(with = "string_or_float")] pub from_amount: Amount,
+}
+
+impl ConvertQuote {
+    // time in ms, best the server time: an expired quote is refused with BinanceErrorCode::QuoteExpired
+    pub fn is_expired_at(&self, time: u64) -> bool {
+        time > self.valid_timestamp
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertAcceptance {
+    #[serde(with = "string_or_integer")] pub order_id: u64,
+    pub create_time: u64,
+    pub order_status: ConvertOrderStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertOrder {
+    #[serde(with = "string_or_integer")] pub order_id: u64,
+    pub order_status: ConvertOrderStatus,
+    pub from_asset: String,
+    #[serde(with = "string_or_float")] pub from_amount: Amount,
+    pub to_asset: String,
+    #[serde(with = "string_or_float")] pub to_amount: Amount,
+    #[serde(with = "string_or_float")] pub ratio: Amount,
+    #[serde(with = "string_or_float")] pub inverse_ratio: Amount,
+    pub create_time: u64,
+}
+
+// One page of conversions, more_data when the range holds more than limit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertTradeFlow {
+    #[serde(default)]
+    pub list: Vec<ConvertTrade>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub limit: u16,
+    pub more_data: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertTrade {
+    pub quote_id: String,
+    #[serde(with = "string_or_integer")] pub order_id: u64,
+    pub order_status: ConvertOrderStatus,
+    pub from_asset: String,
+    #[serde(with = "string_or_float")] pub from_amount: Amount,
+    pub to_asset: String,
+    #[serde(with = "string_or_float")] pub to_amount: Amount,
+    #[serde(with = "string_or_float")] pub ratio: Amount,
+    #[serde(with = "string_or_float")] pub inverse_ratio: Amount,
+    pub create_time: u64,
+}
+
+// Small balances that can be converted to BNB
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertibleDust {
+    pub details: Vec<DustAsset>,
+    #[serde(with = "string_or_float")] pub total_transfer_btc: Amount,
+    #[serde(rename = "totalTransferBNB", with = "string_or_float")] pub total_transfer_bnb: Amount,
+    #[serde(with = "string_or_float")] pub dribblet_percentage: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DustAsset {
+    pub asset: String,
+    pub asset_full_name: String,
+    #[serde(with = "string_or_float")] pub amount_free: Amount,
+    #[serde(rename = "toBTC", with = "string_or_float")] pub to_btc: Amount,
+    #[serde(rename = "toBNB", with = "string_or_float")] pub to_bnb: Amount,
+    #[serde(rename = "toBNBOffExchange", with = "string_or_float")] pub to_bnb_off_exchange: Amount,
+    #[serde(with = "string_or_float")] pub exchange: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DustTransfer {
+    #[serde(with = "string_or_float")] pub total_service_charge: Amount,
+    #[serde(with = "string_or_float")] pub total_transfered: Amount,
+    pub transfer_result: Vec<DustTransferResult>,
+}
+
+impl DustTransfer {
+    // Requested assets Binance did not convert, e.g. because they stopped being convertible
+    pub fn unconverted(&self, requested: &[&str]) -> Vec<String> {
+        requested
+            .iter()
+            .filter(|asset| !self.transfer_result.iter().any(|result| result.from_asset == **asset))
+            .map(|asset| asset.to_string())
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DustTransferResult {
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    pub from_asset: String,
+    pub operate_time: u64,
+    #[serde(with = "string_or_float")] pub service_charge_amount: Amount,
+    pub tran_id: u64,
+    #[serde(with = "string_or_float")] pub transfered_amount: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Withdrawal {
+    pub id: String,
+}
+
+// Status of a deposit record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositStatus {
+    Pending,
+    Success,
+    // Credited, but not withdrawable yet
+    CreditedCannotWithdraw,
+    WrongDeposit,
+    WaitingUserConfirm,
+    // A status this version doesn't know
+    Other(i32),
+}
+
+impl DepositStatus {
+    pub fn code(&self) -> i32 {
+        match *self {
+            DepositStatus::Pending => 0,
+            DepositStatus::Success => 1,
+            DepositStatus::CreditedCannotWithdraw => 6,
+            DepositStatus::WrongDeposit => 7,
+            DepositStatus::WaitingUserConfirm => 8,
+            DepositStatus::Other(code) => code,
+        }
+    }
+}
+
+impl From<i32> for DepositStatus {
+    fn from(code: i32) -> DepositStatus {
+        match code {
+            0 => DepositStatus::Pending,
+            1 => DepositStatus::Success,
+            6 => DepositStatus::CreditedCannotWithdraw,
+            7 => DepositStatus::WrongDeposit,
+            8 => DepositStatus::WaitingUserConfirm,
+            code => DepositStatus::Other(code),
+        }
+    }
+}
+
+impl Serialize for DepositStatus {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for DepositStatus {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<DepositStatus, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(DepositStatus::from(i32::deserialize(deserializer)?))
+    }
+}
+
+// Status of a withdrawal record, from the confirmation email to completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawStatus {
+    EmailSent,
+    Cancelled,
+    AwaitingApproval,
+    Rejected,
+    Processing,
+    Failure,
+    Completed,
+    // A status this version doesn't know
+    Other(i32),
+}
+
+impl WithdrawStatus {
+    pub fn code(&self) -> i32 {
+        match *self {
+            WithdrawStatus::EmailSent => 0,
+            WithdrawStatus::Cancelled => 1,
+            WithdrawStatus::AwaitingApproval => 2,
+            WithdrawStatus::Rejected => 3,
+            WithdrawStatus::Processing => 4,
+            WithdrawStatus::Failure => 5,
+            WithdrawStatus::Completed => 6,
+            WithdrawStatus::Other(code) => code,
+        }
+    }
+}
+
+impl From<i32> for WithdrawStatus {
+    fn from(code: i32) -> WithdrawStatus {
+        match code {
+            0 => WithdrawStatus::EmailSent,
+            1 => WithdrawStatus::Cancelled,
+            2 => WithdrawStatus::AwaitingApproval,
+            3 => WithdrawStatus::Rejected,
+            4 => WithdrawStatus::Processing,
+            5 => WithdrawStatus::Failure,
+            6 => WithdrawStatus::Completed,
+            code => WithdrawStatus::Other(code),
+        }
+    }
+}
+
+impl Serialize for WithdrawStatus {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for WithdrawStatus {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<WithdrawStatus, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(WithdrawStatus::from(i32::deserialize(deserializer)?))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRecord {
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    pub coin: String,
+    pub network: String,
+    pub status: DepositStatus,
+    pub address: String,
+    #[serde(default)] pub address_tag: String,
+    // Empty while the deposit is not on chain yet
+    #[serde(default)] pub tx_id: String,
+    pub insert_time: u64,
+    // "12/12", confirmations so far / needed
+    #[serde(default)] pub confirm_times: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawRecord {
+    pub id: String,
+    #[serde(default)] pub withdraw_order_id: Option<String>,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(with = "string_or_float")] pub transaction_fee: Amount,
+    pub coin: String,
+    pub status: WithdrawStatus,
+    pub address: String,
+    // Empty until the withdrawal was broadcast
+    #[serde(default)] pub tx_id: String,
+    // "2019-10-12 11:12:02" in UTC
+    pub apply_time: String,
+    #[serde(default)] pub network: String,
+}
+
+// transactionType of the fiat orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FiatTransactionType {
+    Deposit,
+    Withdraw,
+}
+
+impl FiatTransactionType {
+    pub fn code(&self) -> u8 {
+        match *self {
+            FiatTransactionType::Deposit => 0,
+            FiatTransactionType::Withdraw => 1,
+        }
+    }
+}
+
+// transactionType of the fiat payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FiatPaymentType {
+    Buy,
+    Sell,
+}
+
+impl FiatPaymentType {
+    pub fn code(&self) -> u8 {
+        match *self {
+            FiatPaymentType::Buy => 0,
+            FiatPaymentType::Sell => 1,
+        }
+    }
+}
+
+// A fiat deposit or withdrawal, amount is indicated_amount less total_fee. Times are in ms
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatOrder {
+    pub order_no: String,
+    pub fiat_currency: String,
+    #[serde(with = "string_or_float")] pub indicated_amount: Amount,
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(with = "string_or_float")] pub total_fee: Amount,
+    // e.g. "BankAccount"
+    pub method: String,
+    // e.g. "Processing", "Failed", "Successful", "Finished", "Refunding", "Refunded", "Expired"
+    pub status: String,
+    pub create_time: u64,
+    pub update_time: u64,
+}
+
+// Crypto bought (or sold) with a card: source_amount of fiat_currency for obtain_amount of crypto_currency
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatPayment {
+    pub order_no: String,
+    #[serde(with = "string_or_float")] pub source_amount: Amount,
+    pub fiat_currency: String,
+    #[serde(with = "string_or_float")] pub obtain_amount: Amount,
+    pub crypto_currency: String,
+    #[serde(with = "string_or_float")] pub total_fee: Amount,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    // e.g. "Processing", "Completed", "Failed", "Refunded"
+    pub status: String,
+    #[serde(default)]
+    pub payment_method: Option<String>,
+    pub create_time: u64,
+    pub update_time: u64,
+}
+
+// Answer of margin transfers, loans and repayments
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionId {
+    pub tran_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAccount {
+    pub borrow_enabled: bool,
+    #[serde(with = "string_or_float")] pub margin_level: Amount,
+    #[serde(with = "string_or_float")] pub total_asset_of_btc: Amount,
+    #[serde(with = "string_or_float")] pub total_liability_of_btc: Amount,
+    #[serde(with = "string_or_float")] pub total_net_asset_of_btc: Amount,
+    pub trade_enabled: bool,
+    pub transfer_enabled: bool,
+    pub user_assets: Vec<MarginAsset>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub borrowed: Amount,
+    #[serde(with = "string_or_float")] pub free: Amount,
+    #[serde(with = "string_or_float")] pub interest: Amount,
+    #[serde(with = "string_or_float")] pub locked: Amount,
+    #[serde(with = "string_or_float")] pub net_asset: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginStatus {
+    pub success: bool,
+    pub symbol: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAccountInfo {
+    pub assets: Vec<IsolatedMarginPair>,
+    // Missing when the account was filtered by symbols
+    #[serde(default, with = "string_or_float_opt")] pub total_asset_of_btc: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub total_liability_of_btc: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub total_net_asset_of_btc: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginPair {
+    pub symbol: String,
+    pub base_asset: IsolatedMarginAsset,
+    pub quote_asset: IsolatedMarginAsset,
+    pub isolated_created: bool,
+    pub trade_enabled: bool,
+    #[serde(with = "string_or_float")] pub margin_level: Amount,
+    pub margin_level_status: String,
+    #[serde(with = "string_or_float")] pub margin_ratio: Amount,
+    #[serde(with = "string_or_float")] pub index_price: Amount,
+    #[serde(with = "string_or_float")] pub liquidate_price: Amount,
+    #[serde(with = "string_or_float")] pub liquidate_rate: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAsset {
+    pub asset: String,
+    pub borrow_enabled: bool,
+    pub repay_enabled: bool,
+    #[serde(with = "string_or_float")] pub borrowed: Amount,
+    #[serde(with = "string_or_float")] pub free: Amount,
+    #[serde(with = "string_or_float")] pub interest: Amount,
+    #[serde(with = "string_or_float")] pub locked: Amount,
+    #[serde(with = "string_or_float")] pub net_asset: Amount,
+    #[serde(with = "string_or_float")] pub net_asset_of_btc: Amount,
+    #[serde(with = "string_or_float")] pub total_asset: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxBorrowable {
+    #[serde(with = "string_or_float")] pub amount: Amount,
+    #[serde(default, with = "string_or_float_opt")] pub borrow_limit: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[serde(untagged)]
+pub enum Prices {
+    AllPrices(Vec<SymbolPrice>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SymbolPrice {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub price: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[serde(untagged)]
+pub enum BookTickers {
+    AllBookTickers(Vec<Tickers>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum KlineSummaries {
+    AllKlineSummaries(Vec<KlineSummary>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Tickers {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub bid_price: Amount,
+    #[serde(with = "string_or_float")] pub bid_qty: Amount,
+    #[serde(with = "string_or_float")] pub ask_price: Amount,
+    #[serde(with = "string_or_float")] pub ask_qty: Amount,
+}
+
+// OCO order list, orderReports comes with placing and canceling only
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: u64,
+    pub contingency_type: String,
+    pub list_status_type: String,
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OrderListItem>,
+    #[serde(default)] pub order_reports: Vec<OrderReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListItem {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReport {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub orig_qty: Amount,
+    #[serde(with = "string_or_float")] pub executed_qty: Amount,
+    #[serde(with = "string_or_float")] pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")] pub type_name: OrderType,
+    pub side: OrderSide,
+    #[serde(default, with = "string_or_float_opt")] pub stop_price: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeHistory {
+    #[serde(default)] pub symbol: String,
+    pub id: u64,
+    pub order_id: Option<u64>,
+    #[serde(default = "no_order_list")] pub order_list_id: i64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub qty: Amount,
+    #[serde(default, with = "string_or_float")] pub quote_qty: Amount,
+    #[serde(with = "string_or_float")] pub commission: Amount,
+    pub commission_asset: String,
+    pub time: u64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_best_match: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceStats {
+    // Only in the answers of /api/v3/ticker/24hr
+    #[serde(default)] pub symbol: String,
+    #[serde(with = "string_or_float")] pub price_change: Amount,
+    #[serde(with = "string_or_float")] pub price_change_percent: Amount,
+    #[serde(with = "string_or_float")] pub weighted_avg_price: Amount,
+    #[serde(with = "string_or_float")] pub prev_close_price: Amount,
+    #[serde(with = "string_or_float")] pub last_price: Amount,
+    #[serde(default, with = "string_or_float")] pub last_qty: Amount,
+    #[serde(with = "string_or_float")] pub bid_price: Amount,
+    #[serde(default, with = "string_or_float")] pub bid_qty: Amount,
+    #[serde(with = "string_or_float")] pub ask_price: Amount,
+    #[serde(default, with = "string_or_float")] pub ask_qty: Amount,
+    #[serde(with = "string_or_float")] pub open_price: Amount,
+    #[serde(with = "string_or_float")] pub high_price: Amount,
+    #[serde(with = "string_or_float")] pub low_price: Amount,
+    #[serde(with = "string_or_float")] pub volume: Amount,
+    #[serde(default, with = "string_or_float")] pub quote_volume: Amount,
+    pub open_time: u64,
+    pub close_time: u64,
+    // -1 when there was no trade in the window
+    pub first_id: i64,
+    pub last_id: i64,
+    pub count: u64,
+}
+
+// Answer of /api/v3/ticker, the 24hr statistics over a rolling window
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingWindowStats {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub price_change: Amount,
+    #[serde(with = "string_or_float")] pub price_change_percent: Amount,
+    #[serde(with = "string_or_float")] pub weighted_avg_price: Amount,
+    #[serde(with = "string_or_float")] pub open_price: Amount,
+    #[serde(with = "string_or_float")] pub high_price: Amount,
+    #[serde(with = "string_or_float")] pub low_price: Amount,
+    #[serde(with = "string_or_float")] pub last_price: Amount,
+    #[serde(with = "string_or_float")] pub volume: Amount,
+    #[serde(with = "string_or_float")] pub quote_volume: Amount,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub first_id: i64,
+    pub last_id: i64,
+    pub count: u64,
+}
+
+// Response type of the 24hr ticker, MINI answers MiniPriceStats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TickerType {
+    #[serde(rename = "FULL")] Full,
+    #[serde(rename = "MINI")] Mini,
+}
+
+impl TickerType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            TickerType::Full => "FULL",
+            TickerType::Mini => "MINI",
+        }
+    }
+}
+
+// MINI 24hr ticker, the fields of the miniTicker stream
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniPriceStats {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub open_price: Amount,
+    #[serde(with = "string_or_float")] pub high_price: Amount,
+    #[serde(with = "string_or_float")] pub low_price: Amount,
+    #[serde(with = "string_or_float")] pub last_price: Amount,
+    #[serde(with = "string_or_float")] pub volume: Amount,
+    #[serde(with = "string_or_float")] pub quote_volume: Amount,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub first_id: i64,
+    pub last_id: i64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    m: u64,
+    t: u64,
+    b: u64,
+    s: u64,
+
+    #[serde(rename = "T")] t_ignore: bool,
+    #[serde(rename = "W")] w_ignore: bool,
+    #[serde(rename = "D")] d_ignore: bool,
+
+    #[serde(rename = "B")] pub balance: Vec<EventBalance>,
+}
+
+impl AccountUpdateEvent {
+    pub fn to_balances(&self) -> Vec<Balance> {
+        self.balance.iter().map(Balance::from).collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventBalance {
+    #[serde(rename = "a")] pub asset: String,
+    #[serde(rename = "f", with = "string_or_float")] pub free: Amount,
+    #[serde(rename = "l", with = "string_or_float")] pub locked: Amount,
+}
+
+impl<'a> From<&'a EventBalance> for Balance {
+    fn from(balance: &'a EventBalance) -> Balance {
+        Balance {
+            asset: balance.asset.clone(),
+            free: balance.free,
+            locked: balance.locked,
+        }
+    }
+}
+
+impl From<EventBalance> for Balance {
+    fn from(balance: EventBalance) -> Balance {
+        Balance {
+            asset: balance.asset,
+            free: balance.free,
+            locked: balance.locked,
+        }
+    }
+}
+
+// State change of an order list (OCO)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListStatusEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "g")] pub order_list_id: i64,
+
+    #[serde(rename = "c")] pub contingency_type: String,
+
+    #[serde(rename = "l")] pub list_status_type: String,
+
+    #[serde(rename = "L")] pub list_order_status: String,
+
+    #[serde(rename = "r")] pub list_reject_reason: String,
+
+    #[serde(rename = "C")] pub list_client_order_id: String,
+
+    #[serde(rename = "T")] pub transaction_time: u64,
+
+    #[serde(rename = "O")] pub orders: Vec<OrderListOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "i")] pub order_id: u64,
+
+    #[serde(rename = "c")] pub client_order_id: String,
+}
+
+// Margin user data stream, sent when the margin level falls to the margin call level
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "cw", default, with = "string_or_float_opt")] pub cross_wallet_balance: Option<Amount>,
+
+    #[serde(rename = "p")] pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "ps")] pub position_side: Option<String>,
+
+    #[serde(rename = "pa", with = "string_or_float")] pub position_amount: Amount,
+
+    #[serde(rename = "mt")] pub margin_type: String,
+
+    #[serde(rename = "iw", default, with = "string_or_float_opt")] pub isolated_wallet: Option<Amount>,
+
+    #[serde(rename = "mp", with = "string_or_float")] pub mark_price: Amount,
+
+    #[serde(rename = "up", with = "string_or_float")] pub unrealized_pnl: Amount,
+
+    #[serde(rename = "mm", with = "string_or_float")] pub maintenance_margin_required: Amount,
+}
+
+// Sent on a user data stream, spot or futures, once its listen key expired. The stream has to be
+// restarted with a new listen key
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpiredEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E", with = "string_or_integer")] pub event_time: u64,
+
+    #[serde(default)] pub listen_key: Option<String>,
+}
+
+// USD-M futures ORDER_TRADE_UPDATE, the counterpart of executionReport
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesOrderTradeEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "T")] pub transaction_time: u64,
+
+    #[serde(rename = "o")] pub order: FuturesOrderUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesOrderUpdate {
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "c")] pub client_order_id: String,
+
+    #[serde(rename = "S")] pub side: OrderSide,
+
+    #[serde(rename = "o")] pub order_type: FuturesOrderType,
+
+    #[serde(rename = "f")] pub time_in_force: TimeInForce,
+
+    #[serde(rename = "q", with = "string_or_float")] pub qty: Amount,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+
+    #[serde(rename = "ap", with = "string_or_float")] pub average_price: Amount,
+
+    #[serde(rename = "sp", with = "string_or_float")] pub stop_price: Amount,
+
+    // NEW, CANCELED, CALCULATED (liquidation), EXPIRED, TRADE or AMENDMENT
+    #[serde(rename = "x")] pub execution_type: String,
+
+    #[serde(rename = "X")] pub order_status: OrderStatus,
+
+    #[serde(rename = "i")] pub order_id: u64,
+
+    #[serde(rename = "l", with = "string_or_float")] pub qty_last_filled_trade: Amount,
+
+    #[serde(rename = "z", with = "string_or_float")] pub accumulated_qty_filled_trades: Amount,
+
+    #[serde(rename = "L", with = "string_or_float")] pub price_last_filled_trade: Amount,
+
+    // Missing until the order trades
+    #[serde(rename = "N", default)] pub commission_asset: Option<String>,
+
+    #[serde(rename = "n", default, with = "string_or_float_opt")] pub commission: Option<Amount>,
+
+    #[serde(rename = "T")] pub trade_order_time: u64,
+
+    #[serde(rename = "t")] pub trade_id: i64,
+
+    #[serde(rename = "b", with = "string_or_float")] pub bids_notional: Amount,
+
+    #[serde(rename = "a", with = "string_or_float")] pub ask_notional: Amount,
+
+    #[serde(rename = "m")] pub is_buyer_maker: bool,
+
+    #[serde(rename = "R")] pub reduce_only: bool,
+
+    #[serde(rename = "wt")] pub working_type: WorkingType,
+
+    #[serde(rename = "ot")] pub original_order_type: FuturesOrderType,
+
+    #[serde(rename = "ps")] pub position_side: PositionSide,
+
+    #[serde(rename = "cp", default)] pub close_position: bool,
+
+    // Trailing stop orders only
+    #[serde(rename = "AP", default, with = "string_or_float_opt")] pub activation_price: Option<Amount>,
+
+    #[serde(rename = "cr", default, with = "string_or_float_opt")] pub callback_rate: Option<Amount>,
+
+    #[serde(rename = "rp", default, with = "string_or_float_opt")] pub realized_profit: Option<Amount>,
+}
+
+// USD-M futures ACCOUNT_UPDATE, the balances and positions that changed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesAccountUpdateEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "T")] pub transaction_time: u64,
+
+    #[serde(rename = "a")] pub update: FuturesAccountUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesAccountUpdate {
+    // What caused the update: ORDER, FUNDING_FEE, DEPOSIT, MARGIN_TRANSFER, ...
+    #[serde(rename = "m")] pub reason: String,
+
+    #[serde(rename = "B")] pub balances: Vec<FuturesEventBalance>,
+
+    #[serde(rename = "P")] pub positions: Vec<FuturesEventPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesEventBalance {
+    #[serde(rename = "a")] pub asset: String,
+
+    #[serde(rename = "wb", with = "string_or_float")] pub wallet_balance: Amount,
+
+    #[serde(rename = "cw", with = "string_or_float")] pub cross_wallet_balance: Amount,
+
+    // Change of the balance, apart from PnL and commission
+    #[serde(rename = "bc", default, with = "string_or_float_opt")] pub balance_change: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesEventPosition {
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "pa", with = "string_or_float")] pub position_amount: Amount,
+
+    #[serde(rename = "ep", with = "string_or_float")] pub entry_price: Amount,
+
+    #[serde(rename = "cr", with = "string_or_float")] pub accumulated_realized: Amount,
+
+    #[serde(rename = "up", with = "string_or_float")] pub unrealized_pnl: Amount,
+
+    // "isolated" or "cross"
+    #[serde(rename = "mt")] pub margin_type: String,
+
+    #[serde(rename = "iw", with = "string_or_float")] pub isolated_wallet: Amount,
+
+    #[serde(rename = "ps")] pub position_side: PositionSide,
+}
+
+// Successor of outboundAccountInfo, carries only the balances that changed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPositionEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "u")] pub last_update_time: u64,
+
+    #[serde(rename = "B")] pub balances: Vec<EventBalance>,
+}
+
+impl AccountPositionEvent {
+    // The changed balances as AccountInformation lists them, assets the event doesn't name are unchanged
+    pub fn to_balances(&self) -> Vec<Balance> {
+        self.balances.iter().map(Balance::from).collect()
+    }
+}
+
+// Deposits, withdrawals and transfers between accounts
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceUpdateEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "a")] pub asset: String,
+
+    #[serde(rename = "d", with = "string_or_float")] pub delta: Amount,
+
+    #[serde(rename = "T")] pub clear_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderTradeEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "c")] pub new_client_order_id: String,
+
+    #[serde(rename = "S")] pub side: OrderSide,
+
+    #[serde(rename = "o")] pub order_type: OrderType,
+
+    #[serde(rename = "f")] pub time_in_force: TimeInForce,
+
+    #[serde(rename = "q", with = "string_or_float")] pub qty: Amount,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+
+    #[serde(rename = "P", with = "string_or_float")] pub stop_price: Amount,
+
+    #[serde(rename = "F", with = "string_or_float")] pub iceberg_qty: Amount,
+
+    #[serde(rename = "g")] pub order_list_id: i64,
+
+    // Client order id of the canceled order, new_client_order_id is the one of the cancel request
+    #[serde(rename = "C")] pub orig_client_order_id: Option<String>,
+
+    #[serde(rename = "x")] pub execution_type: String,
+
+    #[serde(rename = "X")] pub order_status: OrderStatus,
+
+    #[serde(rename = "r")] pub order_reject_reason: String,
+
+    #[serde(rename = "i")] pub order_id: u64,
+
+    #[serde(rename = "l", with = "string_or_float")] pub qty_last_filled_trade: Amount,
+
+    #[serde(rename = "z", with = "string_or_float")] pub accumulated_qty_filled_trades: Amount,
+
+    #[serde(rename = "L", with = "string_or_float")] pub price_last_filled_trade: Amount,
+
+    #[serde(rename = "n", with = "string_or_float")] pub commission: Amount,
+
+    #[serde(rename = "N")] pub asset_commisioned: Option<String>,
+
+    #[serde(rename = "T")] pub trade_order_time: u64,
+
+    #[serde(rename = "t")] pub trade_id: i64,
+
+    #[serde(rename = "I")] pub i_ignore: u64,
+
+    pub w: bool,
+
+    #[serde(rename = "m")] pub is_buyer_maker: bool,
+
+    #[serde(rename = "M")] pub m_ignore: bool,
+
+    #[serde(rename = "O")] pub order_creation_time: Option<u64>,
+
+    #[serde(rename = "W")] pub working_time: Option<u64>,
+
+    #[serde(rename = "V")] pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+
+    // Only when self-trade prevention expired the order
+    #[serde(rename = "v")] pub prevented_match_id: Option<u64>,
+
+    #[serde(rename = "A", default, with = "string_or_float_opt")] pub prevented_quantity: Option<Amount>,
+
+    #[serde(rename = "Z", default, with = "string_or_float_opt")] pub cummulative_quote_qty: Option<Amount>,
+
+    // Quote quantity of the last fill, price_last_filled_trade * qty_last_filled_trade
+    #[serde(rename = "Y", default, with = "string_or_float_opt")] pub last_quote_qty: Option<Amount>,
+
+    // Only on orders placed with a quote order quantity
+    #[serde(rename = "Q", default, with = "string_or_float_opt")] pub quote_order_qty: Option<Amount>,
+}
+
+// The order as GET /api/v3/order would answer it after this event, to keep orders from the REST API and from
+// the user data stream in one place. update_time is the event time, time is the event time too when the
+// stream leaves out the creation time
+impl<'a> From<&'a OrderTradeEvent> for Order {
+    fn from(event: &'a OrderTradeEvent) -> Order {
+        let client_order_id = match event.orig_client_order_id {
+            Some(ref id) if !id.is_empty() => id.clone(),
+            _ => event.new_client_order_id.clone(),
+        };
+
+        Order {
+            symbol: event.symbol.clone(),
+            order_id: event.order_id,
+            order_list_id: event.order_list_id,
+            client_order_id,
+            price: event.price,
+            orig_qty: event.qty,
+            executed_qty: event.accumulated_qty_filled_trades,
+            status: event.order_status.clone(),
+            time_in_force: event.time_in_force.clone(),
+            type_name: event.order_type.clone(),
+            side: event.side.clone(),
+            stop_price: event.stop_price,
+            iceberg_qty: event.iceberg_qty,
+            time: event.order_creation_time.unwrap_or(event.event_time),
+            update_time: Some(event.event_time),
+            is_working: Some(event.w),
+            cummulative_quote_qty: event.cummulative_quote_qty,
+            orig_quote_order_qty: event.quote_order_qty,
+            working_time: event.working_time,
+            self_trade_prevention_mode: event.self_trade_prevention_mode.clone(),
+            prevented_match_id: event.prevented_match_id,
+            prevented_quantity: event.prevented_quantity,
+        }
+    }
+}
+
+impl From<OrderTradeEvent> for Order {
+    fn from(event: OrderTradeEvent) -> Order {
+        Order::from(&event)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "a")] pub aggregated_trade_id: u64,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+
+    #[serde(rename = "q", with = "string_or_float")] pub qty: Amount,
+
+    #[serde(rename = "f")] pub first_break_trade_id: u64,
+
+    #[serde(rename = "l")] pub last_break_trade_id: u64,
+
+    #[serde(rename = "T")] pub trade_order_time: u64,
+
+    #[serde(rename = "m")] pub is_buyer_maker: bool,
+
+    #[serde(rename = "M")] pub m_ignore: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "t")] pub trade_id: u64,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+
+    #[serde(rename = "q", with = "string_or_float")] pub qty: Amount,
+
+    #[serde(rename = "b")] pub buyer_order_id: u64,
+
+    #[serde(rename = "a")] pub seller_order_id: u64,
+
+    #[serde(rename = "T")] pub trade_order_time: u64,
+
+    #[serde(rename = "m")] pub is_buyer_maker: bool,
+
+    #[serde(rename = "M")] pub m_ignore: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayTickerEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price_change: Amount,
+
+    #[serde(rename = "P", with = "string_or_float")] pub price_change_percent: Amount,
+
+    #[serde(rename = "w", with = "string_or_float")] pub average_price: Amount,
+
+    #[serde(rename = "x", with = "string_or_float")] pub prev_close: Amount,
+
+    #[serde(rename = "c", with = "string_or_float")] pub current_close: Amount,
+
+    #[serde(rename = "Q", with = "string_or_float")] pub current_close_qty: Amount,
+
+    #[serde(rename = "b", with = "string_or_float")] pub best_bid: Amount,
+
+    #[serde(rename = "B", with = "string_or_float")] pub best_bid_qty: Amount,
+
+    #[serde(rename = "a", with = "string_or_float")] pub best_ask: Amount,
+
+    #[serde(rename = "A", with = "string_or_float")] pub best_ask_qty: Amount,
+
+    #[serde(rename = "o", with = "string_or_float")] pub open: Amount,
+
+    #[serde(rename = "h", with = "string_or_float")] pub high: Amount,
+
+    #[serde(rename = "l", with = "string_or_float")] pub low: Amount,
+
+    #[serde(rename = "v", with = "string_or_float")] pub volume: Amount,
+
+    #[serde(rename = "q", with = "string_or_float")] pub quote_volume: Amount,
+
+    #[serde(rename = "O")] pub open_time: u64,
+
+    #[serde(rename = "C")] pub close_time: u64,
+
+    #[serde(rename = "F")] pub first_trade_id: u64,
+
+    #[serde(rename = "L")] pub last_trade_id: u64,
+
+    #[serde(rename = "n")] pub num_trades: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BookTickerEvent {
+    #[serde(rename = "u")] pub update_id: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "b", with = "string_or_float")] pub best_bid: Amount,
+
+    #[serde(rename = "B", with = "string_or_float")] pub best_bid_qty: Amount,
+
+    #[serde(rename = "a", with = "string_or_float")] pub best_ask: Amount,
+
+    #[serde(rename = "A", with = "string_or_float")] pub best_ask_qty: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniTickerEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "c", with = "string_or_float")] pub close: Amount,
+
+    #[serde(rename = "o", with = "string_or_float")] pub open: Amount,
+
+    #[serde(rename = "h", with = "string_or_float")] pub high: Amount,
+
+    #[serde(rename = "l", with = "string_or_float")] pub low: Amount,
+
+    #[serde(rename = "v", with = "string_or_float")] pub volume: Amount,
+
+    #[serde(rename = "q", with = "string_or_float")] pub quote_volume: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KlineEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "k")] pub kline: Kline,
+}
+
+// Trade of GET /api/v3/trades and /api/v3/historicalTrades
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTrade {
+    pub id: u64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub qty: Amount,
+    #[serde(with = "string_or_float")] pub quote_qty: Amount,
+    pub time: u64,
+    pub is_buyer_maker: bool,
+    pub is_best_match: bool,
+}
+
+// Average price over the last mins minutes, what PERCENT_PRICE filters compare against
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AveragePrice {
+    pub mins: u64,
+    #[serde(with = "string_or_float")] pub price: Amount,
+}
+
+// Trades of one taker order at one price, GET /api/v3/aggTrades
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AggTrade {
+    #[serde(rename = "a")] pub agg_trade_id: u64,
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+    #[serde(rename = "q", with = "string_or_float")] pub qty: Amount,
+    #[serde(rename = "f")] pub first_trade_id: u64,
+    #[serde(rename = "l")] pub last_trade_id: u64,
+    #[serde(rename = "T")] pub time: u64,
+    #[serde(rename = "m")] pub is_buyer_maker: bool,
+    #[serde(rename = "M")] pub is_best_match: bool,
+}
+
+// Kline of GET /api/v3/klines. Binance sends it as an array,
+// [open_time, open, high, low, close, volume, close_time, quote_asset_volume, number_of_trades,
+// taker_buy_base_asset_volume, taker_buy_quote_asset_volume, ignore], it serializes with field names
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct KlineSummary {
+    pub open_time: i64,
+
+    #[serde(with = "string_or_float")] pub open: Amount,
+
+    #[serde(with = "string_or_float")] pub high: Amount,
+
+    #[serde(with = "string_or_float")] pub low: Amount,
+
+    #[serde(with = "string_or_float")] pub close: Amount,
+
+    #[serde(with = "string_or_float")] pub volume: Amount,
+
+    pub close_time: i64,
+
+    #[serde(with = "string_or_float")] pub quote_asset_volume: Amount,
+
+    pub number_of_trades: i64,
+
+    #[serde(with = "string_or_float")] pub taker_buy_base_asset_volume: Amount,
+
+    #[serde(with = "string_or_float")] pub taker_buy_quote_asset_volume: Amount,
+}
+
+// The named form KlineSummary serializes to
+#[derive(Deserialize)]
+struct NamedKlineSummary {
+    open_time: i64,
+    #[serde(with = "string_or_float")] open: Amount,
+    #[serde(with = "string_or_float")] high: Amount,
+    #[serde(with = "string_or_float")] low: Amount,
+    #[serde(with = "string_or_float")] close: Amount,
+    #[serde(with = "string_or_float")] volume: Amount,
+    close_time: i64,
+    #[serde(with = "string_or_float")] quote_asset_volume: Amount,
+    number_of_trades: i64,
+    #[serde(with = "string_or_float")] taker_buy_base_asset_volume: Amount,
+    #[serde(with = "string_or_float")] taker_buy_quote_asset_volume: Amount,
+}
+
+#[derive(Deserialize)]
+struct KlineAmount(#[serde(with = "string_or_float")] Amount);
+
+// Reads the array of the REST API as well as the named form, so stored klines load back
+impl<'de> Deserialize<'de> for KlineSummary {
+    fn deserialize<D>(deserializer: D) -> Result<KlineSummary, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KlineSummaryVisitor;
+
+        impl<'de> de::Visitor<'de> for KlineSummaryVisitor {
+            type Value = KlineSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a kline array or object")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<KlineSummary, A::Error>
+                where A: de::SeqAccess<'de>
+            {
+                let kline = KlineSummary {
+                    open_time: next_kline_field(&mut seq, 0)?,
+                    open: next_kline_field::<A, KlineAmount>(&mut seq, 1)?.0,
+                    high: next_kline_field::<A, KlineAmount>(&mut seq, 2)?.0,
+                    low: next_kline_field::<A, KlineAmount>(&mut seq, 3)?.0,
+                    close: next_kline_field::<A, KlineAmount>(&mut seq, 4)?.0,
+                    volume: next_kline_field::<A, KlineAmount>(&mut seq, 5)?.0,
+                    close_time: next_kline_field(&mut seq, 6)?,
+                    quote_asset_volume: next_kline_field::<A, KlineAmount>(&mut seq, 7)?.0,
+                    number_of_trades: next_kline_field(&mut seq, 8)?,
+                    taker_buy_base_asset_volume: next_kline_field::<A, KlineAmount>(&mut seq, 9)?.0,
+                    taker_buy_quote_asset_volume: next_kline_field::<A, KlineAmount>(&mut seq, 10)?.0,
+                };
+                // The unused trailing field, and whatever Binance appends later
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+
+                Ok(kline)
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<KlineSummary, A::Error>
+                where A: de::MapAccess<'de>
+            {
+                let named = NamedKlineSummary::deserialize(de::value::MapAccessDeserializer::new(map))?;
+
+                Ok(KlineSummary {
+                    open_time: named.open_time,
+                    open: named.open,
+                    high: named.high,
+                    low: named.low,
+                    close: named.close,
+                    volume: named.volume,
+                    close_time: named.close_time,
+                    quote_asset_volume: named.quote_asset_volume,
+                    number_of_trades: named.number_of_trades,
+                    taker_buy_base_asset_volume: named.taker_buy_base_asset_volume,
+                    taker_buy_quote_asset_volume: named.taker_buy_quote_asset_volume,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(KlineSummaryVisitor)
+    }
+}
+
+fn next_kline_field<'de, A, T>(seq: &mut A, index: usize) -> Result<T, A::Error>
+    where A: de::SeqAccess<'de>, T: Deserialize<'de>
+{
+    match seq.next_element()? {
+        Some(value) => Ok(value),
+        None => Err(de::Error::invalid_length(index, &"a kline array of at least 11 fields")),
+    }
+}
+
+// The candle of a kline stream, to merge live klines into a series read with get_klines
+impl From<Kline> for KlineSummary {
+    fn from(kline: Kline) -> KlineSummary {
+        KlineSummary {
+            open_time: kline.start_time,
+            open: kline.open,
+            high: kline.high,
+            low: kline.low,
+            close: kline.close,
+            volume: kline.volume,
+            close_time: kline.end_time,
+            quote_asset_volume: kline.quote_volume,
+            number_of_trades: i64::from(kline.number_of_trades),
+            taker_buy_base_asset_volume: kline.active_buy_volume,
+            taker_buy_quote_asset_volume: kline.active_volume_buy_quote,
+        }
+    }
+}
+
+impl<'a> From<&'a Kline> for KlineSummary {
+    fn from(kline: &'a Kline) -> KlineSummary {
+        KlineSummary::from(kline.clone())
+    }
+}
+
+impl<'a> From<&'a KlineEvent> for KlineSummary {
+    fn from(event: &'a KlineEvent) -> KlineSummary {
+        KlineSummary::from(&event.kline)
+    }
+}
+
+impl KlineEvent {
+    pub fn to_kline_summary(&self) -> KlineSummary {
+        KlineSummary::from(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Kline {
+    #[serde(rename = "t")] pub start_time: i64,
+
+    #[serde(rename = "T")] pub end_time: i64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "i")] pub interval: KlineInterval,
+
+    #[serde(rename = "f")] pub first_trade_id: i32,
+
+    #[serde(rename = "L")] pub last_trade_id: i32,
+
+    #[serde(rename = "o", with = "string_or_float")] pub open: Amount,
+
+    #[serde(rename = "c", with = "string_or_float")] pub close: Amount,
+
+    #[serde(rename = "h", with = "string_or_float")] pub high: Amount,
+
+    #[serde(rename = "l", with = "string_or_float")] pub low: Amount,
+
+    #[serde(rename = "v", with = "string_or_float")] pub volume: Amount,
+
+    #[serde(rename = "n")] pub number_of_trades: i32,
+
+    #[serde(rename = "x")] pub is_final_bar: bool,
+
+    #[serde(rename = "q", with = "string_or_float")] pub quote_volume: Amount,
+
+    #[serde(rename = "V", with = "string_or_float")] pub active_buy_volume: Amount,
+
+    #[serde(rename = "Q", with = "string_or_float")] pub active_volume_buy_quote: Amount,
+
+    #[serde(rename = "B")] pub ignore_me: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthOrderBookEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "U")] pub first_update_id: u64,
+
+    #[serde(rename = "u")] pub final_update_id: u64,
+
+    #[serde(rename = "b")] pub bids: Vec<Bids>,
+
+    #[serde(rename = "a")] pub asks: Vec<Asks>
+}
+
+// USD-M futures, <symbol>@markPrice[@1s]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPriceEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "p", with = "string_or_float")] pub mark_price: Amount,
+
+    #[serde(rename = "i", with = "string_or_float")] pub index_price: Amount,
+
+    #[serde(rename = "P", with = "string_or_float")] pub estimated_settle_price: Amount,
+
+    #[serde(rename = "r", with = "string_or_float")] pub funding_rate: Amount,
+
+    #[serde(rename = "T")] pub next_funding_time: u64,
+}
+
+// USD-M futures, <symbol>@forceOrder
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidationOrderEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "o")] pub order: LiquidationOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidationOrder {
+    #[serde(rename = "s")] pub symbol: String,
+
+    #[serde(rename = "S")] pub side: OrderSide,
+
+    #[serde(rename = "o")] pub order_type: OrderType,
+
+    #[serde(rename = "f")] pub time_in_force: TimeInForce,
+
+    #[serde(rename = "q", with = "string_or_float")] pub original_qty: Amount,
+
+    #[serde(rename = "p", with = "string_or_float")] pub price: Amount,
+
+    #[serde(rename = "ap", with = "string_or_float")] pub average_price: Amount,
+
+    #[serde(rename = "X")] pub order_status: OrderStatus,
+
+    #[serde(rename = "l", with = "string_or_float")] pub last_filled_qty: Amount,
+
+    #[serde(rename = "z", with = "string_or_float")] pub accumulated_filled_qty: Amount,
+
+    #[serde(rename = "T")] pub trade_time: u64,
+}
+
+// USD-M futures, <pair>_<contractType>@continuousKline_<interval>
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousKlineEvent {
+    #[serde(rename = "e")] pub event_type: String,
+
+    #[serde(rename = "E")] pub event_time: u64,
+
+    #[serde(rename = "ps")] pub pair: String,
+
+    #[serde(rename = "ct")] pub contract_type: String,
+
+    #[serde(rename = "k")] pub kline: ContinuousKline,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousKline {
+    #[serde(rename = "t")] pub start_time: i64,
+
+    #[serde(rename = "T")] pub end_time: i64,
+
+    #[serde(rename = "i")] pub interval: KlineInterval,
+
+    #[serde(rename = "f")] pub first_update_id: u64,
+
+    #[serde(rename = "L")] pub last_update_id: u64,
+
+    #[serde(rename = "o", with = "string_or_float")] pub open: Amount,
+
+    #[serde(rename = "c", with = "string_or_float")] pub close: Amount,
+
+    #[serde(rename = "h", with = "string_or_float")] pub high: Amount,
+
+    #[serde(rename = "l", with = "string_or_float")] pub low: Amount,
+
+    #[serde(rename = "v", with = "string_or_float")] pub volume: Amount,
+
+    #[serde(rename = "n")] pub number_of_trades: i64,
+
+    #[serde(rename = "x")] pub is_final_bar: bool,
+
+    #[serde(rename = "q", with = "string_or_float")] pub quote_volume: Amount,
+
+    #[serde(rename = "V", with = "string_or_float")] pub active_buy_volume: Amount,
+
+    #[serde(rename = "Q", with = "string_or_float")] pub active_volume_buy_quote: Amount,
+
+    #[serde(rename = "B")] pub ignore_me: String,
+}
+
+// Acknowledgement of a SUBSCRIBE / UNSUBSCRIBE / LIST_SUBSCRIPTIONS request,
+// result holds the active streams for LIST_SUBSCRIPTIONS and is null otherwise
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SubscriptionResponse {
+    pub result: Option<Vec<String>>,
+    pub error: Option<SubscriptionError>,
+    pub id: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SubscriptionError {
+    pub code: i64,
+    pub msg: String,
+}
+
+// USD-M futures account of GET /fapi/v2/account
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAccountInformation {
+    pub fee_tier: u32,
+    pub can_trade: bool,
+    pub can_deposit: bool,
+    pub can_withdraw: bool,
+    pub update_time: u64,
+    #[serde(with = "string_or_float")] pub total_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub total_maint_margin: Amount,
+    #[serde(with = "string_or_float")] pub total_wallet_balance: Amount,
+    #[serde(with = "string_or_float")] pub total_unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub total_margin_balance: Amount,
+    #[serde(with = "string_or_float")] pub total_position_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub total_open_order_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub total_cross_wallet_balance: Amount,
+    #[serde(rename = "totalCrossUnPnl", with = "string_or_float")] pub total_cross_unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub available_balance: Amount,
+    #[serde(with = "string_or_float")] pub max_withdraw_amount: Amount,
+    pub assets: Vec<FuturesAsset>,
+    pub positions: Vec<FuturesAccountPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub wallet_balance: Amount,
+    #[serde(with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub margin_balance: Amount,
+    #[serde(with = "string_or_float")] pub maint_margin: Amount,
+    #[serde(with = "string_or_float")] pub initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub position_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub open_order_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub cross_wallet_balance: Amount,
+    #[serde(rename = "crossUnPnl", with = "string_or_float")] pub cross_unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub available_balance: Amount,
+    #[serde(with = "string_or_float")] pub max_withdraw_amount: Amount,
+    // Whether the asset can be used as margin in multi-assets mode
+    #[serde(default)] pub margin_available: bool,
+    #[serde(default)] pub update_time: u64,
+}
+
+// Position as listed by the futures account, see FuturesPosition for the liquidation price
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAccountPosition {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub maint_margin: Amount,
+    #[serde(with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub position_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub open_order_initial_margin: Amount,
+    #[serde(with = "string_or_integer")] pub leverage: u32,
+    pub isolated: bool,
+    #[serde(with = "string_or_float")] pub entry_price: Amount,
+    #[serde(with = "string_or_float")] pub max_notional: Amount,
+    pub position_side: PositionSide,
+    // Negative for a short position in one-way mode
+    #[serde(with = "string_or_float")] pub position_amt: Amount,
+    #[serde(default)] pub update_time: u64,
+}
+
+// Position of GET /fapi/v2/positionRisk
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesPosition {
+    pub symbol: String,
+    pub position_side: PositionSide,
+    #[serde(with = "string_or_float")] pub position_amt: Amount,
+    #[serde(with = "string_or_float")] pub entry_price: Amount,
+    #[serde(with = "string_or_float")] pub mark_price: Amount,
+    #[serde(with = "string_or_float")] pub liquidation_price: Amount,
+    #[serde(rename = "unRealizedProfit", with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_integer")] pub leverage: u32,
+    // "isolated" or "cross"
+    pub margin_type: String,
+    #[serde(with = "string_or_float")] pub isolated_margin: Amount,
+    // "true" or "false"
+    pub is_auto_add_margin: String,
+    #[serde(with = "string_or_float")] pub max_notional_value: Amount,
+    #[serde(default, with = "string_or_float_opt")] pub notional: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub isolated_wallet: Option<Amount>,
+    #[serde(default)] pub update_time: u64,
+}
+
+// Answer of POST /fapi/v1/leverage
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesLeverage {
+    pub symbol: String,
+    pub leverage: u32,
+    #[serde(with = "string_or_float")] pub max_notional_value: Amount,
+}
+
+// {"code":200,"msg":"success"}
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FuturesStatus {
+    pub code: i64,
+    pub msg: String,
+}
+
+// USD-M futures order, as placed, canceled or queried. time is missing from the answers of
+// placing and canceling
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub avg_price: Amount,
+    #[serde(with = "string_or_float")] pub orig_qty: Amount,
+    #[serde(with = "string_or_float")] pub executed_qty: Amount,
+    #[serde(default, with = "string_or_float_opt")] pub cum_qty: Option<Amount>,
+    #[serde(with = "string_or_float")] pub cum_quote: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")] pub type_name: FuturesOrderType,
+    // Type the order was placed with, before a triggered stop turned it into a LIMIT or MARKET one
+    pub orig_type: FuturesOrderType,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub reduce_only: bool,
+    // Closes the whole position once triggered
+    pub close_position: bool,
+    #[serde(with = "string_or_float")] pub stop_price: Amount,
+    pub working_type: WorkingType,
+    #[serde(default)] pub price_protect: bool,
+    // Trailing stop orders only
+    #[serde(default, with = "string_or_float_opt")] pub activate_price: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub price_rate: Option<Amount>,
+    #[serde(default)] pub time: Option<u64>,
+    pub update_time: u64,
+}
+
+// Funding of a USD-M perpetual contract, GET /fapi/v1/fundingRate
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub funding_rate: Amount,
+    pub funding_time: u64,
+    // Missing from the older records
+    #[serde(default, with = "string_or_float_opt")] pub mark_price: Option<Amount>,
+}
+
+// Mark price, index price and funding of a USD-M contract, GET /fapi/v1/premiumIndex
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PremiumIndex {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub mark_price: Amount,
+    #[serde(with = "string_or_float")] pub index_price: Amount,
+    #[serde(with = "string_or_float")] pub estimated_settle_price: Amount,
+    // Empty for delivery contracts
+    #[serde(default, with = "string_or_float_opt")] pub last_funding_rate: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub interest_rate: Option<Amount>,
+    pub next_funding_time: u64,
+    pub time: u64,
+}
+
+// Open interest of a USD-M contract in contracts of the base asset, GET /fapi/v1/openInterest
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub open_interest: Amount,
+    pub time: u64,
+}
+
+// One period of GET /futures/data/openInterestHist
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterestHist {
+    pub symbol: String,
+    // In the base asset
+    #[serde(with = "string_or_float")] pub sum_open_interest: Amount,
+    // In the quote asset
+    #[serde(with = "string_or_float")] pub sum_open_interest_value: Amount,
+    pub timestamp: u64,
+}
+
+// COIN-M futures exchange info of GET /dapi/v1/exchangeInfo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesExchangeInformation {
+    pub timezone: String,
+    pub server_time: u64,
+    pub rate_limits: Vec<RateLimit>,
+    pub symbols: Vec<CoinFuturesSymbol>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesSymbol {
+    // e.g. BTCUSD_PERP or BTCUSD_240628, see ContractSymbol
+    pub symbol: String,
+    pub pair: String,
+    // PERPETUAL, CURRENT_QUARTER or NEXT_QUARTER
+    pub contract_type: String,
+    // Far in the future for perpetual contracts
+    pub delivery_date: u64,
+    pub onboard_date: u64,
+    #[serde(default)] pub contract_status: String,
+    // Value of one contract in the quote asset, e.g. 100 USD for BTCUSD
+    pub contract_size: u64,
+    pub margin_asset: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub price_precision: u32,
+    pub quantity_precision: u32,
+    #[serde(default)] pub order_types: Vec<FuturesOrderType>,
+    #[serde(default)] pub time_in_force: Vec<TimeInForce>,
+    #[serde(default)] pub filters: Vec<Filters>,
+}
+
+// Mark price of a COIN-M contract, GET /dapi/v1/premiumIndex
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesMarkPrice {
+    pub symbol: String,
+    pub pair: String,
+    #[serde(with = "string_or_float")] pub mark_price: Amount,
+    #[serde(with = "string_or_float")] pub index_price: Amount,
+    #[serde(with = "string_or_float")] pub estimated_settle_price: Amount,
+    // Perpetual contracts only, empty for delivery contracts
+    #[serde(default, with = "string_or_float_opt")] pub last_funding_rate: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub interest_rate: Option<Amount>,
+    #[serde(default)] pub next_funding_time: u64,
+    pub time: u64,
+}
+
+// COIN-M futures account of GET /dapi/v1/account. Margins are in the margin asset, e.g. BTC
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesAccountInformation {
+    pub fee_tier: u32,
+    pub can_trade: bool,
+    pub can_deposit: bool,
+    pub can_withdraw: bool,
+    #[serde(default)] pub update_time: u64,
+    pub assets: Vec<CoinFuturesAsset>,
+    pub positions: Vec<CoinFuturesAccountPosition>,
+}
 
-    #[serde(rename = "t")] pub trade_id: i64,
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")] pub wallet_balance: Amount,
+    #[serde(with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub margin_balance: Amount,
+    #[serde(with = "string_or_float")] pub maint_margin: Amount,
+    #[serde(with = "string_or_float")] pub initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub position_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub open_order_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub max_withdraw_amount: Amount,
+    #[serde(with = "string_or_float")] pub cross_wallet_balance: Amount,
+    #[serde(rename = "crossUnPnl", with = "string_or_float")] pub cross_unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub available_balance: Amount,
+}
 
-    #[serde(skip_serializing, rename = "I")] pub i_ignore: u64,
+// Unlike FuturesAccountPosition, position_amt and max_qty count contracts
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesAccountPosition {
+    pub symbol: String,
+    #[serde(with = "string_or_float")] pub position_amt: Amount,
+    #[serde(with = "string_or_float")] pub initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub maint_margin: Amount,
+    #[serde(with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")] pub position_initial_margin: Amount,
+    #[serde(with = "string_or_float")] pub open_order_initial_margin: Amount,
+    #[serde(with = "string_or_integer")] pub leverage: u32,
+    pub isolated: bool,
+    pub position_side: PositionSide,
+    #[serde(with = "string_or_float")] pub entry_price: Amount,
+    #[serde(with = "string_or_float")] pub max_qty: Amount,
+    #[serde(default)] pub update_time: u64,
+}
 
-    #[serde(skip_serializing)] pub w: bool,
+// Position of GET /dapi/v1/positionRisk. position_amt and max_qty count contracts, notional_value
+// is in the margin asset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesPosition {
+    pub symbol: String,
+    pub position_side: PositionSide,
+    #[serde(with = "string_or_float")] pub position_amt: Amount,
+    #[serde(with = "string_or_float")] pub entry_price: Amount,
+    #[serde(with = "string_or_float")] pub mark_price: Amount,
+    #[serde(with = "string_or_float")] pub liquidation_price: Amount,
+    #[serde(rename = "unRealizedProfit", with = "string_or_float")] pub unrealized_profit: Amount,
+    #[serde(with = "string_or_integer")] pub leverage: u32,
+    #[serde(with = "string_or_float")] pub max_qty: Amount,
+    // "isolated" or "cross"
+    pub margin_type: String,
+    #[serde(with = "string_or_float")] pub isolated_margin: Amount,
+    // "true" or "false"
+    pub is_auto_add_margin: String,
+    #[serde(default, with = "string_or_float_opt")] pub notional_value: Option<Amount>,
+    #[serde(default)] pub update_time: u64,
+}
 
-    #[serde(rename = "m")] pub is_buyer_maker: bool,
+// COIN-M futures order. Quantities count contracts, cum_base is the traded value in the base asset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinFuturesOrder {
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_float")] pub price: Amount,
+    #[serde(with = "string_or_float")] pub avg_price: Amount,
+    #[serde(with = "string_or_float")] pub orig_qty: Amount,
+    #[serde(with = "string_or_float")] pub executed_qty: Amount,
+    #[serde(default, with = "string_or_float_opt")] pub cum_qty: Option<Amount>,
+    #[serde(with = "string_or_float")] pub cum_base: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")] pub type_name: FuturesOrderType,
+    pub orig_type: FuturesOrderType,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    #[serde(with = "string_or_float")] pub stop_price: Amount,
+    pub working_type: WorkingType,
+    #[serde(default)] pub price_protect: bool,
+    #[serde(default, with = "string_or_float_opt")] pub activate_price: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")] pub price_rate: Option<Amount>,
+    #[serde(default)] pub time: Option<u64>,
+    pub update_time: u64,
+}
 
-    #[serde(skip_serializing, rename = "M")] pub m_ignore: bool,
+// Day a COIN-M delivery contract settles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeliveryDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct TradesEvent {
-    #[serde(rename = "e")] pub event_type: String,
+// A COIN-M contract symbol split into its pair and delivery: "BTCUSD_PERP" is the BTCUSD perpetual
+// contract, "BTCUSD_240628" the one delivered on 2024-06-28
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContractSymbol {
+    pub pair: String,
+    // None for perpetual contracts
+    pub delivery: Option<DeliveryDate>,
+}
 
-    #[serde(rename = "E")] pub event_time: u64,
+impl ContractSymbol {
+    pub fn is_perpetual(&self) -> bool {
+        self.delivery.is_none()
+    }
+}
 
-    #[serde(rename = "s")] pub symbol: String,
+impl FromStr for ContractSymbol {
+    type Err = ::errors::Error;
 
-    #[serde(rename = "a")] pub aggregated_trade_id: u64,
+    fn from_str(s: &str) -> ::errors::Result<ContractSymbol> {
+        let symbol = s.trim().to_uppercase();
+        let mut parts = symbol.splitn(2, '_');
+        let pair = parts.next().unwrap_or("");
+        let suffix = parts.next().unwrap_or("");
+        if pair.is_empty() || !pair.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(::errors::BinanceError::Parameter(format!("Invalid contract symbol {:?}", s)));
+        }
+
+        let delivery = match suffix {
+            "PERP" => None,
+            date if date.len() == 6 && date.chars().all(|c| c.is_ascii_digit()) => {
+                // Two digits each, they parse
+                let number = |digits: &str| digits.parse::<u8>().unwrap_or(0);
+                let (year, month, day) = (number(&date[..2]), number(&date[2..4]), number(&date[4..]));
+                if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+                    bail!(::errors::BinanceError::Parameter(format!(
+                        "Invalid delivery date in contract symbol {:?}",
+                        s
+                    )));
+                }
+                Some(DeliveryDate { year: 2000 + u16::from(year), month, day })
+            }
+            _ => bail!(::errors::BinanceError::Parameter(format!("Invalid contract symbol {:?}", s))),
+        };
+
+        Ok(ContractSymbol { pair: pair.into(), delivery })
+    }
+}
+
+impl fmt::Display for ContractSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.delivery {
+            Some(date) => write!(f, "{}_{:02}{:02}{:02}", self.pair, date.year % 100, date.month, date.day),
+            None => write!(f, "{}_PERP", self.pair),
+        }
+    }
+}
 
-    #[serde(rename = "p")] pub price: String,
+// Enum of the tokens of a Binance field, Other keeps the ones added after this crate
+macro_rules! token_enum {
+    ($(#[$meta:meta])* pub enum $name:ident { $($variant:ident => $token:tt,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            Other(String),
+        }
 
-    #[serde(rename = "q")] pub qty: String,
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match *self {
+                    $($name::$variant => $token,)*
+                    $name::Other(ref other) => other,
+                }
+            }
+        }
 
-    #[serde(rename = "f")] pub first_break_trade_id: u64,
+        impl<'a> From<&'a str> for $name {
+            fn from(token: &'a str) -> $name {
+                match token {
+                    $($token => $name::$variant,)*
+                    other => $name::Other(other.into()),
+                }
+            }
+        }
 
-    #[serde(rename = "l")] pub last_break_trade_id: u64,
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
 
-    #[serde(rename = "T")] pub trade_order_time: u64,
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
 
-    #[serde(rename = "m")] pub is_buyer_maker: bool,
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<$name, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let token = String::deserialize(deserializer)?;
 
-    #[serde(skip_serializing, rename = "M")] pub m_ignore: bool,
+                Ok($name::from(token.as_str()))
+            }
+        }
+    };
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct DayTickerEvent {
-    #[serde(rename = "e")] pub event_type: String,
+token_enum! {
+    // Trading permission of symbols and accounts, the trading groups (TRD_GRP_002...) are Other
+    pub enum Permission {
+        Spot => "SPOT",
+        Margin => "MARGIN",
+        Leveraged => "LEVERAGED",
+    }
+}
 
-    #[serde(rename = "E")] pub event_time: u64,
+token_enum! {
+    pub enum OrderSide {
+        Buy => "BUY",
+        Sell => "SELL",
+    }
+}
 
-    #[serde(rename = "s")] pub symbol: String,
+token_enum! {
+    pub enum OrderType {
+        Limit => "LIMIT",
+        Market => "MARKET",
+        StopLoss => "STOP_LOSS",
+        StopLossLimit => "STOP_LOSS_LIMIT",
+        TakeProfit => "TAKE_PROFIT",
+        TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+        // A LIMIT order rejected when it would trade at once
+        LimitMaker => "LIMIT_MAKER",
+    }
+}
 
-    #[serde(rename = "p")] pub price_change: String,
+impl OrderType {
+    // Triggered by a stop price
+    pub fn is_stop(&self) -> bool {
+        let stop_types = [
+            OrderType::StopLoss, OrderType::StopLossLimit, OrderType::TakeProfit, OrderType::TakeProfitLimit,
+        ];
 
-    #[serde(rename = "P")] pub price_change_percent: String,
+        stop_types.contains(self)
+    }
 
-    #[serde(rename = "w")] pub average_price: String,
+    // Placed with a price and a time in force
+    pub fn is_limit(&self) -> bool {
+        [OrderType::Limit, OrderType::StopLossLimit, OrderType::TakeProfitLimit].contains(self)
+    }
+}
 
-    #[serde(rename = "x")] pub prev_close: String,
+token_enum! {
+    pub enum OrderStatus {
+        New => "NEW",
+        PartiallyFilled => "PARTIALLY_FILLED",
+        Filled => "FILLED",
+        Canceled => "CANCELED",
+        PendingCancel => "PENDING_CANCEL",
+        Rejected => "REJECTED",
+        Expired => "EXPIRED",
+        // Expired by self-trade prevention
+        ExpiredInMatch => "EXPIRED_IN_MATCH",
+    }
+}
 
-    #[serde(rename = "c")] pub current_close: String,
+token_enum! {
+    // How long an order stays on the book. IOC fills what it can at once and cancels the rest,
+    // FOK fills completely at once or not at all
+    pub enum TimeInForce {
+        GoodTillCanceled => "GTC",
+        ImmediateOrCancel => "IOC",
+        FillOrKill => "FOK",
+    }
+}
 
-    #[serde(rename = "Q")] pub current_close_qty: String,
+// What placing an order answers: ACK only the ids, RESULT the final state, FULL also the fills
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderResponseType {
+    #[serde(rename = "ACK")] Ack,
+    #[serde(rename = "RESULT")] Result,
+    #[serde(rename = "FULL")] Full,
+}
 
-    #[serde(rename = "b")] pub best_bid: String,
+impl OrderResponseType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            OrderResponseType::Ack => "ACK",
+            OrderResponseType::Result => "RESULT",
+            OrderResponseType::Full => "FULL",
+        }
+    }
+}
 
-    #[serde(rename = "B")] pub best_bid_qty: String,
+token_enum! {
+    // One-way mode holds a single BOTH position per USD-M futures symbol, hedge mode a LONG and
+    // a SHORT one
+    pub enum PositionSide {
+        Both => "BOTH",
+        Long => "LONG",
+        Short => "SHORT",
+    }
+}
 
-    #[serde(rename = "a")] pub best_ask: String,
+token_enum! {
+    pub enum FuturesOrderType {
+        Limit => "LIMIT",
+        Market => "MARKET",
+        Stop => "STOP",
+        StopMarket => "STOP_MARKET",
+        TakeProfit => "TAKE_PROFIT",
+        TakeProfitMarket => "TAKE_PROFIT_MARKET",
+        TrailingStopMarket => "TRAILING_STOP_MARKET",
+    }
+}
 
-    #[serde(rename = "A")] pub best_ask_qty: String,
+token_enum! {
+    // Price a futures stop order is triggered on
+    pub enum WorkingType {
+        MarkPrice => "MARK_PRICE",
+        ContractPrice => "CONTRACT_PRICE",
+    }
+}
 
-    #[serde(rename = "o")] pub open: String,
+token_enum! {
+    // PROCESS and ACCEPT_SUCCESS are still converting, SUCCESS and FAIL are final
+    pub enum ConvertOrderStatus {
+        Process => "PROCESS",
+        AcceptSuccess => "ACCEPT_SUCCESS",
+        Success => "SUCCESS",
+        Fail => "FAIL",
+    }
+}
 
-    #[serde(rename = "h")] pub high: String,
+// Whether cancel_replace_order places the new order when the cancel fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReplaceMode {
+    StopOnFailure,
+    AllowFailure,
+}
 
-    #[serde(rename = "l")] pub low: String,
+impl CancelReplaceMode {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            CancelReplaceMode::StopOnFailure => "STOP_ON_FAILURE",
+            CancelReplaceMode::AllowFailure => "ALLOW_FAILURE",
+        }
+    }
+}
 
-    #[serde(rename = "v")] pub volume: String,
+// What happens when an order would match another order of the same account (or trade group).
+// Modes this version doesn't know deserialize into Other
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SelfTradePreventionMode {
+    // Expires the incoming order
+    ExpireTaker,
+    // Expires the resting order
+    ExpireMaker,
+    ExpireBoth,
+    None,
+    Other(String),
+}
 
-    #[serde(rename = "q")] pub quote_volume: String,
+impl SelfTradePreventionMode {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            SelfTradePreventionMode::ExpireTaker => "EXPIRE_TAKER",
+            SelfTradePreventionMode::ExpireMaker => "EXPIRE_MAKER",
+            SelfTradePreventionMode::ExpireBoth => "EXPIRE_BOTH",
+            SelfTradePreventionMode::None => "NONE",
+            SelfTradePreventionMode::Other(ref mode) => mode,
+        }
+    }
+}
 
-    #[serde(rename = "O")] pub open_time: u64,
+impl<'a> From<&'a str> for SelfTradePreventionMode {
+    fn from(mode: &'a str) -> SelfTradePreventionMode {
+        match mode {
+            "EXPIRE_TAKER" => SelfTradePreventionMode::ExpireTaker,
+            "EXPIRE_MAKER" => SelfTradePreventionMode::ExpireMaker,
+            "EXPIRE_BOTH" => SelfTradePreventionMode::ExpireBoth,
+            "NONE" => SelfTradePreventionMode::None,
+            other => SelfTradePreventionMode::Other(other.into()),
+        }
+    }
+}
 
-    #[serde(rename = "C")] pub close_time: u64,
+impl Serialize for SelfTradePreventionMode {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
 
-    #[serde(rename = "F")] pub first_trade_id: u64,
+impl<'de> Deserialize<'de> for SelfTradePreventionMode {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<SelfTradePreventionMode, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mode = String::deserialize(deserializer)?;
 
-    #[serde(rename = "L")] pub last_trade_id: u64,
+        Ok(SelfTradePreventionMode::from(mode.as_str()))
+    }
+}
 
-    #[serde(rename = "n")] pub num_trades: u64,
+// Kline / candlestick intervals, shared by the klines endpoint and the kline streams.
+// Intervals this version doesn't know deserialize into Other
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KlineInterval {
+    Seconds1,
+    Minutes1,
+    Minutes3,
+    Minutes5,
+    Minutes15,
+    Minutes30,
+    Hours1,
+    Hours2,
+    Hours4,
+    Hours6,
+    Hours8,
+    Hours12,
+    Days1,
+    Days3,
+    Weeks1,
+    Months1,
+    Other(String),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct KlineEvent {
-    #[serde(rename = "e")] pub event_type: String,
+impl KlineInterval {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            KlineInterval::Seconds1 => "1s",
+            KlineInterval::Minutes1 => "1m",
+            KlineInterval::Minutes3 => "3m",
+            KlineInterval::Minutes5 => "5m",
+            KlineInterval::Minutes15 => "15m",
+            KlineInterval::Minutes30 => "30m",
+            KlineInterval::Hours1 => "1h",
+            KlineInterval::Hours2 => "2h",
+            KlineInterval::Hours4 => "4h",
+            KlineInterval::Hours6 => "6h",
+            KlineInterval::Hours8 => "8h",
+            KlineInterval::Hours12 => "12h",
+            KlineInterval::Days1 => "1d",
+            KlineInterval::Days3 => "3d",
+            KlineInterval::Weeks1 => "1w",
+            KlineInterval::Months1 => "1M",
+            KlineInterval::Other(ref interval) => interval,
+        }
+    }
+}
 
-    #[serde(rename = "E")] pub event_time: u64,
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-    #[serde(rename = "s")] pub symbol: String,
+impl FromStr for KlineInterval {
+    type Err = ();
+
+    // Never fails, unknown tokens become Other
+    fn from_str(s: &str) -> ::std::result::Result<KlineInterval, ()> {
+        Ok(match s {
+            "1s" => KlineInterval::Seconds1,
+            "1m" => KlineInterval::Minutes1,
+            "3m" => KlineInterval::Minutes3,
+            "5m" => KlineInterval::Minutes5,
+            "15m" => KlineInterval::Minutes15,
+            "30m" => KlineInterval::Minutes30,
+            "1h" => KlineInterval::Hours1,
+            "2h" => KlineInterval::Hours2,
+            "4h" => KlineInterval::Hours4,
+            "6h" => KlineInterval::Hours6,
+            "8h" => KlineInterval::Hours8,
+            "12h" => KlineInterval::Hours12,
+            "1d" => KlineInterval::Days1,
+            "3d" => KlineInterval::Days3,
+            "1w" => KlineInterval::Weeks1,
+            "1M" => KlineInterval::Months1,
+            other => KlineInterval::Other(other.into()),
+        })
+    }
+}
 
-    #[serde(rename = "k")] pub kline: Kline,
+impl<'a> From<&'a str> for KlineInterval {
+    fn from(interval: &'a str) -> KlineInterval {
+        interval.parse().unwrap_or_else(|_| KlineInterval::Other(interval.into()))
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct KlineSummary {
-    pub open_time: i64,
+impl From<KlineInterval> for String {
+    fn from(interval: KlineInterval) -> String {
+        interval.as_str().into()
+    }
+}
 
-    pub open: f64,
+// Window of the rolling window statistics: 1 to 59 minutes, 1 to 23 hours or 1 to 7 days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowSize {
+    Minutes(u8),
+    Hours(u8),
+    Days(u8),
+}
 
-    pub high: f64,
+impl WindowSize {
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            WindowSize::Minutes(minutes) => (1..=59).contains(&minutes),
+            WindowSize::Hours(hours) => (1..=23).contains(&hours),
+            WindowSize::Days(days) => (1..=7).contains(&days),
+        }
+    }
+}
 
-    pub low: f64,
+impl fmt::Display for WindowSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WindowSize::Minutes(minutes) => write!(f, "{}m", minutes),
+            WindowSize::Hours(hours) => write!(f, "{}h", hours),
+            WindowSize::Days(days) => write!(f, "{}d", days),
+        }
+    }
+}
 
-    pub close: f64,
+impl FromStr for WindowSize {
+    type Err = ();
 
-    pub volume: f64,
+    // "15m", "4h" or "7d", out of range sizes are rejected
+    fn from_str(s: &str) -> ::std::result::Result<WindowSize, ()> {
+        if s.len() < 2 {
+            return Err(());
+        }
+        let (amount, unit) = s.split_at(s.len() - 1);
+        let amount: u8 = amount.parse().map_err(|_| ())?;
+        let window_size = match unit {
+            "m" => WindowSize::Minutes(amount),
+            "h" => WindowSize::Hours(amount),
+            "d" => WindowSize::Days(amount),
+            _ => return Err(()),
+        };
+
+        if window_size.is_valid() {
+            Ok(window_size)
+        } else {
+            Err(())
+        }
+    }
+}
 
-    pub close_time: i64,
+impl Serialize for KlineInterval {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
 
-    pub quote_asset_volume: f64,
+impl<'de> Deserialize<'de> for KlineInterval {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<KlineInterval, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let interval = String::deserialize(deserializer)?;
 
-    pub number_of_trades: i64,
+        Ok(KlineInterval::from(interval.as_str()))
+    }
+}
 
-    pub taker_buy_base_asset_volume: f64,
+// Source and destination wallet of a universal transfer: MAIN is spot, UMFUTURE USD-M futures,
+// CMFUTURE COIN-M futures, FUNDING the funding wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferType {
+    MainUmfuture,
+    MainCmfuture,
+    MainMargin,
+    MainFunding,
+    UmfutureMain,
+    UmfutureMargin,
+    UmfutureFunding,
+    CmfutureMain,
+    CmfutureMargin,
+    CmfutureFunding,
+    MarginMain,
+    MarginUmfuture,
+    MarginCmfuture,
+    MarginIsolatedMargin,
+    MarginFunding,
+    IsolatedMarginMargin,
+    IsolatedMarginIsolatedMargin,
+    FundingMain,
+    FundingUmfuture,
+    FundingCmfuture,
+    FundingMargin,
+    Other(String),
+}
 
-    pub taker_buy_quote_asset_volume: f64,
+impl TransferType {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            TransferType::MainUmfuture => "MAIN_UMFUTURE",
+            TransferType::MainCmfuture => "MAIN_CMFUTURE",
+            TransferType::MainMargin => "MAIN_MARGIN",
+            TransferType::MainFunding => "MAIN_FUNDING",
+            TransferType::UmfutureMain => "UMFUTURE_MAIN",
+            TransferType::UmfutureMargin => "UMFUTURE_MARGIN",
+            TransferType::UmfutureFunding => "UMFUTURE_FUNDING",
+            TransferType::CmfutureMain => "CMFUTURE_MAIN",
+            TransferType::CmfutureMargin => "CMFUTURE_MARGIN",
+            TransferType::CmfutureFunding => "CMFUTURE_FUNDING",
+            TransferType::MarginMain => "MARGIN_MAIN",
+            TransferType::MarginUmfuture => "MARGIN_UMFUTURE",
+            TransferType::MarginCmfuture => "MARGIN_CMFUTURE",
+            TransferType::MarginIsolatedMargin => "MARGIN_ISOLATEDMARGIN",
+            TransferType::MarginFunding => "MARGIN_FUNDING",
+            TransferType::IsolatedMarginMargin => "ISOLATEDMARGIN_MARGIN",
+            TransferType::IsolatedMarginIsolatedMargin => "ISOLATEDMARGIN_ISOLATEDMARGIN",
+            TransferType::FundingMain => "FUNDING_MAIN",
+            TransferType::FundingUmfuture => "FUNDING_UMFUTURE",
+            TransferType::FundingCmfuture => "FUNDING_CMFUTURE",
+            TransferType::FundingMargin => "FUNDING_MARGIN",
+            TransferType::Other(ref transfer_type) => transfer_type,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct Kline {
-    #[serde(rename = "t")] pub start_time: i64,
+impl fmt::Display for TransferType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-    #[serde(rename = "T")] pub end_time: i64,
+impl<'a> From<&'a str> for TransferType {
+    fn from(transfer_type: &'a str) -> TransferType {
+        match transfer_type {
+            "MAIN_UMFUTURE" => TransferType::MainUmfuture,
+            "MAIN_CMFUTURE" => TransferType::MainCmfuture,
+            "MAIN_MARGIN" => TransferType::MainMargin,
+            "MAIN_FUNDING" => TransferType::MainFunding,
+            "UMFUTURE_MAIN" => TransferType::UmfutureMain,
+            "UMFUTURE_MARGIN" => TransferType::UmfutureMargin,
+            "UMFUTURE_FUNDING" => TransferType::UmfutureFunding,
+            "CMFUTURE_MAIN" => TransferType::CmfutureMain,
+            "CMFUTURE_MARGIN" => TransferType::CmfutureMargin,
+            "CMFUTURE_FUNDING" => TransferType::CmfutureFunding,
+            "MARGIN_MAIN" => TransferType::MarginMain,
+            "MARGIN_UMFUTURE" => TransferType::MarginUmfuture,
+            "MARGIN_CMFUTURE" => TransferType::MarginCmfuture,
+            "MARGIN_ISOLATEDMARGIN" => TransferType::MarginIsolatedMargin,
+            "MARGIN_FUNDING" => TransferType::MarginFunding,
+            "ISOLATEDMARGIN_MARGIN" => TransferType::IsolatedMarginMargin,
+            "ISOLATEDMARGIN_ISOLATEDMARGIN" => TransferType::IsolatedMarginIsolatedMargin,
+            "FUNDING_MAIN" => TransferType::FundingMain,
+            "FUNDING_UMFUTURE" => TransferType::FundingUmfuture,
+            "FUNDING_CMFUTURE" => TransferType::FundingCmfuture,
+            "FUNDING_MARGIN" => TransferType::FundingMargin,
+            other => TransferType::Other(other.into()),
+        }
+    }
+}
 
-    #[serde(rename = "s")] pub symbol: String,
+impl Serialize for TransferType {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
 
-    #[serde(rename = "i")] pub interval: String,
+impl<'de> Deserialize<'de> for TransferType {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<TransferType, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let transfer_type = String::deserialize(deserializer)?;
 
-    #[serde(rename = "f")] pub first_trade_id: i32,
+        Ok(TransferType::from(transfer_type.as_str()))
+    }
+}
 
-    #[serde(rename = "L")] pub last_trade_id: i32,
+// UTC times of the millisecond timestamps, with the chrono feature
+#[cfg(feature = "chrono")]
+trait EpochMillis {
+    fn to_utc(self) -> DateTime<Utc>;
+}
 
-    #[serde(rename = "o")] pub open: String,
+#[cfg(feature = "chrono")]
+impl EpochMillis for u64 {
+    fn to_utc(self) -> DateTime<Utc> {
+        DateTime::from(UNIX_EPOCH + Duration::from_millis(self))
+    }
+}
 
-    #[serde(rename = "c")] pub close: String,
+#[cfg(feature = "chrono")]
+impl EpochMillis for i64 {
+    fn to_utc(self) -> DateTime<Utc> {
+        if self < 0 {
+            DateTime::from(UNIX_EPOCH - Duration::from_millis(self.unsigned_abs()))
+        } else {
+            DateTime::from(UNIX_EPOCH + Duration::from_millis(self as u64))
+        }
+    }
+}
 
-    #[serde(rename = "h")] pub high: String,
+#[cfg(feature = "chrono")]
+macro_rules! utc_times {
+    ($($model:ident { $($accessor:ident => $field:ident,)* })*) => {
+        $(
+            impl $model {
+                $(
+                    pub fn $accessor(&self) -> DateTime<Utc> {
+                        self.$field.to_utc()
+                    }
+                )*
+            }
+        )*
+    };
+}
 
-    #[serde(rename = "l")] pub low: String,
+#[cfg(feature = "chrono")]
+utc_times! {
+    ServerTime { server_time_utc => server_time, }
+    Order { time_utc => time, }
+    Transaction { transact_time_utc => transact_time, }
+    OrderReport { transact_time_utc => transact_time, }
+    TradeHistory { time_utc => time, }
+    MarketTrade { time_utc => time, }
+    AggTrade { time_utc => time, }
+    KlineSummary { open_time_utc => open_time, close_time_utc => close_time, }
+    Kline { open_time_utc => start_time, close_time_utc => end_time, }
+    ContinuousKline { open_time_utc => start_time, close_time_utc => end_time, }
+    AccountUpdateEvent { event_time_utc => event_time, }
+    OrderListStatusEvent { event_time_utc => event_time, }
+    MarginCallEvent { event_time_utc => event_time, }
+    ListenKeyExpiredEvent { event_time_utc => event_time, }
+    FuturesOrderTradeEvent { event_time_utc => event_time, }
+    FuturesAccountUpdateEvent { event_time_utc => event_time, }
+    AccountPositionEvent { event_time_utc => event_time, }
+    BalanceUpdateEvent { event_time_utc => event_time, }
+    OrderTradeEvent { event_time_utc => event_time, trade_order_time_utc => trade_order_time, }
+    TradesEvent { event_time_utc => event_time, trade_order_time_utc => trade_order_time, }
+    TradeEvent { event_time_utc => event_time, trade_order_time_utc => trade_order_time, }
+    DayTickerEvent { event_time_utc => event_time, open_time_utc => open_time, close_time_utc => close_time, }
+    MiniTickerEvent { event_time_utc => event_time, }
+    KlineEvent { event_time_utc => event_time, }
+    DepthOrderBookEvent { event_time_utc => event_time, }
+    MarkPriceEvent { event_time_utc => event_time, }
+    LiquidationOrderEvent { event_time_utc => event_time, }
+    ContinuousKlineEvent { event_time_utc => event_time, }
+    FundingRate { funding_time_utc => funding_time, }
+    PremiumIndex { time_utc => time, next_funding_time_utc => next_funding_time, }
+    OpenInterest { time_utc => time, }
+    OpenInterestHist { timestamp_utc => timestamp, }
+    LockedPosition { purchase_time_utc => purchase_time, deliver_date_utc => deliver_date, }
+    LockedReward { time_utc => time, }
+    EthRedemption { arrival_time_utc => arrival_time, }
+    EthStakingRate { time_utc => time, }
+    ConvertQuote { valid_timestamp_utc => valid_timestamp, }
+    ConvertAcceptance { create_time_utc => create_time, }
+    ConvertOrder { create_time_utc => create_time, }
+    ConvertTrade { create_time_utc => create_time, }
+    FiatOrder { create_time_utc => create_time, update_time_utc => update_time, }
+    FiatPayment { create_time_utc => create_time, update_time_utc => update_time, }
+    ApiKeyPermissions { create_time_utc => create_time, }
+}
 
-    #[serde(rename = "v")] pub volume: String,
+#[cfg(feature = "chrono")]
+impl Order {
+    pub fn update_time_utc(&self) -> Option<DateTime<Utc>> {
+        self.update_time.map(EpochMillis::to_utc)
+    }
+}
 
-    #[serde(rename = "n")] pub number_of_trades: i32,
+mod empty_string_as_none {
+    use serde::{Deserialize, Deserializer, Serializer};
 
-    #[serde(rename = "x")] pub is_final_bar: bool,
+    // None is written as the "" it was read from
+    pub fn serialize<S>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(value.as_ref().map_or("", String::as_str))
+    }
 
-    #[serde(rename = "q")] pub quote_volume: String,
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+        where D: Deserializer<'de>
+    {
+        let value: Option<String> = Option::deserialize(deserializer)?;
 
-    #[serde(rename = "V")] pub active_buy_volume: String,
+        Ok(value.and_then(|value| if value.is_empty() { None } else { Some(value) }))
+    }
+}
 
-    #[serde(rename = "Q")] pub active_volume_buy_quote: String,
+mod string_or_float {
+    use std::fmt;
 
-    #[serde(skip_serializing, rename = "B")] pub ignore_me: String,
-}
+    use serde::{de, Serializer, Deserializer};
+    use super::Amount;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct DepthOrderBookEvent {
-    #[serde(rename = "e")] pub event_type: String,
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: fmt::Display,
+              S: Serializer
+    {
+        serializer.collect_str(value)
+    }
 
-    #[serde(rename = "E")] pub event_time: u64,
+    // Visits the value directly, depth snapshots carry up to 10000 of them
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Amount, D::Error>
+        where D: Deserializer<'de>
+    {
+        struct StringOrFloat;
+
+        impl<'de> de::Visitor<'de> for StringOrFloat {
+            type Value = Amount;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a float or a string holding one")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Amount, E>
+                where E: de::Error
+            {
+                parse(value).map_err(de::Error::custom)
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Amount, E>
+                where E: de::Error
+            {
+                from_f64(value).map_err(de::Error::custom)
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Amount, E>
+                where E: de::Error
+            {
+                parse(&value.to_string()).map_err(de::Error::custom)
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Amount, E>
+                where E: de::Error
+            {
+                parse(&value.to_string()).map_err(de::Error::custom)
+            }
+        }
 
-    #[serde(rename = "s")] pub symbol: String,
+        deserializer.deserialize_any(StringOrFloat)
+    }
 
-    #[serde(rename = "U")] pub first_update_id: u64,
+    // Some ticker fields of dead symbols hold "INF". An empty value fails like it does for decimal,
+    // a made up 0 would pass for a real amount
+    #[cfg(not(feature = "decimal"))]
+    pub fn parse(value: &str) -> Result<Amount, ::std::num::ParseFloatError> {
+        match value.trim() {
+            "INF" | "+INF" => Ok(::std::f64::INFINITY),
+            "-INF" => Ok(::std::f64::NEG_INFINITY),
+            value => value.parse(),
+        }
+    }
 
-    #[serde(rename = "u")] pub final_update_id: u64,
+    // Decimal has no infinity, and a made up 0 or maximum would pass for a real amount: "" and "INF"
+    // fail to deserialize. The fields that hold them are string_or_float_opt
+    #[cfg(feature = "decimal")]
+    pub fn parse(value: &str) -> Result<Amount, String> {
+        let decimal = match value.trim() {
+            "" => return Err("empty string is not a decimal".into()),
+            "INF" | "+INF" | "-INF" => return Err(format!("{} is not a decimal", value)),
+            // Binance sends scientific notation for some tiny values, e.g. "1E-8"
+            value if value.contains(|c| c == 'e' || c == 'E') => Amount::from_scientific(value),
+            value => value.parse(),
+        };
+
+        decimal.map_err(|e| format!("{} is not a decimal: {}", value, e))
+    }
 
-    #[serde(rename = "b")] pub bids: Vec<Bids>,
+    #[cfg(not(feature = "decimal"))]
+    pub fn from_f64(value: f64) -> Result<Amount, String> {
+        Ok(value)
+    }
 
-    #[serde(rename = "a")] pub asks: Vec<Asks>
+    // A JSON number is already binary, its shortest form is what Binance wrote
+    #[cfg(feature = "decimal")]
+    pub fn from_f64(value: f64) -> Result<Amount, String> {
+        parse(&value.to_string())
+    }
 }
 
-mod string_or_float {
+// Integers Binance sends as strings, e.g. the leverage of futures positions
+mod string_or_integer {
     use std::fmt;
+    use std::str::FromStr;
 
-    use serde::{de, Serializer, Deserialize, Deserializer};
+    use serde::{Deserialize, Serializer, Deserializer};
+    use serde::de::Error;
+    use serde_json::Value;
 
     pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
         where T: fmt::Display,
@@ -465,19 +3977,222 @@ mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: FromStr,
+              T::Err: fmt::Display,
+              D: Deserializer<'de>
+    {
+        match Value::deserialize(deserializer)? {
+            Value::String(value) => value.trim().parse().map_err(D::Error::custom),
+            Value::Number(value) => value.to_string().parse().map_err(D::Error::custom),
+            other => Err(D::Error::custom(format!("expected an integer or a string holding one, got {}", other))),
+        }
+    }
+}
+
+// string_or_float of the fields Binance leaves out, sends as null or as an empty string
+mod string_or_float_opt {
+    use std::fmt;
+
+    use serde::{Deserialize, Serializer, Deserializer};
+    use serde::de::Error;
+    use serde_json::Value;
+    use super::Amount;
+
+    pub fn serialize<S>(value: &Option<Amount>, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match *value {
+            Some(ref value) => serializer.collect_str(value as &fmt::Display),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Amount>, D::Error>
         where D: Deserializer<'de>
     {
-        #[derive(Deserialize)]
-        #[serde(untagged)]
-        enum StringOrFloat {
-            String(String),
-            Float(f64),
+        match Option::<Value>::deserialize(deserializer)? {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(ref value)) if value.trim().is_empty() => Ok(None),
+            Some(Value::String(value)) => super::string_or_float::parse(&value).map(Some).map_err(D::Error::custom),
+            Some(Value::Number(value)) => match value.as_f64() {
+                Some(value) => super::string_or_float::from_f64(value).map(Some).map_err(D::Error::custom),
+                None => Ok(None),
+            },
+            Some(other) => Err(D::Error::custom(format!("expected a float or a string holding one, got {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string};
+
+    #[test]
+    fn tiny_amount_round_trip() {
+        let balance: Balance = from_str(r#"{"asset":"BTC","free":"0.00000001234","locked":"0"}"#).unwrap();
+
+        assert_eq!(balance.free, amount("0.00000001234"));
+        assert_eq!(to_string(&balance).unwrap(), r#"{"asset":"BTC","free":"0.00000001234","locked":"0"}"#);
+    }
+
+    // GET /api/v3/ticker/24hr?symbol=BCCBTC, opened at 0 after a delisting
+    static INFINITE_TICKER: &'static str = r#"{"symbol":"BCCBTC","priceChange":"0.00001000","priceChangePercent":"INF","weightedAvgPrice":"0.00001000","prevClosePrice":"0.00000000","lastPrice":"0.00001000","lastQty":"12.00000000","bidPrice":"0.00000000","bidQty":"0.00000000","askPrice":"0.00001000","askQty":"300.00000000","openPrice":"0.00000000","highPrice":"0.00001000","lowPrice":"0.00000000","volume":"12.00000000","quoteVolume":"0.00012000","openTime":1499783499040,"closeTime":1499869899040,"firstId":28385,"lastId":28385,"count":1}"#;
+
+    // GET /api/v3/depth?symbol=LTCBTC&limit=5
+    static DEPTH: &'static str = r#"{"lastUpdateId":1027024,"bids":[["4.00000000","431.00000000"],["3.99000000","9.00000000"]],"asks":[["4.00000200","12.00000000"]]}"#;
+
+    // balances of GET /api/v3/account
+    static BALANCES: &'static str = r#"[{"asset":"BTC","free":"4723846.89208129","locked":"0.00000000"},{"asset":"LTC","free":"4763368.68006011","locked":"0.00000000"}]"#;
+
+    #[cfg(not(feature = "decimal"))]
+    #[test]
+    fn infinite_ticker_round_trip() {
+        let ticker: PriceStats = from_str(INFINITE_TICKER).unwrap();
+        assert!(ticker.price_change_percent.is_infinite());
+
+        assert_eq!(from_str::<PriceStats>(&to_string(&ticker).unwrap()).unwrap(), ticker);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal_rejects_an_infinite_ticker() {
+        assert!(from_str::<PriceStats>(INFINITE_TICKER).is_err());
+    }
+
+    #[test]
+    fn empty_amounts_are_rejected() {
+        let ticker = INFINITE_TICKER.replace(r#""prevClosePrice":"0.00000000""#, r#""prevClosePrice":"""#);
+        assert!(from_str::<PriceStats>(&ticker).is_err());
+        assert!(from_str::<Vec<Balance>>(&BALANCES.replace("0.00000000", "")).is_err());
+        assert!(from_str::<OrderBook>(&DEPTH.replace("431.00000000", "")).is_err());
+    }
+
+    #[test]
+    fn depth_and_balances_round_trip() {
+        let depth: OrderBook = from_str(DEPTH).unwrap();
+        assert_eq!(depth.bids[1].qty, amount("9"));
+        assert_eq!(from_str::<OrderBook>(&to_string(&depth).unwrap()).unwrap(), depth);
+
+        let levels: Value = from_str(&to_string(&depth).unwrap()).unwrap();
+        assert_eq!(levels["bids"][1][0], Value::String(depth.bids[1].price.to_string()));
+        assert_eq!(levels["bids"][1][1], Value::String(depth.bids[1].qty.to_string()));
+        assert_eq!(levels["asks"][0].as_array().map(Vec::len), Some(2));
+
+        let balances: Vec<Balance> = from_str(BALANCES).unwrap();
+        assert_eq!(balances[1].free, amount("4763368.68006011"));
+        assert_eq!(from_str::<Vec<Balance>>(&to_string(&balances).unwrap()).unwrap(), balances);
+    }
+
+    #[test]
+    fn deposit_address_without_a_tag_round_trips() {
+        // GET /sapi/v1/capital/deposit/address?coin=BTC
+        let json = r#"{"address":"1HPn8Rx2y6nNSfagQBKy27GB99Vbzg89wv","coin":"BTC","tag":"","url":"https://btc.com/1HPn8Rx2y6nNSfagQBKy27GB99Vbzg89wv"}"#;
+        let address: DepositAddress = from_str(json).unwrap();
+
+        assert_eq!(address.tag, None);
+        assert_eq!(to_string(&address).unwrap(), json);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal_rejects_empty_and_infinite_amounts() {
+        for &free in ["", " ", "INF", "-INF"].iter() {
+            let json = format!(r#"{{"asset":"BTC","free":"{}","locked":"0"}}"#, free);
+            assert!(from_str::<Balance>(&json).is_err(), "{:?} deserialized", free);
         }
-        
-        match StringOrFloat::deserialize(deserializer)? {
-            StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-            StringOrFloat::Float(i) => Ok(i),
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal_optional_amounts_are_none_when_empty() {
+        let order: Transaction = from_str(
+            r#"{"symbol":"LTCBTC","orderId":28,"clientOrderId":"x","transactTime":1507725176595,"price":""}"#,
+        ).unwrap();
+
+        assert_eq!(order.price, None);
+    }
+
+    // GET /api/v3/klines?symbol=BNBBTC&interval=1m&limit=2
+    static KLINES: &'static str = r#"[[1672515780000,"0.01634000","0.01634800","0.01633900","0.01634700","142.99100000",1672515839999,"2.33727695",129,"73.92400000","1.20838846","0"],[1672515840000,"0.01634700","0.01635000","0.01634600","0.01634900","54.30500000",1672515899999,"0.88768831",61,"31.04600000","0.50752985","0"]]"#;
+
+    static KLINE_EVENT: &'static str = r#"{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#;
+
+    #[test]
+    fn klines_of_the_rest_api() {
+        let KlineSummaries::AllKlineSummaries(klines) = from_str(KLINES).unwrap();
+
+        assert_eq!(klines.len(), 2);
+        let kline = &klines[0];
+        assert_eq!((kline.open_time, kline.close_time, kline.number_of_trades), (1672515780000, 1672515839999, 129));
+        assert_eq!(kline.open, amount("0.01634"));
+        assert_eq!(kline.high, amount("0.016348"));
+        assert_eq!(kline.low, amount("0.016339"));
+        assert_eq!(kline.close, amount("0.016347"));
+        assert_eq!(kline.volume, amount("142.991"));
+        assert_eq!(kline.quote_asset_volume, amount("2.33727695"));
+        assert_eq!(kline.taker_buy_base_asset_volume, amount("73.924"));
+        assert_eq!(kline.taker_buy_quote_asset_volume, amount("1.20838846"));
+    }
+
+    #[test]
+    fn kline_array_too_short() {
+        assert!(from_str::<KlineSummary>(r#"[1672515780000,"0.01634000","0.01634800"]"#).is_err());
+    }
+
+    #[test]
+    fn kline_serializes_with_field_names() {
+        let KlineSummaries::AllKlineSummaries(klines) = from_str(KLINES).unwrap();
+        let json = to_string(&klines[1]).unwrap();
+
+        let named: ::serde_json::Value = from_str(&json).unwrap();
+        let fields = [
+            "open_time", "open", "high", "low", "close", "volume", "close_time", "quote_asset_volume",
+            "number_of_trades", "taker_buy_base_asset_volume", "taker_buy_quote_asset_volume",
+        ];
+        assert_eq!(named.as_object().map(|named| named.len()), Some(fields.len()));
+        for field in fields.iter() {
+            assert!(named.get(field).is_some(), "no {}", field);
         }
+        assert_eq!(named["open_time"], 1672515840000u64);
+        assert_eq!(named["number_of_trades"], 61);
+        // and reads back
+        assert_eq!(from_str::<KlineSummary>(&json).unwrap(), klines[1]);
+    }
+
+    #[test]
+    fn kline_summary_of_a_stream_kline() {
+        let event: KlineEvent = from_str(KLINE_EVENT).unwrap();
+        let kline = KlineSummary::from(&event);
+
+        assert_eq!((kline.open_time, kline.close_time, kline.number_of_trades), (1672515780000, 1672515839999, 100));
+        assert_eq!((kline.open, kline.close), (amount("0.001"), amount("0.002")));
+        assert_eq!((kline.high, kline.low), (amount("0.0025"), amount("0.0015")));
+        assert_eq!((kline.volume, kline.quote_asset_volume), (amount("1000"), amount("1")));
+        assert_eq!(kline.taker_buy_base_asset_volume, amount("500"));
+        assert_eq!(kline.taker_buy_quote_asset_volume, amount("0.5"));
+        assert_eq!(KlineSummary::from(event.kline.clone()), kline);
+        assert_eq!(event.to_kline_summary(), kline);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn utc_time_of_a_timestamp() {
+        let server_time = ServerTime { server_time: 1609459200000 };
+        let utc = server_time.server_time_utc();
+
+        // 2021-01-01T00:00:00Z
+        assert_eq!(utc.to_rfc3339(), "2021-01-01T00:00:00+00:00");
+        assert_eq!(utc.timestamp_millis(), 1609459200000);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn utc_time_of_a_signed_timestamp() {
+        let KlineSummaries::AllKlineSummaries(klines) = from_str(KLINES).unwrap();
+
+        assert_eq!(klines[0].open_time_utc().timestamp_millis(), 1672515780000);
+        assert_eq!((-1000i64).to_utc().to_rfc3339(), "1969-12-31T23:59:59+00:00");
     }
 }
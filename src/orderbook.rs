@@ -0,0 +1,224 @@
+use model::*;
+use websockets::*;
+use std::cmp::Ordering;
+
+// Local order book kept in sync from a REST depth snapshot plus diff depth events,
+// following https://github.com/binance-exchange/binance-official-api-docs/blob/master/web-socket-streams.md
+pub struct OrderBookManager {
+    pub symbol: String,
+    last_update_id: Option<u64>,
+    needs_snapshot: bool,
+    buffer: Vec<DepthOrderBookEvent>,
+    bids: Vec<Bids>,
+    asks: Vec<Asks>,
+}
+
+impl OrderBookManager {
+    pub fn new<S>(symbol: S) -> OrderBookManager
+        where S: Into<String>
+    {
+        OrderBookManager {
+            symbol: symbol.into(),
+            last_update_id: None,
+            needs_snapshot: true,
+            buffer: Vec::new(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+        }
+    }
+
+    // True until a snapshot has been applied, and again after a sequence gap
+    pub fn needs_snapshot(&self) -> bool {
+        self.needs_snapshot
+    }
+
+    pub fn last_update_id(&self) -> Option<u64> {
+        self.last_update_id
+    }
+
+    // Replaces the book with a REST snapshot and replays the buffered events on top of it
+    pub fn apply_snapshot(&mut self, snapshot: &OrderBook) {
+        self.last_update_id = Some(snapshot.last_update_id);
+        self.needs_snapshot = false;
+
+        self.bids = snapshot.bids.iter().filter(|b| b.qty != ZERO).cloned().collect();
+        self.bids.sort_by(|a, b| cmp_price(b.price, a.price));
+        self.asks = snapshot.asks.iter().filter(|a| a.qty != ZERO).cloned().collect();
+        self.asks.sort_by(|a, b| cmp_price(a.price, b.price));
+
+        let buffered: Vec<DepthOrderBookEvent> = self.buffer.drain(..).collect();
+        for event in &buffered {
+            self.update(event);
+        }
+    }
+
+    // Applies a diff depth event, events are buffered while waiting for a snapshot
+    pub fn update(&mut self, event: &DepthOrderBookEvent) {
+        if event.symbol != self.symbol {
+            return;
+        }
+
+        let last_update_id = match self.last_update_id {
+            Some(id) if !self.needs_snapshot => id,
+            _ => {
+                self.buffer.push(event.clone());
+                return;
+            }
+        };
+
+        // Already contained in the snapshot
+        if event.final_update_id <= last_update_id {
+            return;
+        }
+
+        // The first event after the snapshot may straddle lastUpdateId, every later one must follow on
+        if event.first_update_id > last_update_id + 1 {
+            self.reset();
+            self.buffer.push(event.clone());
+            return;
+        }
+
+        for bid in &event.bids {
+            match self.bids.binary_search_by(|b| cmp_price(bid.price, b.price)) {
+                Ok(i) if bid.qty == ZERO => { self.bids.remove(i); }
+                Ok(i) => self.bids[i] = bid.clone(),
+                Err(i) => if bid.qty != ZERO { self.bids.insert(i, bid.clone()) },
+            }
+        }
+        for ask in &event.asks {
+            match self.asks.binary_search_by(|a| cmp_price(a.price, ask.price)) {
+                Ok(i) if ask.qty == ZERO => { self.asks.remove(i); }
+                Ok(i) => self.asks[i] = ask.clone(),
+                Err(i) => if ask.qty != ZERO { self.asks.insert(i, ask.clone()) },
+            }
+        }
+
+        self.last_update_id = Some(event.final_update_id);
+    }
+
+    pub fn best_bid(&self) -> Option<&Bids> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&Asks> {
+        self.asks.first()
+    }
+
+    // Top n levels per side, best price first
+    pub fn levels(&self, n: usize) -> OrderBook {
+        OrderBook {
+            last_update_id: self.last_update_id.unwrap_or(0),
+            bids: self.bids.iter().take(n).cloned().collect(),
+            asks: self.asks.iter().take(n).cloned().collect(),
+            levels: None,
+        }
+    }
+
+    // Drops the book so it cannot be read while corrupt
+    fn reset(&mut self) {
+        self.needs_snapshot = true;
+        self.last_update_id = None;
+        self.buffer.clear();
+        self.bids.clear();
+        self.asks.clear();
+    }
+}
+
+impl MarketEventHandler for OrderBookManager {
+    fn aggregated_trades_handler(&mut self, _event: &TradesEvent) {}
+
+    fn depth_orderbook_handler(&mut self, event: &DepthOrderBookEvent) {
+        self.update(event);
+    }
+
+    fn partial_orderbook_handler(&mut self, _order_book: &OrderBook) {}
+}
+
+fn cmp_price(a: Amount, b: Amount) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+
+    fn snapshot(last_update_id: u64) -> OrderBook {
+        let bids = r#"[["0.0024","10"],["0.0022","5"]]"#;
+        let asks = r#"[["0.0026","100"],["0.0027","20"]]"#;
+
+        from_str(&format!(r#"{{"lastUpdateId":{},"bids":{},"asks":{}}}"#, last_update_id, bids, asks)).unwrap()
+    }
+
+    fn depth(first_update_id: u64, final_update_id: u64, bids: &str, asks: &str) -> DepthOrderBookEvent {
+        from_str(&format!(
+            r#"{{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":{},"u":{},"b":{},"a":{}}}"#,
+            first_update_id, final_update_id, bids, asks
+        )).unwrap()
+    }
+
+    fn prices(book: &OrderBook) -> (Vec<Amount>, Vec<Amount>) {
+        (
+            book.bids.iter().map(|bid| bid.price).collect(),
+            book.asks.iter().map(|ask| ask.price).collect(),
+        )
+    }
+
+    #[test]
+    fn buffered_events_are_replayed_on_the_snapshot() {
+        let mut book = OrderBookManager::new("BNBBTC");
+        // Contained in the snapshot, then straddling it
+        book.update(&depth(150, 155, r#"[["0.0024","1"]]"#, "[]"));
+        book.update(&depth(156, 162, r#"[["0.0023","3"]]"#, "[]"));
+        assert!(book.needs_snapshot());
+
+        book.apply_snapshot(&snapshot(160));
+        assert!(!book.needs_snapshot());
+        assert_eq!(book.last_update_id(), Some(162));
+        assert_eq!(book.best_bid().map(|bid| bid.qty), Some(amount("10")));
+        assert_eq!(prices(&book.levels(5)).0, vec![amount("0.0024"), amount("0.0023"), amount("0.0022")]);
+    }
+
+    #[test]
+    fn zero_quantity_removes_the_level() {
+        let mut book = OrderBookManager::new("BNBBTC");
+        book.apply_snapshot(&snapshot(160));
+
+        book.update(&depth(161, 161, r#"[["0.0024","0.00000000"]]"#, r#"[["0.0026","0"],["0.0025","4"]]"#));
+        assert_eq!(book.best_bid().map(|bid| bid.price), Some(amount("0.0022")));
+        assert_eq!(book.best_ask().map(|ask| (ask.price, ask.qty)), Some((amount("0.0025"), amount("4"))));
+        assert_eq!(prices(&book.levels(5)).1, vec![amount("0.0025"), amount("0.0027")]);
+
+        // Removing a level that is not in the book leaves it unchanged
+        book.update(&depth(162, 162, r#"[["0.0021","0"]]"#, "[]"));
+        assert_eq!(book.levels(5).bids.len(), 1);
+    }
+
+    #[test]
+    fn gap_in_the_sequence_needs_a_new_snapshot() {
+        let mut book = OrderBookManager::new("BNBBTC");
+        book.apply_snapshot(&snapshot(160));
+        book.update(&depth(161, 163, r#"[["0.0023","3"]]"#, "[]"));
+
+        // 164 is missing
+        book.update(&depth(165, 170, r#"[["0.0020","1"]]"#, "[]"));
+        assert!(book.needs_snapshot());
+        assert!(book.best_bid().is_none());
+        assert_eq!(book.last_update_id(), None);
+
+        book.apply_snapshot(&snapshot(166));
+        assert!(!book.needs_snapshot());
+        assert_eq!(book.last_update_id(), Some(170));
+        assert_eq!(prices(&book.levels(5)).0, vec![amount("0.0024"), amount("0.0022"), amount("0.0020")]);
+    }
+
+    #[test]
+    fn events_of_other_symbols_are_ignored() {
+        let mut book = OrderBookManager::new("ETHBTC");
+        book.apply_snapshot(&snapshot(160));
+
+        book.update(&depth(161, 161, r#"[["0.0024","0"]]"#, "[]"));
+        assert_eq!(book.last_update_id(), Some(160));
+        assert_eq!(book.best_bid().map(|bid| bid.price), Some(amount("0.0024")));
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// What a full queue does with a new event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    // The websocket reader waits for the receiver, the server may disconnect a slow client
+    Block,
+    // The oldest queued event is discarded, see EventReceiver::dropped
+    DropOldest,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    dropped: u64,
+    // Live EventSender clones, the last one to go sets sender_gone
+    senders: usize,
+    sender_gone: bool,
+    receiver_gone: bool,
+    stopped: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl<T> Shared<T> {
+    fn lock<'a>(&'a self) -> MutexGuard<'a, State<T>> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+// Bounded queue between a websocket reader thread and its consumer
+pub(crate) fn bounded<T>(capacity: usize, overflow: Overflow) -> (EventSender<T>, EventReceiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            items: VecDeque::with_capacity(capacity),
+            dropped: 0,
+            senders: 1,
+            sender_gone: false,
+            receiver_gone: false,
+            stopped: false,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity: if capacity == 0 { 1 } else { capacity },
+        overflow,
+    });
+
+    (EventSender { shared: shared.clone() }, EventReceiver { shared })
+}
+
+pub(crate) struct EventSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> EventSender<T> {
+    // Returns false once the receiver is gone or the queue was stopped
+    pub fn send(&self, item: T) -> bool {
+        let mut state = self.shared.lock();
+
+        while state.items.len() >= self.shared.capacity {
+            if state.receiver_gone || state.stopped {
+                return false;
+            }
+            match self.shared.overflow {
+                Overflow::Block => {
+                    state = match self.shared.not_full.wait(state) {
+                        Ok(guard) => guard,
+                        Err(poisoned) => poisoned.into_inner(),
+                    };
+                }
+                Overflow::DropOldest => {
+                    state.items.pop_front();
+                    state.dropped += 1;
+                }
+            }
+        }
+        if state.receiver_gone || state.stopped {
+            return false;
+        }
+
+        state.items.push_back(item);
+        self.shared.not_empty.notify_one();
+        true
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.shared.lock().stopped
+    }
+
+    pub fn stopper(&self) -> QueueStopper<T> {
+        QueueStopper { shared: self.shared.clone() }
+    }
+}
+
+// Several readers can feed one receiver, e.g. the connections of a WebSocketsPool
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> EventSender<T> {
+        self.shared.lock().senders += 1;
+
+        EventSender { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for EventSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            state.sender_gone = true;
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+// Wakes up a sender blocked on a full queue so its thread can shut down
+pub(crate) struct QueueStopper<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> QueueStopper<T> {
+    pub fn stop(&self) {
+        self.shared.lock().stopped = true;
+        self.shared.not_full.notify_all();
+    }
+}
+
+// Receiving end of a websocket event loop running on another thread.
+// Queued events are still delivered after the connection is gone, then recv returns None
+pub struct EventReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> EventReceiver<T> {
+    // Blocks until an event arrives, None once the event loop has ended
+    pub fn recv(&self) -> Option<T> {
+        let mut state = self.shared.lock();
+
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.shared.not_full.notify_one();
+                return Some(item);
+            }
+            if state.sender_gone {
+                return None;
+            }
+            state = match self.shared.not_empty.wait(state) {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+        }
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.lock();
+
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.shared.not_full.notify_one();
+                return Some(item);
+            }
+            let now = Instant::now();
+            if state.sender_gone || now >= deadline {
+                return None;
+            }
+            state = match self.shared.not_empty.wait_timeout(state, deadline - now) {
+                Ok((guard, _)) => guard,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+    }
+
+    pub fn try_recv(&self) -> Option<T> {
+        let mut state = self.shared.lock();
+        let item = state.items.pop_front();
+        if item.is_some() {
+            self.shared.not_full.notify_one();
+        }
+
+        item
+    }
+
+    // False once the event loop has ended and every queued event was received
+    pub fn is_connected(&self) -> bool {
+        let state = self.shared.lock();
+
+        !state.sender_gone || !state.items.is_empty()
+    }
+
+    // Number of events discarded by Overflow::DropOldest
+    pub fn dropped(&self) -> u64 {
+        self.shared.lock().dropped
+    }
+}
+
+impl<T> Iterator for EventReceiver<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.recv()
+    }
+}
+
+impl<T> Drop for EventReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receiver_gone = true;
+        self.shared.not_full.notify_all();
+    }
+}
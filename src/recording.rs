@@ -0,0 +1,130 @@
+use errors::*;
+use util::get_timestamp;
+use websockets::WebSockets;
+use serde_json::to_string;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// When a recording moves on to a new file. The finished file is renamed to
+// "<path>.<unix time in ms>" and recording continues in path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Never,
+    // Size in bytes
+    Size(u64),
+    Interval(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplaySpeed {
+    AsFastAsPossible,
+    // Sleeps between messages as long as the gaps between their receive times
+    Recorded,
+}
+
+// One line of a recording: {"t":<receive time in ms>,"msg":"<text frame>"}
+#[derive(Serialize)]
+struct RecordedLine<'a> {
+    t: u64,
+    msg: &'a str,
+}
+
+#[derive(Deserialize)]
+struct RecordedMessage {
+    t: u64,
+    msg: String,
+}
+
+// Appends received text frames to a newline delimited JSON file
+pub(crate) struct Recorder {
+    path: PathBuf,
+    rotation: Rotation,
+    file: BufWriter<File>,
+    written: u64,
+    opened_at: Instant,
+}
+
+impl Recorder {
+    pub fn open(path: &Path, rotation: Rotation) -> Result<Recorder> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Recorder {
+            path: path.to_path_buf(),
+            rotation,
+            file: BufWriter::new(file),
+            written,
+            opened_at: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, msg: &str) -> Result<()> {
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+
+        let mut line = to_string(&RecordedLine { t: get_timestamp()?, msg })?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        // Flushed per message so a crash loses nothing that was already received
+        self.file.flush()?;
+        self.written += line.len() as u64;
+
+        Ok(())
+    }
+
+    fn should_rotate(&self) -> bool {
+        match self.rotation {
+            Rotation::Never => false,
+            Rotation::Size(size) => self.written > 0 && self.written >= size,
+            Rotation::Interval(interval) => self.opened_at.elapsed() >= interval,
+        }
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+
+        let mut finished = self.path.clone().into_os_string();
+        finished.push(format!(".{}", get_timestamp()?));
+        fs::rename(&self.path, &finished)?;
+
+        let rotation = self.rotation;
+        *self = Recorder::open(&self.path.clone(), rotation)?;
+
+        Ok(())
+    }
+}
+
+// Feeds a recording made with WebSockets::record_to through the handlers of web_socket,
+// returns the number of replayed messages. web_socket does not need to be connected
+pub fn replay_from<P>(path: P, web_socket: &mut WebSockets, speed: ReplaySpeed) -> Result<u64>
+where
+    P: AsRef<Path>,
+{
+    let reader = BufReader::new(File::open(path)?);
+    let mut previous: Option<u64> = None;
+    let mut replayed = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let recorded: RecordedMessage = from_json(&line)?;
+
+        if let (ReplaySpeed::Recorded, Some(previous)) = (speed, previous) {
+            if recorded.t > previous {
+                thread::sleep(Duration::from_millis(recorded.t - previous));
+            }
+        }
+        previous = Some(recorded.t);
+
+        web_socket.process_message(&recorded.msg)?;
+        replayed += 1;
+    }
+
+    Ok(replayed)
+}
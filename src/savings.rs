@@ -0,0 +1,158 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::BTreeMap;
+
+static SAPI_V1_FLEXIBLE_LIST: &'static str = "/sapi/v1/simple-earn/flexible/list";
+static SAPI_V1_FLEXIBLE_SUBSCRIBE: &'static str = "/sapi/v1/simple-earn/flexible/subscribe";
+static SAPI_V1_FLEXIBLE_REDEEM: &'static str = "/sapi/v1/simple-earn/flexible/redeem";
+static SAPI_V1_FLEXIBLE_POSITION: &'static str = "/sapi/v1/simple-earn/flexible/position";
+
+// Flexible Simple Earn (savings) endpoints, subscribed assets can be redeemed at any time
+#[derive(Clone)]
+pub struct Savings {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl Savings {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Products of one asset or of all of them, current is the page starting at 1, size at most 100
+    pub fn get_flexible_products(
+        &self, asset: Option<&str>, current: Option<u32>, size: Option<u32>,
+    ) -> Result<(FlexibleProducts)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_FLEXIBLE_LIST, &request)?;
+        let products: FlexibleProducts = from_json(&data)?;
+
+        Ok(products)
+    }
+
+    // Subscribes amount of the product's asset from the spot wallet
+    pub fn subscribe_flexible_product<S>(&self, product_id: S, amount: Amount) -> Result<(FlexibleSubscription)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("productId".into(), product_id.into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_FLEXIBLE_SUBSCRIBE, &request)?;
+        let subscription: FlexibleSubscription = from_json(&data)?;
+
+        Ok(subscription)
+    }
+
+    // Redeems amount back to the spot wallet, everything when None. Redemptions are
+    // credited at once, there is no fast / normal distinction anymore
+    pub fn redeem_flexible_product<S>(&self, product_id: S, amount: Option<Amount>) -> Result<(FlexibleRedemption)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("productId".into(), product_id.into());
+        match amount {
+            Some(amount) => {
+                parameters.insert("amount".into(), amount.to_string());
+            }
+            None => {
+                parameters.insert("redeemAll".into(), "true".into());
+            }
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_FLEXIBLE_REDEEM, &request)?;
+        let redemption: FlexibleRedemption = from_json(&data)?;
+
+        Ok(redemption)
+    }
+
+    // Our flexible positions, of one asset or of all of them
+    pub fn get_flexible_positions(
+        &self, asset: Option<&str>, current: Option<u32>, size: Option<u32>,
+    ) -> Result<(FlexiblePositions)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_FLEXIBLE_POSITION, &request)?;
+        let positions: FlexiblePositions = from_json(&data)?;
+
+        Ok(positions)
+    }
+}
+
+pub(crate) fn insert_page(parameters: &mut BTreeMap<String, String>, current: Option<u32>, size: Option<u32>) {
+    if let Some(current) = current {
+        parameters.insert("current".into(), current.to_string());
+    }
+    if let Some(size) = size {
+        parameters.insert("size".into(), size.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Binance, Config};
+    use client::fake::{answer, client, FakeTransport};
+    use std::sync::Arc;
+
+    // GET /sapi/v1/simple-earn/flexible/list
+    static FLEXIBLE_PRODUCTS: &'static str = r#"{"rows":[{"asset":"BTC","latestAnnualPercentageRate":"0.05000000","tierAnnualPercentageRate":{"0-5BTC":0.05,"5-10BTC":0.03},"airDropPercentageRate":"0.05000000","canPurchase":true,"canRedeem":true,"isSoldOut":true,"hot":true,"minPurchaseAmount":"0.01000000","productId":"BTC001","subscriptionStartTime":1646182276000,"status":"PURCHASING"}],"total":1}"#;
+
+    // GET /sapi/v1/simple-earn/flexible/position
+    static FLEXIBLE_POSITIONS: &'static str = r#"{"rows":[{"totalAmount":"75.46000000","tierAnnualPercentageRate":{"0-5BTC":0.05,"5-10BTC":0.03},"latestAnnualPercentageRate":"0.02599895","yesterdayAirdropPercentageRate":"0.02599895","asset":"USDT","airDropAsset":"BETH","canRedeem":true,"collateralAmount":"232.23123213","productId":"USDT001","yesterdayRealTimeRewards":"0.10293829","cumulativeBonusRewards":"0.22759183","cumulativeRealTimeRewards":"0.22759183","cumulativeTotalRewards":"0.45459183","autoSubscribe":true}],"total":1}"#;
+
+    fn savings(transport: &Arc<FakeTransport>) -> Savings {
+        Savings::new_with_client(client(transport), &Config::default())
+    }
+
+    #[test]
+    fn get_flexible_products() {
+        let transport = FakeTransport::new(vec![answer(200, FLEXIBLE_PRODUCTS)]);
+
+        let products = savings(&transport).get_flexible_products(Some("BTC"), Some(1), Some(10)).unwrap();
+        assert_eq!(products.total, 1);
+        let product = &products.rows[0];
+        assert_eq!(product.asset, "BTC");
+        assert_eq!(product.product_id, "BTC001");
+        assert_eq!(product.latest_annual_percentage_rate, amount("0.05"));
+        assert!(product.can_purchase && product.can_redeem && product.is_sold_out);
+        assert_eq!(product.min_purchase_amount, amount("0.01"));
+        assert_eq!(product.status, "PURCHASING");
+        let url = &transport.requests()[0].1;
+        assert!(url.starts_with("https://api.binance.com/sapi/v1/simple-earn/flexible/list?asset=BTC&current=1&"));
+        assert!(url.contains("&size=10&"));
+    }
+
+    #[test]
+    fn get_flexible_positions() {
+        let transport = FakeTransport::new(vec![answer(200, FLEXIBLE_POSITIONS)]);
+
+        let positions = savings(&transport).get_flexible_positions(None, None, None).unwrap();
+        assert_eq!(positions.total, 1);
+        let position = &positions.rows[0];
+        assert_eq!(position.asset, "USDT");
+        assert_eq!(position.product_id, "USDT001");
+        assert_eq!(position.total_amount, amount("75.46"));
+        assert_eq!(position.latest_annual_percentage_rate, amount("0.02599895"));
+        assert_eq!(position.yesterday_real_time_rewards, amount("0.10293829"));
+        assert_eq!(position.cumulative_total_rewards, amount("0.45459183"));
+        assert!(position.can_redeem && position.auto_subscribe);
+        let url = &transport.requests()[0].1;
+        assert!(url.starts_with("https://api.binance.com/sapi/v1/simple-earn/flexible/position?"));
+    }
+}
@@ -0,0 +1,180 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use savings::insert_page;
+use std::collections::BTreeMap;
+
+static SAPI_V1_LOCKED_LIST: &'static str = "/sapi/v1/simple-earn/locked/list";
+static SAPI_V1_LOCKED_SUBSCRIBE: &'static str = "/sapi/v1/simple-earn/locked/subscribe";
+static SAPI_V1_LOCKED_REDEEM: &'static str = "/sapi/v1/simple-earn/locked/redeem";
+static SAPI_V1_LOCKED_POSITION: &'static str = "/sapi/v1/simple-earn/locked/position";
+static SAPI_V1_LOCKED_REWARDS: &'static str = "/sapi/v1/simple-earn/locked/history/rewardsRecord";
+
+static SAPI_V2_ETH_STAKE: &'static str = "/sapi/v2/eth-staking/eth/stake";
+static SAPI_V1_ETH_REDEEM: &'static str = "/sapi/v1/eth-staking/eth/redeem";
+static SAPI_V1_ETH_RATE_HISTORY: &'static str = "/sapi/v1/eth-staking/eth/history/rateHistory";
+static SAPI_V2_ETH_ACCOUNT: &'static str = "/sapi/v2/eth-staking/account";
+
+// Locked Simple Earn (locked staking) and ETH staking endpoints. A product sold out since it was listed
+// fails the subscription with BinanceErrorCode::ProductSoldOut, a position still in its lock period the
+// redemption with BinanceErrorCode::RedemptionLocked
+#[derive(Clone)]
+pub struct Staking {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl Staking {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Products of one asset or of all of them, current is the page starting at 1, size at most 100
+    pub fn get_locked_products(
+        &self, asset: Option<&str>, current: Option<u32>, size: Option<u32>,
+    ) -> Result<(LockedProducts)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_LOCKED_LIST, &request)?;
+        let products: LockedProducts = from_json(&data)?;
+
+        Ok(products)
+    }
+
+    // Locks amount of the product's asset from the spot wallet for its duration, auto_subscribe
+    // locks it again once it is delivered
+    pub fn subscribe_locked_product<S>(
+        &self, project_id: S, amount: Amount, auto_subscribe: bool,
+    ) -> Result<(LockedSubscription)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("projectId".into(), project_id.into());
+        parameters.insert("amount".into(), amount.to_string());
+        parameters.insert("autoSubscribe".into(), auto_subscribe.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_LOCKED_SUBSCRIBE, &request)?;
+        let subscription: LockedSubscription = from_json(&data)?;
+
+        Ok(subscription)
+    }
+
+    // Redeems a position before its end, when its product allows it (LockedPosition::can_redeem_early).
+    // The principal is credited redeem_period days later, without the rewards
+    pub fn redeem_locked_position(&self, position_id: u64) -> Result<(LockedRedemption)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("positionId".into(), position_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_LOCKED_REDEEM, &request)?;
+        let redemption: LockedRedemption = from_json(&data)?;
+
+        Ok(redemption)
+    }
+
+    // Our locked positions, of one asset or of all of them
+    pub fn get_locked_positions(
+        &self, asset: Option<&str>, position_id: Option<u64>, current: Option<u32>, size: Option<u32>,
+    ) -> Result<(LockedPositions)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+        if let Some(position_id) = position_id {
+            parameters.insert("positionId".into(), position_id.to_string());
+        }
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_LOCKED_POSITION, &request)?;
+        let positions: LockedPositions = from_json(&data)?;
+
+        Ok(positions)
+    }
+
+    // Rewards paid to the locked positions, the last 3 months when no time range is given
+    pub fn get_locked_rewards(
+        &self, asset: Option<&str>, position_id: Option<u64>, start_time: Option<u64>, end_time: Option<u64>,
+        current: Option<u32>, size: Option<u32>,
+    ) -> Result<(LockedRewards)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+        if let Some(position_id) = position_id {
+            parameters.insert("positionId".into(), position_id.to_string());
+        }
+        insert_time_range(&mut parameters, start_time, end_time);
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_LOCKED_REWARDS, &request)?;
+        let rewards: LockedRewards = from_json(&data)?;
+
+        Ok(rewards)
+    }
+
+    // Stakes amount ETH of the spot wallet, received as WBETH
+    pub fn stake_eth(&self, amount: Amount) -> Result<(EthStake)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("amount".into(), amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V2_ETH_STAKE, &request)?;
+        let stake: EthStake = from_json(&data)?;
+
+        Ok(stake)
+    }
+
+    // Redeems amount WBETH back to ETH, credited at arrival_time
+    pub fn redeem_eth(&self, amount: Amount) -> Result<(EthRedemption)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("asset".into(), "WBETH".into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_ETH_REDEEM, &request)?;
+        let redemption: EthRedemption = from_json(&data)?;
+
+        Ok(redemption)
+    }
+
+    // Daily APR and WBETH to ETH exchange rate, the last 30 days when no time range is given
+    pub fn get_eth_staking_rates(
+        &self, start_time: Option<u64>, end_time: Option<u64>, current: Option<u32>, size: Option<u32>,
+    ) -> Result<(EthStakingRates)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        insert_time_range(&mut parameters, start_time, end_time);
+        insert_page(&mut parameters, current, size);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ETH_RATE_HISTORY, &request)?;
+        let rates: EthStakingRates = from_json(&data)?;
+
+        Ok(rates)
+    }
+
+    pub fn get_eth_staking_account(&self) -> Result<(EthStakingAccount)> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V2_ETH_ACCOUNT, &request)?;
+        let account: EthStakingAccount = from_json(&data)?;
+
+        Ok(account)
+    }
+}
+
+fn insert_time_range(parameters: &mut BTreeMap<String, String>, start_time: Option<u64>, end_time: Option<u64>) {
+    if let Some(start_time) = start_time {
+        parameters.insert("startTime".into(), start_time.to_string());
+    }
+    if let Some(end_time) = end_time {
+        parameters.insert("endTime".into(), end_time.to_string());
+    }
+}
@@ -0,0 +1,106 @@
+use errors::*;
+use model::KlineInterval;
+
+// Stream names for WebSockets::connect and connect_multiple_streams, e.g.
+// streams::kline("BTCUSDT", KlineInterval::Minutes1)? gives "btcusdt@kline_1m". Symbols are a &str,
+// a String or a SymbolName in any case
+
+static PARTIAL_DEPTH_LEVELS: &'static [u16] = &[5, 10, 20];
+
+pub fn agg_trade<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "aggTrade")
+}
+
+pub fn trade<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "trade")
+}
+
+// interval is a KlineInterval or its token, e.g. "15m"
+pub fn kline<S, I>(symbol: S, interval: I) -> Result<String>
+where
+    S: AsRef<str>,
+    I: Into<KlineInterval>,
+{
+    let interval = interval.into();
+    if let KlineInterval::Other(ref interval) = interval {
+        bail!(BinanceError::Parameter(format!("Unsupported kline interval {:?}", interval)));
+    }
+
+    symbol_stream(symbol.as_ref(), &format!("kline_{}", interval))
+}
+
+// Diff depth stream, see OrderBookManager
+pub fn depth<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "depth")
+}
+
+// Top <levels> bids and asks, levels is 5, 10 or 20
+pub fn partial_depth<S>(symbol: S, levels: u16) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    if !PARTIAL_DEPTH_LEVELS.contains(&levels) {
+        bail!(BinanceError::Parameter(format!("Unsupported depth level {}, use 5, 10 or 20", levels)));
+    }
+
+    symbol_stream(symbol.as_ref(), &format!("depth{}", levels))
+}
+
+pub fn ticker<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "ticker")
+}
+
+pub fn ticker_all() -> String {
+    "!ticker@arr".into()
+}
+
+pub fn mini_ticker<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "miniTicker")
+}
+
+pub fn mini_ticker_all() -> String {
+    "!miniTicker@arr".into()
+}
+
+pub fn book_ticker<S>(symbol: S) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    symbol_stream(symbol.as_ref(), "bookTicker")
+}
+
+// The user data stream is named after the listen key of UserStream::start
+pub fn user(listen_key: &str) -> Result<String> {
+    if listen_key.trim().is_empty() {
+        bail!(BinanceError::Parameter("Empty listen key".into()));
+    }
+
+    Ok(listen_key.into())
+}
+
+fn symbol_stream(symbol: &str, stream: &str) -> Result<String> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        bail!(BinanceError::Parameter("Empty symbol".into()));
+    }
+    if !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+        bail!(BinanceError::Parameter(format!("Invalid symbol {:?}", symbol)));
+    }
+
+    Ok(format!("{}@{}", symbol.to_lowercase(), stream))
+}
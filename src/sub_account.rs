@@ -0,0 +1,144 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::BTreeMap;
+
+static SAPI_V1_SUB_ACCOUNT_LIST: &'static str = "/sapi/v1/sub-account/list";
+static SAPI_V3_SUB_ACCOUNT_ASSETS: &'static str = "/sapi/v3/sub-account/assets";
+static SAPI_V1_SUB_ACCOUNT_TRANSFER: &'static str = "/sapi/v1/sub-account/universalTransfer";
+
+// Sub-account endpoints, called with the keys of the master account. Sub-accounts are
+// identified by their email everywhere
+#[derive(Clone)]
+pub struct SubAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+// Filters of get_sub_accounts
+#[derive(Debug, Clone, Default)]
+pub struct SubAccountListOptions {
+    pub email: Option<String>,
+    pub is_freeze: Option<bool>,
+    // Page, starting at 1
+    pub page: Option<u32>,
+    // At most 200
+    pub limit: Option<u32>,
+}
+
+// Filters of get_sub_account_transfer_history, at most 30 days between start_time and end_time
+#[derive(Debug, Clone, Default)]
+pub struct SubAccountTransferHistoryOptions {
+    pub from_email: Option<String>,
+    pub to_email: Option<String>,
+    pub client_tran_id: Option<String>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // Page, starting at 1
+    pub page: Option<u32>,
+    // At most 500
+    pub limit: Option<u32>,
+}
+
+impl SubAccount {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    pub fn get_sub_accounts(&self, options: SubAccountListOptions) -> Result<(Vec<SubAccountInfo>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(email) = options.email {
+            parameters.insert("email".into(), email);
+        }
+        if let Some(is_freeze) = options.is_freeze {
+            parameters.insert("isFreeze".into(), is_freeze.to_string());
+        }
+        if let Some(page) = options.page {
+            parameters.insert("page".into(), page.to_string());
+        }
+        if let Some(limit) = options.limit {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_SUB_ACCOUNT_LIST, &request)?;
+        let sub_accounts: SubAccounts = from_json(&data)?;
+
+        Ok(sub_accounts.sub_accounts)
+    }
+
+    // Spot balances of a sub-account
+    pub fn get_sub_account_assets<S>(&self, email: S) -> Result<(Vec<SubAccountBalance>)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("email".into(), email.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V3_SUB_ACCOUNT_ASSETS, &request)?;
+        let assets: SubAccountAssets = from_json(&data)?;
+
+        Ok(assets.balances)
+    }
+
+    // Moves amount of asset between the wallets of the master and its sub-accounts,
+    // a None email is the master account
+    pub fn sub_account_transfer<S>(
+        &self, from_email: Option<&str>, to_email: Option<&str>, from_account_type: SubAccountType,
+        to_account_type: SubAccountType, asset: S, amount: Amount,
+    ) -> Result<(TransactionId)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(from_email) = from_email {
+            parameters.insert("fromEmail".into(), from_email.into());
+        }
+        if let Some(to_email) = to_email {
+            parameters.insert("toEmail".into(), to_email.into());
+        }
+        parameters.insert("fromAccountType".into(), from_account_type.as_str().into());
+        parameters.insert("toAccountType".into(), to_account_type.as_str().into());
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_SUB_ACCOUNT_TRANSFER, &request)?;
+        let transaction_id: TransactionId = from_json(&data)?;
+
+        Ok(transaction_id)
+    }
+
+    pub fn get_sub_account_transfer_history(
+        &self, options: SubAccountTransferHistoryOptions,
+    ) -> Result<(SubAccountTransferHistory)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(from_email) = options.from_email {
+            parameters.insert("fromEmail".into(), from_email);
+        }
+        if let Some(to_email) = options.to_email {
+            parameters.insert("toEmail".into(), to_email);
+        }
+        if let Some(client_tran_id) = options.client_tran_id {
+            parameters.insert("clientTranId".into(), client_tran_id);
+        }
+        if let Some(start_time) = options.start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = options.end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(page) = options.page {
+            parameters.insert("page".into(), page.to_string());
+        }
+        if let Some(limit) = options.limit {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_SUB_ACCOUNT_TRANSFER, &request)?;
+        let history: SubAccountTransferHistory = from_json(&data)?;
+
+        Ok(history)
+    }
+}
@@ -0,0 +1,445 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::Duration;
+
+static FAPI_V2_ACCOUNT: &'static str = "/fapi/v2/account";
+static FAPI_V2_POSITION_RISK: &'static str = "/fapi/v2/positionRisk";
+static FAPI_V1_LEVERAGE: &'static str = "/fapi/v1/leverage";
+static FAPI_V1_MARGIN_TYPE: &'static str = "/fapi/v1/marginType";
+static FAPI_V1_ORDER: &'static str = "/fapi/v1/order";
+static FAPI_V1_OPEN_ORDERS: &'static str = "/fapi/v1/openOrders";
+static FAPI_V1_LISTEN_KEY: &'static str = "/fapi/v1/listenKey";
+static FAPI_V1_FUNDING_RATE: &'static str = "/fapi/v1/fundingRate";
+static FAPI_V1_PREMIUM_INDEX: &'static str = "/fapi/v1/premiumIndex";
+static FAPI_V1_OPEN_INTEREST: &'static str = "/fapi/v1/openInterest";
+static FUTURES_DATA_OPEN_INTEREST_HIST: &'static str = "/futures/data/openInterestHist";
+
+static FUNDING_RATE_MAX_LIMIT: u16 = 1000;
+static OPEN_INTEREST_HIST_MAX_LIMIT: u16 = 500;
+
+// USD-M futures endpoints, on fapi.binance.com (see Config::futures_api_endpoint). Signed like
+// the spot ones
+#[derive(Clone)]
+pub struct FuturesAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+// Public USD-M futures market data, on the same host as FuturesAccount and unsigned
+#[derive(Clone)]
+pub struct FuturesMarket {
+    pub client: Client,
+}
+
+// Margin of a futures position, shared by the positions of a symbol in isolated mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuturesMarginType {
+    Isolated,
+    Crossed,
+}
+
+impl FuturesMarginType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            FuturesMarginType::Isolated => "ISOLATED",
+            FuturesMarginType::Crossed => "CROSSED",
+        }
+    }
+}
+
+// Parameters of a futures order, USD-M or COIN-M (qty in contracts). position_side is required in
+// hedge mode and must be None (BOTH) in one-way mode, reduce_only is for one-way mode only
+#[derive(Debug, Clone)]
+pub struct FuturesOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub position_side: Option<PositionSide>,
+    pub order_type: FuturesOrderType,
+    // None with close_position
+    pub qty: Option<Amount>,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
+    pub time_in_force: Option<TimeInForce>,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    pub working_type: Option<WorkingType>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl FuturesOrderRequest {
+    pub fn limit<S>(symbol: S, side: OrderSide, qty: Amount, price: Amount) -> FuturesOrderRequest
+        where S: Into<String>
+    {
+        FuturesOrderRequest {
+            price: Some(price),
+            time_in_force: Some(TimeInForce::GoodTillCanceled),
+            ..FuturesOrderRequest::new(symbol, side, FuturesOrderType::Limit, Some(qty))
+        }
+    }
+
+    pub fn market<S>(symbol: S, side: OrderSide, qty: Amount) -> FuturesOrderRequest
+        where S: Into<String>
+    {
+        FuturesOrderRequest::new(symbol, side, FuturesOrderType::Market, Some(qty))
+    }
+
+    // Closes the whole position at the market once stop_price is reached
+    pub fn stop_market_close<S>(symbol: S, side: OrderSide, stop_price: Amount) -> FuturesOrderRequest
+        where S: Into<String>
+    {
+        FuturesOrderRequest {
+            stop_price: Some(stop_price),
+            close_position: true,
+            ..FuturesOrderRequest::new(symbol, side, FuturesOrderType::StopMarket, None)
+        }
+    }
+
+    fn new<S>(symbol: S, side: OrderSide, order_type: FuturesOrderType, qty: Option<Amount>) -> FuturesOrderRequest
+        where S: Into<String>
+    {
+        FuturesOrderRequest {
+            symbol: symbol.into(),
+            side,
+            position_side: None,
+            order_type,
+            qty,
+            price: None,
+            stop_price: None,
+            time_in_force: None,
+            reduce_only: false,
+            close_position: false,
+            working_type: None,
+            new_client_order_id: None,
+        }
+    }
+}
+
+impl FuturesAccount {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Balances, margins and positions
+    pub fn get_account(&self) -> Result<(FuturesAccountInformation)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(FAPI_V2_ACCOUNT, &request)?;
+        let account: FuturesAccountInformation = from_json(&data)?;
+
+        Ok(account)
+    }
+
+    // Positions of every symbol, or of one. Hedge mode lists a LONG and a SHORT position per symbol
+    pub fn get_position_risk(&self, symbol: Option<&str>) -> Result<(Vec<FuturesPosition>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(FAPI_V2_POSITION_RISK, &request)?;
+        let positions: Vec<FuturesPosition> = from_json(&data)?;
+
+        Ok(positions)
+    }
+
+    // 1 to 125, the maximum depends on the symbol and the notional of the position
+    pub fn change_leverage<S>(&self, symbol: S, leverage: u32) -> Result<(FuturesLeverage)>
+        where S: Into<String>
+    {
+        if !(1..=125).contains(&leverage) {
+            bail!(BinanceError::Parameter(format!("Futures leverage must be between 1 and 125, got {}", leverage)));
+        }
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("leverage".into(), leverage.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(FAPI_V1_LEVERAGE, &request)?;
+        let leverage: FuturesLeverage = from_json(&data)?;
+
+        Ok(leverage)
+    }
+
+    // Fails with code -4046 when the symbol already has that margin type
+    pub fn change_margin_type<S>(&self, symbol: S, margin_type: FuturesMarginType) -> Result<(FuturesStatus)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("marginType".into(), margin_type.as_str().into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(FAPI_V1_MARGIN_TYPE, &request)?;
+        let status: FuturesStatus = from_json(&data)?;
+
+        Ok(status)
+    }
+
+    pub fn place_order(&self, order: FuturesOrderRequest) -> Result<(FuturesOrder)> {
+        let parameters = order_parameters(order)?;
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(FAPI_V1_ORDER, &request)?;
+        let order: FuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<(FuturesOrder)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.delete_signed(FAPI_V1_ORDER, &request)?;
+        let order: FuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    pub fn order_status<S>(&self, symbol: S, order_id: u64) -> Result<(FuturesOrder)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(FAPI_V1_ORDER, &request)?;
+        let order: FuturesOrder = from_json(&data)?;
+
+        Ok(order)
+    }
+
+    // Open orders of every symbol (weight 40), or of one
+    pub fn get_open_orders(&self, symbol: Option<&str>) -> Result<(Vec<FuturesOrder>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(FAPI_V1_OPEN_ORDERS, &request)?;
+        let orders: Vec<FuturesOrder> = from_json(&data)?;
+
+        Ok(orders)
+    }
+
+    // Futures user data stream, connect with WebSockets::new_with_host(WebsocketHost::Futures)
+    // and add_futures_user_stream_handler
+    pub fn start_user_stream(&self) -> Result<(UserDataStream)> {
+        let data = self.client.post(FAPI_V1_LISTEN_KEY)?;
+        let user_data_stream: UserDataStream = from_json(&data)?;
+
+        Ok(user_data_stream)
+    }
+
+    // Extends the listen key by 60 minutes
+    pub fn keep_alive_user_stream(&self, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.put(FAPI_V1_LISTEN_KEY, listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+
+    pub fn close_user_stream(&self, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.delete(FAPI_V1_LISTEN_KEY, listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+}
+
+impl FuturesMarket {
+    // Funding rates of one symbol (or of every symbol when None), oldest first. limit is 100
+    // when None, at most 1000
+    pub fn get_funding_rates(
+        &self, symbol: Option<&str>, start_time: Option<u64>, end_time: Option<u64>, limit: Option<u16>,
+    ) -> Result<(Vec<FundingRate>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol.into());
+        }
+        if let Some(start_time) = start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit {
+            if limit == 0 || limit > FUNDING_RATE_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, FUNDING_RATE_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        let request = build_request(&parameters);
+
+        let data = self.client.get(FAPI_V1_FUNDING_RATE, &request)?;
+        let funding_rates: Vec<FundingRate> = from_json(&data)?;
+
+        Ok(funding_rates)
+    }
+
+    // Every funding rate of symbol between start_time and end_time in ms, requested 1000 at a time
+    // like Market::get_klines_range. delay pauses between two requests
+    pub fn get_funding_rate_range<S>(
+        &self, symbol: S, start_time: u64, end_time: u64, delay: Option<Duration>,
+    ) -> Result<(Vec<FundingRate>)>
+        where S: Into<String>
+    {
+        let symbol = symbol.into();
+        let mut funding_rates: Vec<FundingRate> = Vec::new();
+        let mut next_start = start_time;
+
+        while next_start <= end_time {
+            if !funding_rates.is_empty() {
+                if let Some(delay) = delay {
+                    thread::sleep(delay);
+                }
+            }
+
+            let page =
+                self.get_funding_rates(Some(&symbol), Some(next_start), Some(end_time), Some(FUNDING_RATE_MAX_LIMIT))?;
+            let page_len = page.len();
+            let previous_start = next_start;
+
+            for funding_rate in page {
+                // Never the same funding twice at a page boundary
+                if funding_rate.funding_time < next_start {
+                    continue;
+                }
+                next_start = funding_rate.funding_time + 1;
+                funding_rates.push(funding_rate);
+            }
+
+            // A short page is the last one, a page without new records would repeat forever
+            if page_len < FUNDING_RATE_MAX_LIMIT as usize || next_start == previous_start {
+                break;
+            }
+        }
+
+        Ok(funding_rates)
+    }
+
+    pub fn get_premium_index<S>(&self, symbol: S) -> Result<(PremiumIndex)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(FAPI_V1_PREMIUM_INDEX, &request)?;
+        let premium_index: PremiumIndex = from_json(&data)?;
+
+        Ok(premium_index)
+    }
+
+    // Premium index of every symbol
+    pub fn get_all_premium_indexes(&self) -> Result<(Vec<PremiumIndex>)> {
+        let data = self.client.get(FAPI_V1_PREMIUM_INDEX, "")?;
+        let premium_indexes: Vec<PremiumIndex> = from_json(&data)?;
+
+        Ok(premium_indexes)
+    }
+
+    pub fn get_open_interest<S>(&self, symbol: S) -> Result<(OpenInterest)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        let data = self.client.get(FAPI_V1_OPEN_INTEREST, &request)?;
+        let open_interest: OpenInterest = from_json(&data)?;
+
+        Ok(open_interest)
+    }
+
+    // Open interest per period ("5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h" or "1d") of the
+    // last 30 days. limit is 30 when None, at most 500
+    pub fn get_open_interest_hist<S1, S2>(
+        &self, symbol: S1, period: S2, limit: Option<u16>, start_time: Option<u64>, end_time: Option<u64>,
+    ) -> Result<(Vec<OpenInterestHist>)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("period".into(), period.into());
+        if let Some(limit) = limit {
+            if limit == 0 || limit > OPEN_INTEREST_HIST_MAX_LIMIT {
+                bail!(BinanceError::Parameter(format!(
+                    "Limit {} out of range, use 1 to {}",
+                    limit, OPEN_INTEREST_HIST_MAX_LIMIT
+                )));
+            }
+            parameters.insert("limit".into(), limit.to_string());
+        }
+        if let Some(start_time) = start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        let request = build_request(&parameters);
+
+        let data = self.client.get(FUTURES_DATA_OPEN_INTEREST_HIST, &request)?;
+        let history: Vec<OpenInterestHist> = from_json(&data)?;
+
+        Ok(history)
+    }
+}
+
+// Parameters of POST /fapi/v1/order, and of /dapi/v1/order with qty in contracts
+pub(crate) fn order_parameters(order: FuturesOrderRequest) -> Result<(BTreeMap<String, String>)> {
+    if order.qty.is_none() && !order.close_position {
+        bail!(BinanceError::Parameter("Futures orders need a quantity unless they close the position".into()));
+    }
+
+    let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+    parameters.insert("symbol".into(), order.symbol);
+    parameters.insert("side".into(), order.side.as_str().into());
+    if let Some(position_side) = order.position_side {
+        parameters.insert("positionSide".into(), position_side.as_str().into());
+    }
+    parameters.insert("type".into(), order.order_type.as_str().into());
+    if let Some(qty) = order.qty {
+        parameters.insert("quantity".into(), qty.to_string());
+    }
+    if let Some(price) = order.price {
+        parameters.insert("price".into(), price.to_string());
+    }
+    if let Some(stop_price) = order.stop_price {
+        parameters.insert("stopPrice".into(), stop_price.to_string());
+    }
+    if let Some(time_in_force) = order.time_in_force {
+        parameters.insert("timeInForce".into(), time_in_force.as_str().into());
+    }
+    if order.reduce_only {
+        parameters.insert("reduceOnly".into(), "true".into());
+    }
+    if order.close_position {
+        parameters.insert("closePosition".into(), "true".into());
+    }
+    if let Some(working_type) = order.working_type {
+        parameters.insert("workingType".into(), working_type.as_str().into());
+    }
+    if let Some(client_order_id) = order.new_client_order_id {
+        parameters.insert("newClientOrderId".into(), client_order_id);
+    }
+
+    Ok(parameters)
+}
@@ -1,13 +1,24 @@
 use model::*;
 use client::*;
 use errors::*;
-use serde_json::from_str;
+use util::*;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 static USER_DATA_STREAM: &'static str = "/api/v1/userDataStream";
+static MARGIN_USER_DATA_STREAM: &'static str = "/sapi/v1/userDataStream";
+static ISOLATED_MARGIN_USER_DATA_STREAM: &'static str = "/sapi/v1/userDataStream/isolated";
+
+// A listen key expires after 60 minutes without a keepalive
+pub static KEEP_ALIVE_INTERVAL_SECS: u64 = 30 * 60;
 
 #[derive(Clone)]
 pub struct UserStream {
     pub client: Client,
+    // Unused, the listen key requests are not signed
     pub recv_window: u64,
 }
 
@@ -15,7 +26,7 @@ impl UserStream {
     // User Stream
     pub fn start(&self) -> Result<(UserDataStream)> {
         let data = self.client.post(USER_DATA_STREAM)?;
-        let user_data_stream: UserDataStream = from_str(data.as_str())?;
+        let user_data_stream: UserDataStream = from_json(&data)?;
 
         Ok(user_data_stream)
     }
@@ -24,16 +35,269 @@ impl UserStream {
     pub fn keep_alive(&self, listen_key: &str) -> Result<(Success)> {
         let data = self.client.put(USER_DATA_STREAM, listen_key)?;
 
-        let success: Success = from_str(data.as_str())?;
+        let success: Success = from_json(&data)?;
 
         Ok(success)
     }
 
     pub fn close(&self, listen_key: &str) -> Result<(Success)> {
-        let data = self.client.put(USER_DATA_STREAM, listen_key)?;
+        let data = self.client.delete(USER_DATA_STREAM, listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+
+    // Cross margin user stream, connect to its listen key like to a spot one
+    pub fn start_margin(&self) -> Result<(UserDataStream)> {
+        let data = self.client.post(MARGIN_USER_DATA_STREAM)?;
+        let user_data_stream: UserDataStream = from_json(&data)?;
+
+        Ok(user_data_stream)
+    }
+
+    pub fn keep_alive_margin(&self, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.put(MARGIN_USER_DATA_STREAM, listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+
+    pub fn close_margin(&self, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.delete(MARGIN_USER_DATA_STREAM, listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+
+    // Isolated margin user stream of one symbol, every call needs that symbol
+    pub fn start_isolated_margin(&self, symbol: &str) -> Result<(UserDataStream)> {
+        let data = self.client.post(&isolated_endpoint(symbol))?;
+        let user_data_stream: UserDataStream = from_json(&data)?;
+
+        Ok(user_data_stream)
+    }
 
-        let success: Success = from_str(data.as_str())?;
+    pub fn keep_alive_isolated_margin(&self, symbol: &str, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.put(&isolated_endpoint(symbol), listen_key)?;
+
+        let success: Success = from_json(&data)?;
+
+        Ok(success)
+    }
+
+    pub fn close_isolated_margin(&self, symbol: &str, listen_key: &str) -> Result<(Success)> {
+        let data = self.client.delete(&isolated_endpoint(symbol), listen_key)?;
+
+        let success: Success = from_json(&data)?;
 
         Ok(success)
     }
+
+    // Listen key closed when the handle drops, so a panic doesn't leave it open for an hour
+    pub fn start_handle(&self) -> Result<(UserStreamHandle)> {
+        let listen_key = self.start()?.listen_key;
+
+        Ok(UserStreamHandle::new(self.clone(), StreamKind::Spot, listen_key))
+    }
+
+    pub fn start_margin_handle(&self) -> Result<(UserStreamHandle)> {
+        let listen_key = self.start_margin()?.listen_key;
+
+        Ok(UserStreamHandle::new(self.clone(), StreamKind::Margin, listen_key))
+    }
+
+    pub fn start_isolated_margin_handle<S>(&self, symbol: S) -> Result<(UserStreamHandle)>
+        where S: Into<String>
+    {
+        let symbol = symbol.into();
+        let listen_key = self.start_isolated_margin(&symbol)?.listen_key;
+
+        Ok(UserStreamHandle::new(self.clone(), StreamKind::IsolatedMargin(symbol), listen_key))
+    }
+
+    // Starts a user stream whose listen key a background thread keeps alive every interval,
+    // Duration::from_secs(KEEP_ALIVE_INTERVAL_SECS) unless there's a reason for another one
+    pub fn start_with_keepalive(&self, interval: Duration) -> Result<(UserStreamSession)> {
+        Ok(self.start_handle()?.keep_alive_every(interval))
+    }
+
+    pub fn start_margin_with_keepalive(&self, interval: Duration) -> Result<(UserStreamSession)> {
+        Ok(self.start_margin_handle()?.keep_alive_every(interval))
+    }
+
+    pub fn start_isolated_margin_with_keepalive<S>(&self, symbol: S, interval: Duration) -> Result<(UserStreamSession)>
+        where S: Into<String>
+    {
+        Ok(self.start_isolated_margin_handle(symbol)?.keep_alive_every(interval))
+    }
+}
+
+#[derive(Clone)]
+enum StreamKind {
+    Spot,
+    Margin,
+    IsolatedMargin(String),
+}
+
+impl StreamKind {
+    fn keep_alive(&self, user_stream: &UserStream, listen_key: &str) -> Result<(Success)> {
+        match *self {
+            StreamKind::Spot => user_stream.keep_alive(listen_key),
+            StreamKind::Margin => user_stream.keep_alive_margin(listen_key),
+            StreamKind::IsolatedMargin(ref symbol) => user_stream.keep_alive_isolated_margin(symbol, listen_key),
+        }
+    }
+
+    fn close(&self, user_stream: &UserStream, listen_key: &str) -> Result<(Success)> {
+        match *self {
+            StreamKind::Spot => user_stream.close(listen_key),
+            StreamKind::Margin => user_stream.close_margin(listen_key),
+            StreamKind::IsolatedMargin(ref symbol) => user_stream.close_isolated_margin(symbol, listen_key),
+        }
+    }
+}
+
+// Owns a listen key of start_handle. Dropping it closes the key, a failure is only logged (see
+// Client::set_logging), close() reports it
+pub struct UserStreamHandle {
+    user_stream: UserStream,
+    kind: StreamKind,
+    listen_key: String,
+    closed: bool,
+}
+
+impl UserStreamHandle {
+    fn new(user_stream: UserStream, kind: StreamKind, listen_key: String) -> UserStreamHandle {
+        UserStreamHandle {
+            user_stream,
+            kind,
+            listen_key,
+            closed: false,
+        }
+    }
+
+    pub fn listen_key(&self) -> &str {
+        &self.listen_key
+    }
+
+    pub fn keep_alive(&self) -> Result<(Success)> {
+        self.kind.keep_alive(&self.user_stream, &self.listen_key)
+    }
+
+    pub fn close(mut self) -> Result<()> {
+        self.closed = true;
+        self.kind.close(&self.user_stream, &self.listen_key)?;
+
+        Ok(())
+    }
+
+    // Hands the listen key to a session renewing it from a background thread
+    pub fn keep_alive_every(self, interval: Duration) -> UserStreamSession {
+        UserStreamSession::spawn(self, interval)
+    }
+}
+
+impl Drop for UserStreamHandle {
+    fn drop(&mut self) {
+        if !self.closed {
+            if let Err(e) = self.kind.close(&self.user_stream, &self.listen_key) {
+                self.user_stream.client.log_failure("listen key close", &e);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct KeepAliveState {
+    failures: u64,
+    last_error: Option<Error>,
+}
+
+// A listen key with its keepalive thread, connect WebSockets to listen_key(). Failed keepalives
+// are retried on the next interval and show in keep_alive_failures / take_error. Dropping the
+// session stops the thread first, then the handle closes the listen key
+pub struct UserStreamSession {
+    handle: Option<UserStreamHandle>,
+    state: Arc<Mutex<KeepAliveState>>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl UserStreamSession {
+    fn spawn(handle: UserStreamHandle, interval: Duration) -> UserStreamSession {
+        let state = Arc::new(Mutex::new(KeepAliveState::default()));
+        let (stop, stopped) = channel();
+
+        let thread = {
+            let user_stream = handle.user_stream.clone();
+            let kind = handle.kind.clone();
+            let listen_key = handle.listen_key.clone();
+            let state = state.clone();
+            // Waiting on the channel instead of sleeping lets stop() end the thread right away
+            thread::spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    if let Err(e) = kind.keep_alive(&user_stream, &listen_key) {
+                        let mut state = state.lock().unwrap();
+                        state.failures += 1;
+                        state.last_error = Some(e);
+                    }
+                }
+            })
+        };
+
+        UserStreamSession {
+            handle: Some(handle),
+            state,
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    pub fn listen_key(&self) -> &str {
+        self.handle.as_ref().map_or("", UserStreamHandle::listen_key)
+    }
+
+    // Keepalives that failed so far, the listen key expires an hour after the last successful one
+    pub fn keep_alive_failures(&self) -> u64 {
+        self.state.lock().unwrap().failures
+    }
+
+    // The error of the latest failed keepalive, once
+    pub fn take_error(&self) -> Option<Error> {
+        self.state.lock().unwrap().last_error.take()
+    }
+
+    // Stops the keepalive thread and closes the listen key
+    pub fn stop(mut self) -> Result<()> {
+        self.stop_thread();
+        match self.handle.take() {
+            Some(handle) => handle.close(),
+            None => Ok(()),
+        }
+    }
+
+    fn stop_thread(&mut self) {
+        // Dropping the sender wakes the thread up
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for UserStreamSession {
+    // The handle closes the listen key once dropped after this
+    fn drop(&mut self) {
+        self.stop_thread();
+    }
+}
+
+fn isolated_endpoint(symbol: &str) -> String {
+    let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+    parameters.insert("symbol".into(), symbol.into());
+
+    format!("{}?{}", ISOLATED_MARGIN_USER_DATA_STREAM, build_request(&parameters))
 }
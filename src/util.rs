@@ -1,7 +1,6 @@
 use errors::*;
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
-use serde_json::{Value};
 
 pub fn build_request(parameters: &BTreeMap<String, String>) -> String {
     let mut request = String::new();
@@ -14,36 +13,52 @@ pub fn build_request(parameters: &BTreeMap<String, String>) -> String {
     request
 }
 
+// symbols=["BTCUSDT","BNBBTC"], url encoded
+pub fn build_symbols_param(symbols: &[&str]) -> String {
+    build_array_param(symbols)
+}
+
+// A JSON array of strings, url encoded: ["A","B"] is %5B%22A%22,%22B%22%5D
+pub fn build_array_param(values: &[&str]) -> String {
+    let values: Vec<String> = values.iter().map(|value| format!("%22{}%22", value)).collect();
+
+    format!("%5B{}%5D", values.join(","))
+}
+
+// Largest recvWindow Binance accepts, in ms
+pub static MAX_RECV_WINDOW: u64 = 60000;
+
+pub fn check_recv_window(recv_window: u64) -> Result<()> {
+    if recv_window > MAX_RECV_WINDOW {
+        bail!(BinanceError::Parameter(format!(
+            "recvWindow {} exceeds the maximum of {} ms",
+            recv_window, MAX_RECV_WINDOW
+        )));
+    }
+
+    Ok(())
+}
+
 pub fn build_signed_request(mut parameters: BTreeMap<String, String>, recv_window: u64) -> Result<String> {
+    check_recv_window(recv_window)?;
     if recv_window > 0 {
         parameters.insert("recvWindow".into(), recv_window.to_string());
     }
 
-    if let Ok(timestamp) = get_timestamp() {
-        parameters.insert("timestamp".into(), timestamp.to_string());
-
-        let mut request = String::new();
-        for (key, value) in &parameters {
-            let param = format!("{}={}&", key, value);
-            request.push_str(param.as_ref());
-        }
-        request.pop(); // remove last &
+    let timestamp = get_timestamp()?;
+    parameters.insert("timestamp".into(), timestamp.to_string());
 
-        Ok(request)
-    } else {
-         bail!("Failed to get timestamp")
+    let mut request = String::new();
+    for (key, value) in &parameters {
+        let param = format!("{}={}&", key, value);
+        request.push_str(param.as_ref());
     }
-}
-
-pub fn to_i64(v: &Value) -> i64 { 
-    v.as_i64().unwrap() 
-}
+    request.pop(); // remove last &
 
-pub fn to_f64(v: &Value) -> f64 { 
-    v.as_str().unwrap().parse().unwrap() 
+    Ok(request)
 }
 
-fn get_timestamp() -> Result<u64> {
+pub fn get_timestamp() -> Result<u64> {
     let start = SystemTime::now();
     let since_epoch = start.duration_since(UNIX_EPOCH)?;
 
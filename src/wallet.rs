@@ -0,0 +1,534 @@
+use util::*;
+use model::*;
+use client::*;
+use errors::*;
+use std::collections::{BTreeMap, HashMap};
+use url::form_urlencoded::byte_serialize;
+
+static SAPI_V1_WITHDRAW: &'static str = "/sapi/v1/capital/withdraw/apply";
+static SAPI_V1_DEPOSIT_HISTORY: &'static str = "/sapi/v1/capital/deposit/hisrec";
+static SAPI_V1_DEPOSIT_ADDRESS: &'static str = "/sapi/v1/capital/deposit/address";
+static SAPI_V1_DUST_BTC: &'static str = "/sapi/v1/asset/dust-btc";
+static SAPI_V1_DUST: &'static str = "/sapi/v1/asset/dust";
+static SAPI_V1_WITHDRAW_HISTORY: &'static str = "/sapi/v1/capital/withdraw/history";
+static SAPI_V1_ASSET_TRANSFER: &'static str = "/sapi/v1/asset/transfer";
+static SAPI_V1_TRADE_FEE: &'static str = "/sapi/v1/asset/tradeFee";
+static SAPI_V1_ACCOUNT_SNAPSHOT: &'static str = "/sapi/v1/accountSnapshot";
+static SAPI_V1_ACCOUNT_STATUS: &'static str = "/sapi/v1/account/status";
+static SAPI_V1_API_TRADING_STATUS: &'static str = "/sapi/v1/account/apiTradingStatus";
+static SAPI_V1_API_RESTRICTIONS: &'static str = "/sapi/v1/account/apiRestrictions";
+static SAPI_V1_ASSET_DETAIL: &'static str = "/sapi/v1/asset/assetDetail";
+static SAPI_V1_COIN_CONFIG: &'static str = "/sapi/v1/capital/config/getall";
+static SAPI_V1_FIAT_ORDERS: &'static str = "/sapi/v1/fiat/orders";
+static SAPI_V1_FIAT_PAYMENTS: &'static str = "/sapi/v1/fiat/payments";
+
+static FIAT_SUCCESS_CODE: &'static str = "000000";
+
+// Both account status endpoints wrap their answer in {"data": ...}
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+// {"code":"000000","message":"success","data":[...],"total":1,"success":true} of the fiat endpoints
+#[derive(Deserialize)]
+struct FiatAnswer<T> {
+    code: String,
+    message: String,
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+    success: bool,
+}
+
+impl<T> FiatAnswer<T> {
+    fn into_rows(self) -> Result<Vec<T>> {
+        if !self.success || self.code != FIAT_SUCCESS_CODE {
+            bail!(BinanceError::Api {
+                status: 200,
+                code: self.code.parse().unwrap_or(0),
+                msg: self.message,
+            });
+        }
+
+        Ok(self.data)
+    }
+}
+
+// Wallet endpoints: deposits, withdrawals and transfers between wallets
+#[derive(Clone)]
+pub struct Wallet {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+// Optional parameters of a withdrawal
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawOptions {
+    // Memo / tag for the chains that need one
+    pub address_tag: Option<String>,
+    // e.g. "ETH" or "TRX", the default network of the coin when None
+    pub network: Option<String>,
+    // Label of the address in the address book
+    pub name: Option<String>,
+    // Our own id, a retry with the same id cannot withdraw twice
+    pub withdraw_order_id: Option<String>,
+}
+
+// Filters of get_deposit_history, at most 90 days between start_time and end_time
+#[derive(Debug, Clone, Default)]
+pub struct DepositHistoryOptions {
+    pub coin: Option<String>,
+    pub status: Option<DepositStatus>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub offset: Option<u32>,
+    // At most 1000
+    pub limit: Option<u32>,
+}
+
+// Filters of get_withdraw_history, at most 90 days between start_time and end_time
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawHistoryOptions {
+    pub coin: Option<String>,
+    pub withdraw_order_id: Option<String>,
+    pub status: Option<WithdrawStatus>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub offset: Option<u32>,
+    // At most 1000
+    pub limit: Option<u32>,
+}
+
+// Filters of get_universal_transfer_history
+#[derive(Debug, Clone, Default)]
+pub struct UniversalTransferHistoryOptions {
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // Page, starting at 1
+    pub current: Option<u32>,
+    // Rows per page, at most 100
+    pub size: Option<u32>,
+    // Isolated margin symbol, needed for the ISOLATEDMARGIN transfer types
+    pub from_symbol: Option<String>,
+    pub to_symbol: Option<String>,
+}
+
+// Filters of get_account_snapshot, the default gives the last 7 days
+#[derive(Debug, Clone, Default)]
+pub struct AccountSnapshotOptions {
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // Days, at least 7 and at most 30
+    pub limit: Option<u32>,
+}
+
+// Filters of get_fiat_orders and get_fiat_payments, the last 30 days when no time range is given
+#[derive(Debug, Clone, Default)]
+pub struct FiatHistoryOptions {
+    pub begin_time: Option<u64>,
+    pub end_time: Option<u64>,
+    // Page, starting at 1
+    pub page: Option<u32>,
+    // Rows per page, 100 by default and at most 500
+    pub rows: Option<u32>,
+}
+
+impl Wallet {
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // Withdraw amount of asset to address. Rejections are BinanceError::Api, see
+    // is_insufficient_balance, is_address_not_whitelisted and is_withdrawal_disabled
+    pub fn withdraw<S1, S2>(
+        &self, asset: S1, address: S2, amount: Amount, options: WithdrawOptions,
+    ) -> Result<(Withdrawal)>
+        where S1: Into<String>, S2: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("coin".into(), asset.into());
+        // Free-form values, escaped before being signed so that the query signed is the query sent
+        parameters.insert("address".into(), encoded(address.into()));
+        parameters.insert("amount".into(), amount.to_string());
+
+        if let Some(address_tag) = options.address_tag {
+            parameters.insert("addressTag".into(), encoded(address_tag));
+        }
+        if let Some(network) = options.network {
+            parameters.insert("network".into(), network);
+        }
+        if let Some(name) = options.name {
+            parameters.insert("name".into(), encoded(name));
+        }
+        if let Some(withdraw_order_id) = options.withdraw_order_id {
+            parameters.insert("withdrawOrderId".into(), encoded(withdraw_order_id));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_WITHDRAW, &request)?;
+        let withdrawal: Withdrawal = from_json(&data)?;
+
+        Ok(withdrawal)
+    }
+
+    // network is passed as is (e.g. "ETH", "TRX", "BSC"), None gives the default network of the coin.
+    // Addresses differ per network
+    pub fn get_deposit_address(&self, coin: &str, network: Option<&str>) -> Result<(DepositAddress)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("coin".into(), coin.into());
+        if let Some(network) = network {
+            parameters.insert("network".into(), network.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_DEPOSIT_ADDRESS, &request)?;
+        let deposit_address: DepositAddress = from_json(&data)?;
+
+        Ok(deposit_address)
+    }
+
+    // Withdrawal fee, minimum and deposit / withdrawal status of one asset or of all of them, by asset.
+    // get_all_coin_info has them per network
+    pub fn get_asset_details(&self, asset: Option<&str>) -> Result<(HashMap<String, AssetDetail>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(asset) = asset {
+            parameters.insert("asset".into(), asset.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ASSET_DETAIL, &request)?;
+        let details: HashMap<String, AssetDetail> = from_json(&data)?;
+
+        Ok(details)
+    }
+
+    // Every coin with our balance of it and its networks, each with its own fees, limits and status
+    pub fn get_all_coin_info(&self) -> Result<(Vec<CoinInfo>)> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_COIN_CONFIG, &request)?;
+        let coins: Vec<CoinInfo> = from_json(&data)?;
+
+        Ok(coins)
+    }
+
+    pub fn get_deposit_history(&self, options: DepositHistoryOptions) -> Result<(Vec<DepositRecord>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(coin) = options.coin {
+            parameters.insert("coin".into(), coin);
+        }
+        if let Some(status) = options.status {
+            parameters.insert("status".into(), status.code().to_string());
+        }
+        insert_range(&mut parameters, options.start_time, options.end_time, options.offset, options.limit);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_DEPOSIT_HISTORY, &request)?;
+        let deposits: Vec<DepositRecord> = from_json(&data)?;
+
+        Ok(deposits)
+    }
+
+    pub fn get_withdraw_history(&self, options: WithdrawHistoryOptions) -> Result<(Vec<WithdrawRecord>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(coin) = options.coin {
+            parameters.insert("coin".into(), coin);
+        }
+        if let Some(withdraw_order_id) = options.withdraw_order_id {
+            parameters.insert("withdrawOrderId".into(), encoded(withdraw_order_id));
+        }
+        if let Some(status) = options.status {
+            parameters.insert("status".into(), status.code().to_string());
+        }
+        insert_range(&mut parameters, options.start_time, options.end_time, options.offset, options.limit);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_WITHDRAW_HISTORY, &request)?;
+        let withdrawals: Vec<WithdrawRecord> = from_json(&data)?;
+
+        Ok(withdrawals)
+    }
+
+    // Balances that convert_dust can turn into BNB
+    pub fn get_convertible_dust(&self) -> Result<(ConvertibleDust)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_DUST_BTC, &request)?;
+        let dust: ConvertibleDust = from_json(&data)?;
+
+        Ok(dust)
+    }
+
+    // Converts the dust of assets to BNB. Assets Binance refused are missing from
+    // transfer_result, see DustTransfer::unconverted
+    pub fn convert_dust(&self, assets: &[&str]) -> Result<(DustTransfer)> {
+        if assets.is_empty() {
+            bail!(BinanceError::Parameter("No assets to convert".into()));
+        }
+
+        // asset repeats once per asset, which the parameter map cannot hold
+        let assets: Vec<String> = assets.iter().map(|asset| format!("asset={}", asset)).collect();
+        let signed = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let request = format!("{}&{}", assets.join("&"), signed);
+
+        let data = self.client.post_signed(SAPI_V1_DUST, &request)?;
+        let transfer: DustTransfer = from_json(&data)?;
+
+        Ok(transfer)
+    }
+
+    // "Normal" unless the account is restricted
+    pub fn get_account_status(&self) -> Result<(String)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ACCOUNT_STATUS, &request)?;
+        let status: Data<String> = from_json(&data)?;
+
+        Ok(status.data)
+    }
+
+    pub fn get_api_trading_status(&self) -> Result<(ApiTradingStatus)> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_API_TRADING_STATUS, &request)?;
+        let status: Data<ApiTradingStatus> = from_json(&data)?;
+
+        Ok(status.data)
+    }
+
+    // What the API key of the client is allowed to do
+    pub fn get_api_key_permissions(&self) -> Result<(ApiKeyPermissions)> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_API_RESTRICTIONS, &request)?;
+        let permissions: ApiKeyPermissions = from_json(&data)?;
+
+        Ok(permissions)
+    }
+
+    // Fails with BinanceError::ApiKeyPermissions unless the API key has exactly the required
+    // permissions, e.g. at startup:
+    // wallet.assert_permissions(Permissions { reading: true, spot_and_margin_trading: true, ..Permissions::default() })
+    pub fn assert_permissions(&self, required: Permissions) -> Result<(ApiKeyPermissions)> {
+        let permissions = self.get_api_key_permissions()?;
+        permissions.check(&required)?;
+
+        Ok(permissions)
+    }
+
+    // Moves amount of asset between two wallets, e.g. TransferType::MainUmfuture from spot to USD-M futures
+    pub fn universal_transfer<S>(
+        &self, transfer_type: TransferType, asset: S, amount: Amount,
+    ) -> Result<(TransactionId)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("type".into(), transfer_type.as_str().into());
+        parameters.insert("asset".into(), asset.into());
+        parameters.insert("amount".into(), amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.post_signed(SAPI_V1_ASSET_TRANSFER, &request)?;
+        let transaction_id: TransactionId = from_json(&data)?;
+
+        Ok(transaction_id)
+    }
+
+    pub fn get_universal_transfer_history(
+        &self, transfer_type: TransferType, options: UniversalTransferHistoryOptions,
+    ) -> Result<(UniversalTransferHistory)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("type".into(), transfer_type.as_str().into());
+        if let Some(start_time) = options.start_time {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = options.end_time {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(current) = options.current {
+            parameters.insert("current".into(), current.to_string());
+        }
+        if let Some(size) = options.size {
+            parameters.insert("size".into(), size.to_string());
+        }
+        if let Some(from_symbol) = options.from_symbol {
+            parameters.insert("fromSymbol".into(), from_symbol);
+        }
+        if let Some(to_symbol) = options.to_symbol {
+            parameters.insert("toSymbol".into(), to_symbol);
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ASSET_TRANSFER, &request)?;
+        let history: UniversalTransferHistory = from_json(&data)?;
+
+        Ok(history)
+    }
+
+    // Fee rates of our account, with VIP level and BNB discount applied. All symbols when None
+    pub fn get_trade_fees(&self, symbol: Option<&str>) -> Result<(Vec<TradeFee>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_TRADE_FEE, &request)?;
+        let fees: Vec<TradeFee> = from_json(&data)?;
+
+        Ok(fees)
+    }
+
+    // Daily snapshots of the spot, margin or futures account, oldest first
+    pub fn get_account_snapshot(
+        &self, snapshot_type: AccountSnapshotType, options: AccountSnapshotOptions,
+    ) -> Result<(Vec<AccountSnapshot>)> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("type".into(), snapshot_type.as_str().into());
+        insert_range(&mut parameters, options.start_time, options.end_time, None, options.limit);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_ACCOUNT_SNAPSHOT, &request)?;
+        let snapshots: AccountSnapshots = from_json(&data)?;
+
+        Ok(snapshots.snapshot_vos)
+    }
+
+    // Fiat deposits or withdrawals by bank transfer, a page shorter than options.rows is the last one
+    pub fn get_fiat_orders(
+        &self, transaction_type: FiatTransactionType, options: FiatHistoryOptions,
+    ) -> Result<(Vec<FiatOrder>)> {
+        let parameters = fiat_parameters(transaction_type.code(), options);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_FIAT_ORDERS, &request)?;
+        let answer: FiatAnswer<FiatOrder> = from_json(&data)?;
+
+        answer.into_rows()
+    }
+
+    // Crypto bought or sold with a card
+    pub fn get_fiat_payments(
+        &self, payment_type: FiatPaymentType, options: FiatHistoryOptions,
+    ) -> Result<(Vec<FiatPayment>)> {
+        let parameters = fiat_parameters(payment_type.code(), options);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data = self.client.get_signed(SAPI_V1_FIAT_PAYMENTS, &request)?;
+        let answer: FiatAnswer<FiatPayment> = from_json(&data)?;
+
+        answer.into_rows()
+    }
+}
+
+// Indexes the answer of get_trade_fees by symbol
+pub fn fees_by_symbol(fees: Vec<TradeFee>) -> HashMap<String, TradeFee> {
+    fees.into_iter().map(|fee| (fee.symbol.clone(), fee)).collect()
+}
+
+fn insert_range(
+    parameters: &mut BTreeMap<String, String>, start_time: Option<u64>, end_time: Option<u64>, offset: Option<u32>,
+    limit: Option<u32>,
+) {
+    if let Some(start_time) = start_time {
+        parameters.insert("startTime".into(), start_time.to_string());
+    }
+    if let Some(end_time) = end_time {
+        parameters.insert("endTime".into(), end_time.to_string());
+    }
+    if let Some(offset) = offset {
+        parameters.insert("offset".into(), offset.to_string());
+    }
+    if let Some(limit) = limit {
+        parameters.insert("limit".into(), limit.to_string());
+    }
+}
+
+fn fiat_parameters(transaction_type: u8, options: FiatHistoryOptions) -> BTreeMap<String, String> {
+    let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+    parameters.insert("transactionType".into(), transaction_type.to_string());
+    if let Some(begin_time) = options.begin_time {
+        parameters.insert("beginTime".into(), begin_time.to_string());
+    }
+    if let Some(end_time) = options.end_time {
+        parameters.insert("endTime".into(), end_time.to_string());
+    }
+    if let Some(page) = options.page {
+        parameters.insert("page".into(), page.to_string());
+    }
+    if let Some(rows) = options.rows {
+        parameters.insert("rows".into(), rows.to_string());
+    }
+
+    parameters
+}
+
+fn encoded(value: String) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Binance, Config};
+    use client::fake::{answer, client, FakeTransport};
+    use reqwest::Method;
+    use std::sync::Arc;
+
+    #[test]
+    fn withdraw_values_are_escaped() {
+        assert_eq!(encoded("my wallet #2".into()), "my+wallet+%232");
+        assert_eq!(encoded("a&timestamp=0".into()), "a%26timestamp%3D0");
+        let address = "0x32Be343B94f860124dC4fEe278FDCBD38C102D88";
+        assert_eq!(encoded(address.into()), address);
+    }
+
+    static COIN_CONFIG: &'static str = r#"[{"coin":"BNB","depositAllEnable":true,"free":"0.00000000","freeze":"0.00000000","ipoable":"0.00000000","ipoing":"0.00000000","isLegalMoney":false,"locked":"0.00000000","name":"BNB","networkList":[{"addressRegex":"^(bnb1)[0-9a-z]{38}$","coin":"BNB","depositDesc":"Wallet Maintenance, Deposit Suspended","depositEnable":false,"isDefault":false,"memoRegex":"^[0-9A-Za-z\\-_]{1,120}$","minConfirm":1,"name":"BEP2","network":"BNB","specialTips":"Both a MEMO and an Address are required to successfully deposit your BEP2-BNB tokens to Binance.","unLockConfirm":0,"withdrawDesc":"Wallet Maintenance, Withdrawal Suspended","withdrawEnable":false,"withdrawFee":"0.00000220","withdrawIntegerMultiple":"0.00000001","withdrawMax":"9999999999.99999999","withdrawMin":"0.00000440","sameAddress":true,"estimatedArrivalTime":25,"busy":false},{"addressRegex":"^(0x)[0-9A-Fa-f]{40}$","coin":"BNB","depositEnable":true,"isDefault":true,"memoRegex":"","minConfirm":15,"name":"BNB Smart Chain (BEP20)","network":"BSC","specialTips":"","unLockConfirm":0,"withdrawEnable":true,"withdrawFee":"0.00000000","withdrawIntegerMultiple":"0.00000001","withdrawMax":"9999999999.99999999","withdrawMin":"0.00000000","sameAddress":false,"estimatedArrivalTime":25,"busy":false}],"storage":"0.00000000","trading":true,"withdrawAllEnable":true,"withdrawing":"0.00000000"}]"#;
+
+    fn wallet(transport: &Arc<FakeTransport>) -> Wallet {
+        Wallet::new_with_client(client(transport), &Config::default())
+    }
+
+    #[test]
+    fn get_all_coin_info() {
+        let transport = FakeTransport::new(vec![answer(200, COIN_CONFIG)]);
+
+        let coins = wallet(&transport).get_all_coin_info().unwrap();
+        assert_eq!(coins.len(), 1);
+        let bsc = coins[0].default_network().unwrap();
+        assert_eq!(bsc.network, "BSC");
+        assert!(bsc.withdraw_enable);
+        assert_eq!(bsc.min_confirm, 15);
+        let bep2 = coins[0].network("BNB").unwrap();
+        assert!(!bep2.deposit_enable);
+        assert_eq!(bep2.withdraw_fee, amount("0.0000022"));
+        assert_eq!(bep2.withdraw_desc, "Wallet Maintenance, Withdrawal Suspended");
+        assert!(transport.requests()[0].1.starts_with("https://api.binance.com/sapi/v1/capital/config/getall?"));
+    }
+
+    #[test]
+    fn withdraw_signs_the_escaped_values() {
+        let transport = FakeTransport::new(vec![answer(200, r#"{"id":"7213fea8e94b4a5593d507237e5a555b"}"#)]);
+        let options = WithdrawOptions { name: Some("cold wallet".into()), ..WithdrawOptions::default() };
+
+        let withdrawal = wallet(&transport).withdraw("BNB", "bnb1&x", amount("1.5"), options).unwrap();
+        assert_eq!(withdrawal.id, "7213fea8e94b4a5593d507237e5a555b");
+
+        let (method, url) = transport.requests().remove(0);
+        assert_eq!(method, Method::Post);
+        assert!(url.contains("?address=bnb1%26x&amount=1.5&coin=BNB&name=cold+wallet&recvWindow=5000&timestamp="));
+    }
+
+    #[test]
+    fn withdraw_rejected() {
+        let rejected = r#"{"code":-4026,"msg":"User has insufficient balance"}"#;
+        let transport = FakeTransport::new(vec![answer(400, rejected)]);
+
+        let address = "0x32Be343B94f860124dC4fEe278FDCBD38C102D88";
+        match wallet(&transport).withdraw("BNB", address, amount("1"), WithdrawOptions::default()) {
+            Err(ref error) if error.binance_error().map_or(false, |error| error.is_insufficient_balance()) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
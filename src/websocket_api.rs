@@ -0,0 +1,420 @@
+use model::*;
+use errors::*;
+use client::*;
+use util::*;
+use proxy::{self, Proxy};
+use websockets::{close_socket, set_read_timeout};
+use url::Url;
+use serde_json::{from_value, to_string, Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::ErrorKind as IoErrorKind;
+use std::time::{Duration, Instant};
+
+use tungstenite::connect;
+use tungstenite::Error as TungsteniteError;
+use tungstenite::Message;
+use tungstenite::protocol::WebSocket;
+use tungstenite::client::AutoStream;
+
+static WEBSOCKET_API_URL: &'static str = "wss://ws-api.binance.com:443/ws-api/v3";
+static WEBSOCKET_API_TESTNET_URL: &'static str = "wss://ws-api.testnet.binance.vision/ws-api/v3";
+
+static ORDER_PLACE: &'static str = "order.place";
+static ORDER_CANCEL: &'static str = "order.cancel";
+static ORDER_STATUS: &'static str = "order.status";
+static ACCOUNT_STATUS: &'static str = "account.status";
+
+static DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+// {"id":1,"method":"order.place","params":{...}}
+#[derive(Serialize)]
+struct ApiRequest<'a> {
+    id: u64,
+    method: &'a str,
+    params: Map<String, Value>,
+}
+
+// {"id":1,"status":200,"result":{...}} or {"id":1,"status":400,"error":{"code":-2010,"msg":"..."}}
+#[derive(Deserialize)]
+struct ApiResponse {
+    id: Option<u64>,
+    status: u16,
+    result: Option<Value>,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    code: i64,
+    msg: String,
+}
+
+// Client of the websocket API (ws-api), trades over one connection instead of a REST round trip
+// per request. Requests are signed like the REST ones, responses may arrive in any order and
+// each request waits for its own id
+pub struct WebsocketApi {
+    socket: WebSocket<AutoStream>,
+    client: Client,
+    recv_window: u64,
+    timeout: Duration,
+    next_id: u64,
+    responses: HashMap<u64, ApiResponse>,
+    // Requests sent and not answered yet. A request that timed out leaves it, its late response is
+    // discarded like the ones of unknown ids
+    in_flight: HashSet<u64>,
+}
+
+impl WebsocketApi {
+    pub fn connect(api_key: Option<String>, secret_key: Option<String>) -> Result<WebsocketApi> {
+        WebsocketApi::connect_with_endpoint(WEBSOCKET_API_URL, api_key, secret_key)
+    }
+
+    pub fn connect_testnet(api_key: Option<String>, secret_key: Option<String>) -> Result<WebsocketApi> {
+        WebsocketApi::connect_with_endpoint(WEBSOCKET_API_TESTNET_URL, api_key, secret_key)
+    }
+
+    // Honours https_proxy / all_proxy and no_proxy like WebSockets does
+    pub fn connect_with_endpoint(
+        endpoint: &str, api_key: Option<String>, secret_key: Option<String>,
+    ) -> Result<WebsocketApi> {
+        let url = Url::parse(endpoint)?;
+        let answer = match Proxy::from_env_for(&url) {
+            Some(ref proxy) => proxy::connect(&url, proxy),
+            None => Ok(connect(url)?),
+        };
+        let socket = match answer {
+            Ok((socket, _)) => socket,
+            Err(e) => bail!(e),
+        };
+
+        Ok(WebsocketApi {
+            socket,
+            client: Client::new(api_key, secret_key),
+            recv_window: 5000,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            next_id: 1,
+            responses: HashMap::new(),
+            in_flight: HashSet::new(),
+        })
+    }
+
+    pub fn set_recv_window(&mut self, recv_window: u64) -> Result<()> {
+        update_recv_window(&mut self.recv_window, recv_window)
+    }
+
+    // How long request waits for its response, 10 seconds by default
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    // Place a LIMIT GTC order
+    pub fn place_limit_order<S>(
+        &mut self, symbol: S, side: OrderSide, qty: Amount, price: Amount,
+    ) -> Result<(Transaction)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("side".into(), side.as_str().into());
+        parameters.insert("type".into(), OrderType::Limit.as_str().into());
+        parameters.insert("timeInForce".into(), TimeInForce::GoodTillCanceled.as_str().into());
+        parameters.insert("quantity".into(), qty.to_string());
+        parameters.insert("price".into(), price.to_string());
+
+        let result = self.request(ORDER_PLACE, parameters, true)?;
+
+        Ok(from_value(result)?)
+    }
+
+    // Place a MARKET order
+    pub fn place_market_order<S>(&mut self, symbol: S, side: OrderSide, qty: Amount) -> Result<(Transaction)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("side".into(), side.as_str().into());
+        parameters.insert("type".into(), OrderType::Market.as_str().into());
+        parameters.insert("quantity".into(), qty.to_string());
+
+        let result = self.request(ORDER_PLACE, parameters, true)?;
+
+        Ok(from_value(result)?)
+    }
+
+    pub fn cancel_order<S>(&mut self, symbol: S, order_id: u64) -> Result<(OrderCanceled)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let result = self.request(ORDER_CANCEL, parameters, true)?;
+
+        Ok(from_value(result)?)
+    }
+
+    pub fn order_status<S>(&mut self, symbol: S, order_id: u64) -> Result<(Order)>
+        where S: Into<String>
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let result = self.request(ORDER_STATUS, parameters, true)?;
+
+        Ok(from_value(result)?)
+    }
+
+    pub fn account_status(&mut self) -> Result<(AccountInformation)> {
+        let result = self.request(ACCOUNT_STATUS, BTreeMap::new(), true)?;
+
+        Ok(from_value(result)?)
+    }
+
+    // Sends a request and waits for its result. Binance errors surface as
+    // BinanceError::Api, a missing response as BinanceError::WebsocketApiTimeout
+    pub fn request(&mut self, method: &str, parameters: BTreeMap<String, String>, signed: bool) -> Result<Value> {
+        let id = self.send_request(method, parameters, signed)?;
+        let timeout = self.timeout;
+
+        self.wait_for(id, timeout)
+    }
+
+    // Sends a request without waiting, several requests can be in flight before wait_for
+    pub fn send_request(
+        &mut self, method: &str, mut parameters: BTreeMap<String, String>, signed: bool,
+    ) -> Result<u64> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        // The parameters of the caller go out as the strings they are, e.g. a numeric client order id
+        let mut params: Map<String, Value> = Map::new();
+        if signed {
+            check_recv_window(self.recv_window)?;
+            let timestamp = get_timestamp()? as i64 + self.client.time_offset();
+            parameters.insert("apiKey".into(), self.client.api_key().into());
+            if self.recv_window > 0 {
+                parameters.insert("recvWindow".into(), self.recv_window.to_string());
+                params.insert("recvWindow".into(), Value::from(self.recv_window));
+            }
+            parameters.insert("timestamp".into(), timestamp.to_string());
+            params.insert("timestamp".into(), Value::from(timestamp));
+            // The signature covers the parameters sorted by name, as BTreeMap iterates them
+            let signature = self.client.signature(&build_request(&parameters));
+            parameters.insert("signature".into(), signature);
+        }
+        for (key, value) in parameters {
+            params.entry(key).or_insert(Value::String(value));
+        }
+
+        let request = to_string(&ApiRequest { id, method, params })?;
+        self.socket.write_message(Message::Text(request))?;
+        self.in_flight.insert(id);
+
+        Ok(id)
+    }
+
+    // Waits for the response of a send_request, responses of other requests read meanwhile are kept
+    pub fn wait_for(&mut self, id: u64, timeout: Duration) -> Result<Value> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(response) = self.responses.remove(&id) {
+                return into_result(response);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                self.in_flight.remove(&id);
+                bail!(BinanceError::WebsocketApiTimeout { id });
+            }
+            set_read_timeout(&self.socket, Some(deadline - now))?;
+
+            // Pings are answered by tungstenite on the next read
+            let msg = match self.socket.read_message() {
+                Ok(Message::Text(msg)) => msg,
+                Ok(Message::Binary(data)) => match String::from_utf8(data) {
+                    Ok(msg) => msg,
+                    Err(_) => continue,
+                },
+                Ok(_) => continue,
+                Err(TungsteniteError::Io(ref e))
+                    if e.kind() == IoErrorKind::WouldBlock || e.kind() == IoErrorKind::TimedOut =>
+                {
+                    continue;
+                }
+                Err(TungsteniteError::ConnectionClosed(frame)) => {
+                    bail!(BinanceError::WebsocketClosed {
+                        frame: frame.map(|frame| frame.into_owned()),
+                    });
+                }
+                Err(e) => bail!(e),
+            };
+
+            let response: ApiResponse = from_json(&msg)?;
+            if let Some(response_id) = response.id {
+                if self.in_flight.remove(&response_id) {
+                    self.responses.insert(response_id, response);
+                }
+            }
+        }
+    }
+
+    pub fn disconnect(mut self) -> Result<()> {
+        close_socket(&mut self.socket)
+    }
+}
+
+fn into_result(response: ApiResponse) -> Result<Value> {
+    if let Some(error) = response.error {
+        bail!(BinanceError::Api {
+            status: response.status,
+            code: error.code,
+            msg: error.msg,
+        });
+    }
+    match response.result {
+        Some(result) => Ok(result),
+        None => bail!(BinanceError::Internal(format!(
+            "Websocket API response without result, status {}",
+            response.status
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread::{self, JoinHandle};
+    use tungstenite::accept;
+
+    // Local ws-api server running session on the first connection
+    fn serve<F>(session: F) -> (String, JoinHandle<()>)
+    where
+        F: FnOnce(WebSocket<TcpStream>) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}/ws-api/v3", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            match accept(stream) {
+                Ok(socket) => session(socket),
+                Err(_) => panic!("websocket handshake failed"),
+            }
+        });
+
+        (endpoint, server)
+    }
+
+    fn read_request(socket: &mut WebSocket<TcpStream>) -> Value {
+        match socket.read_message().unwrap() {
+            Message::Text(request) => from_str(&request).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    fn reply(socket: &mut WebSocket<TcpStream>, response: String) {
+        socket.write_message(Message::Text(response)).unwrap();
+    }
+
+    fn api(endpoint: &str) -> WebsocketApi {
+        WebsocketApi::connect_with_endpoint(endpoint, Some("api-key".into()), Some("secret-key".into())).unwrap()
+    }
+
+    #[test]
+    fn responses_out_of_order_reach_their_requests() {
+        let (endpoint, server) = serve(|mut socket| {
+            let first = read_request(&mut socket);
+            let second = read_request(&mut socket);
+            reply(&mut socket, format!(r#"{{"id":{},"status":200,"result":{{"n":2}}}}"#, second["id"]));
+            reply(&mut socket, format!(r#"{{"id":{},"status":200,"result":{{"n":1}}}}"#, first["id"]));
+            while socket.read_message().is_ok() {}
+        });
+        let mut api = api(&endpoint);
+
+        let first = api.send_request("order.status", BTreeMap::new(), false).unwrap();
+        let second = api.send_request("order.status", BTreeMap::new(), false).unwrap();
+        assert_eq!(api.wait_for(first, Duration::from_secs(5)).unwrap()["n"], 1);
+        assert_eq!(api.wait_for(second, Duration::from_secs(5)).unwrap()["n"], 2);
+        api.disconnect().unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn signed_parameters_keep_their_strings() {
+        let (endpoint, server) = serve(|mut socket| {
+            let request = read_request(&mut socket);
+            let params = &request["params"];
+            assert_eq!(params["newClientOrderId"], "12345");
+            assert_eq!(params["recvWindow"], 5000);
+            assert!(params["timestamp"].is_u64());
+
+            let payload = format!(
+                "apiKey=api-key&newClientOrderId=12345&recvWindow=5000&symbol=LTCBTC&timestamp={}",
+                params["timestamp"]
+            );
+            let client = Client::new(None, Some("secret-key".into()));
+            assert_eq!(params["signature"], Value::String(client.signature(&payload)));
+            reply(&mut socket, format!(r#"{{"id":{},"status":200,"result":{{}}}}"#, request["id"]));
+            while socket.read_message().is_ok() {}
+        });
+        let mut api = api(&endpoint);
+
+        let mut parameters = BTreeMap::new();
+        parameters.insert("symbol".to_string(), "LTCBTC".to_string());
+        parameters.insert("newClientOrderId".to_string(), "12345".to_string());
+        api.request("order.status", parameters, true).unwrap();
+        api.disconnect().unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn late_response_of_a_timed_out_request_is_discarded() {
+        let (endpoint, server) = serve(|mut socket| {
+            let late = read_request(&mut socket);
+            let next = read_request(&mut socket);
+            reply(&mut socket, format!(r#"{{"id":{},"status":200,"result":{{"n":1}}}}"#, late["id"]));
+            reply(&mut socket, format!(r#"{{"id":{},"status":200,"result":{{"n":2}}}}"#, next["id"]));
+            while socket.read_message().is_ok() {}
+        });
+        let mut api = api(&endpoint);
+        api.set_timeout(Duration::from_millis(50));
+
+        match api.request("order.status", BTreeMap::new(), false) {
+            Err(BinanceError::WebsocketApiTimeout { id: 1 }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        api.set_timeout(Duration::from_secs(5));
+        assert_eq!(api.request("order.status", BTreeMap::new(), false).unwrap()["n"], 2);
+        assert!(api.responses.is_empty() && api.in_flight.is_empty());
+        api.disconnect().unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn error_response_is_an_api_error() {
+        let (endpoint, server) = serve(|mut socket| {
+            let request = read_request(&mut socket);
+            reply(
+                &mut socket,
+                format!(
+                    r#"{{"id":{},"status":400,"error":{{"code":-2010,"msg":"Account has insufficient balance for requested action."}}}}"#,
+                    request["id"]
+                ),
+            );
+            while socket.read_message().is_ok() {}
+        });
+        let mut api = api(&endpoint);
+
+        match api.request("order.place", BTreeMap::new(), false) {
+            Err(BinanceError::Api { status: 400, code: -2010, ref msg }) => {
+                assert_eq!(msg, "Account has insufficient balance for requested action.")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        api.disconnect().unwrap();
+        server.join().unwrap();
+    }
+}
@@ -1,199 +1,2582 @@
 use model::*;
 use errors::*;
+use userstream::{UserStream, KEEP_ALIVE_INTERVAL_SECS};
+use proxy::{self, Proxy};
+use queue::{bounded, EventReceiver, EventSender, Overflow, QueueStopper};
+use recording::{Recorder, Rotation};
 use url::Url;
-use serde_json::from_str;
+use serde_json;
+use serde_json::{from_str, from_value, to_string, Value};
+use std::cmp;
+use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use tungstenite::connect;
-use tungstenite::protocol::WebSocket;
+use tungstenite::Error as TungsteniteError;
+use tungstenite::Message;
+use tungstenite::protocol::{CloseFrame, WebSocket};
 use tungstenite::client::AutoStream;
+use tungstenite::stream::Stream;
+use std::io::ErrorKind as IoErrorKind;
 use tungstenite::handshake::client::Response;
 
-static WEBSOCKET_URL: &'static str = "wss://stream.binance.com:9443/ws/";
+static WEBSOCKET_URL: &'static str = "wss://stream.binance.com:9443";
+static WEBSOCKET_US_URL: &'static str = "wss://stream.binance.us:9443";
+static WEBSOCKET_TESTNET_URL: &'static str = "wss://testnet.binance.vision";
+static WEBSOCKET_FUTURES_URL: &'static str = "wss://fstream.binance.com";
+
+static SINGLE_STREAM: &'static str = "/ws/";
+static MULTI_STREAM: &'static str = "/stream?streams="; // <streamName1>/<streamName2>/<streamName3>
+static SUBSCRIBE: &'static str = "SUBSCRIBE";
+static UNSUBSCRIBE: &'static str = "UNSUBSCRIBE";
+static LIST_SUBSCRIPTIONS: &'static str = "LIST_SUBSCRIPTIONS";
+static REQUEST_ID: &'static str = "id";
+
+// Events a spawned event loop queues before Overflow applies
+static DEFAULT_QUEUE_CAPACITY: usize = 1024;
+// How often a spawned event loop checks whether it should stop
+static STOP_POLL_MS: u64 = 100;
+// Binance drops every connection after 24 hours
+static CONNECTION_LIFETIME_SECS: u64 = 24 * 60 * 60;
+static SCHEDULED_RECONNECT_MARGIN_SECS: u64 = 5 * 60;
+// Read timeout used to notice the end of the connection lifetime or a stale connection
+// on a quiet stream
+static LIFETIME_POLL_MS: u64 = 1000;
+// How long a closing connection waits for the server to acknowledge the Close frame
+static CLOSE_TIMEOUT_MS: u64 = 1000;
+
+// Binance drops connections sending more than 5 control messages per second
+static MAX_CONTROL_MESSAGES_PER_SECOND: usize = 5;
 
-static WEBSOCKET_MULTI_STREAM: &'static str = "wss://stream.binance.com:9443/stream?streams="; // <streamName1>/<streamName2>/<streamName3>
-// {"stream":"<streamName>","data":<rawPayload>}
 static STREAM: &'static str = "stream";
-static _DATA: &'static str = "data";
+static DATA: &'static str = "data";
+
+// Every event payload carries its type in the "e" field
+static EVENT_TYPE: &'static str = "e";
 
 static OUTBOUND_ACCOUNT_INFO: &'static str = "outboundAccountInfo";
 static EXECUTION_REPORT: &'static str = "executionReport";
+static BALANCE_UPDATE: &'static str = "balanceUpdate";
+static OUTBOUND_ACCOUNT_POSITION: &'static str = "outboundAccountPosition";
+static LIST_STATUS: &'static str = "listStatus";
+static MARGIN_CALL: &'static str = "MARGIN_CALL";
+static LISTEN_KEY_EXPIRED: &'static str = "listenKeyExpired";
+// USD-M futures user data stream, the spot events have other types
+static ORDER_TRADE_UPDATE: &'static str = "ORDER_TRADE_UPDATE";
+static FUTURES_ACCOUNT_UPDATE: &'static str = "ACCOUNT_UPDATE";
 
 static KLINE: &'static str = "kline";
 static AGGREGATED_TRADE: &'static str = "aggTrade";
+static TRADE: &'static str = "trade";
 static DEPTH_ORDERBOOK : &'static str = "depthUpdate";
 static PARTIAL_ORDERBOOK : &'static str = "lastUpdateId";
 
 static DAYTICKER: &'static str = "24hrTicker";
+static MINI_TICKER: &'static str = "24hrMiniTicker";
+
+static MARK_PRICE: &'static str = "markPriceUpdate";
+static FORCE_ORDER: &'static str = "forceOrder";
+static CONTINUOUS_KLINE: &'static str = "continuous_kline";
+
+// Book ticker payloads have no event type, they are recognized by their fields
+static BOOK_TICKER: [&'static str; 3] = ["u", "b", "A"];
 
 pub trait UserStreamEventHandler {
-    fn account_update_handler(&self, event: &AccountUpdateEvent);
-    fn order_trade_handler(&self, event: &OrderTradeEvent);
+    fn account_update_handler(&mut self, event: &AccountUpdateEvent);
+    fn order_trade_handler(&mut self, event: &OrderTradeEvent);
+    fn balance_update_handler(&mut self, _event: &BalanceUpdateEvent) {}
+    fn account_position_handler(&mut self, _event: &AccountPositionEvent) {}
+    fn list_status_handler(&mut self, _event: &OrderListStatusEvent) {}
+    fn margin_call_handler(&mut self, _event: &MarginCallEvent) {}
+    // The stream delivers nothing more, start a new one
+    fn listen_key_expired_handler(&mut self, _event: &ListenKeyExpiredEvent) {}
+}
+
+// USD-M futures user data stream, see FuturesAccount::start_user_stream
+pub trait FuturesUserStreamEventHandler {
+    fn order_trade_update_handler(&mut self, event: &FuturesOrderTradeEvent);
+    fn account_update_handler(&mut self, event: &FuturesAccountUpdateEvent);
+    fn margin_call_handler(&mut self, _event: &MarginCallEvent) {}
+    fn listen_key_expired_handler(&mut self, _event: &ListenKeyExpiredEvent) {}
 }
 
 pub trait MarketEventHandler {
-    fn aggregated_trades_handler(&self, event: &TradesEvent);
-    fn depth_orderbook_handler(&self, event: &DepthOrderBookEvent);
-    fn partial_orderbook_handler(&self, order_book: &OrderBook);
+    fn aggregated_trades_handler(&mut self, event: &TradesEvent);
+    fn depth_orderbook_handler(&mut self, event: &DepthOrderBookEvent);
+    fn partial_orderbook_handler(&mut self, order_book: &OrderBook);
 }
 
+pub trait TradeEventHandler {
+    fn trade_handler(&mut self, event: &TradeEvent);
+}
+
+// <symbol>@ticker delivers single events, !ticker@arr delivers the whole market
 pub trait DayTickerEventHandler {
-    fn day_ticker_handler(&self, event: &[DayTickerEvent]);
+    fn day_ticker_handler(&mut self, event: &[DayTickerEvent]);
+    fn symbol_ticker_handler(&mut self, _event: &DayTickerEvent) {}
+}
+
+// <symbol>@miniTicker delivers single events, !miniTicker@arr delivers the whole market
+pub trait MiniTickerEventHandler {
+    fn mini_ticker_handler(&mut self, event: &MiniTickerEvent);
+    fn all_mini_ticker_handler(&mut self, events: &[MiniTickerEvent]);
 }
 
 pub trait KlineEventHandler {
-    fn kline_handler(&self, event: &KlineEvent);
+    fn kline_handler(&mut self, event: &KlineEvent);
+}
+
+pub trait BookTickerEventHandler {
+    fn book_ticker_handler(&mut self, event: &BookTickerEvent);
+}
+
+// USD-M futures streams, see WebsocketHost::Futures
+pub trait MarkPriceEventHandler {
+    fn mark_price_handler(&mut self, event: &MarkPriceEvent);
+}
+
+pub trait LiquidationEventHandler {
+    fn liquidation_handler(&mut self, event: &LiquidationOrderEvent);
+}
+
+pub trait ContinuousKlineEventHandler {
+    fn continuous_kline_handler(&mut self, event: &ContinuousKlineEvent);
+}
+
+// Receives the events of a combined stream together with the name of the stream
+// (e.g. "btcusdt@depth20") they were delivered on
+pub trait CombinedStreamEventHandler {
+    fn aggregated_trades_handler(&mut self, _stream: &str, _event: &TradesEvent) {}
+    fn trade_handler(&mut self, _stream: &str, _event: &TradeEvent) {}
+    fn depth_orderbook_handler(&mut self, _stream: &str, _event: &DepthOrderBookEvent) {}
+    fn partial_orderbook_handler(&mut self, _stream: &str, _order_book: &OrderBook) {}
+    fn day_ticker_handler(&mut self, _stream: &str, _events: &[DayTickerEvent]) {}
+    fn symbol_ticker_handler(&mut self, _stream: &str, _event: &DayTickerEvent) {}
+    fn kline_handler(&mut self, _stream: &str, _event: &KlineEvent) {}
+    fn book_ticker_handler(&mut self, _stream: &str, _event: &BookTickerEvent) {}
+    fn mini_ticker_handler(&mut self, _stream: &str, _event: &MiniTickerEvent) {}
+    fn all_mini_ticker_handler(&mut self, _stream: &str, _events: &[MiniTickerEvent]) {}
+    fn mark_price_handler(&mut self, _stream: &str, _event: &MarkPriceEvent) {}
+    fn liquidation_handler(&mut self, _stream: &str, _event: &LiquidationOrderEvent) {}
+    fn continuous_kline_handler(&mut self, _stream: &str, _event: &ContinuousKlineEvent) {}
+}
+
+pub trait SubscriptionEventHandler {
+    fn subscription_handler(&mut self, response: &SubscriptionResponse);
+}
+
+// Receives the raw message of any payload that could not be deserialized
+pub trait ErrorEventHandler {
+    fn error_handler(&mut self, msg: &str, error: &serde_json::Error);
+}
+
+// Official websocket hosts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebsocketHost {
+    Binance,
+    BinanceUs,
+    Testnet,
+    // USD-M futures
+    Futures,
+}
+
+impl WebsocketHost {
+    pub fn base_url(&self) -> &'static str {
+        match *self {
+            WebsocketHost::Binance => WEBSOCKET_URL,
+            WebsocketHost::BinanceUs => WEBSOCKET_US_URL,
+            WebsocketHost::Testnet => WEBSOCKET_TESTNET_URL,
+            WebsocketHost::Futures => WEBSOCKET_FUTURES_URL,
+        }
+    }
+}
+
+// Number of price levels of a partial book depth stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Levels {
+    Five,
+    Ten,
+    Twenty,
+}
+
+impl Levels {
+    pub fn count(&self) -> u8 {
+        match *self {
+            Levels::Five => 5,
+            Levels::Ten => 10,
+            Levels::Twenty => 20,
+        }
+    }
+
+    // Extracts the levels from a stream name such as "btcusdt@depth10@100ms"
+    pub fn from_stream_name(stream: &str) -> Option<Levels> {
+        let depth = stream.split('@').nth(1)?;
+        match depth {
+            "depth5" => Some(Levels::Five),
+            "depth10" => Some(Levels::Ten),
+            "depth20" => Some(Levels::Twenty),
+            _ => None,
+        }
+    }
+}
+
+// Update speed of depth streams, Binance defaults to 1000ms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateSpeed {
+    Ms100,
+    Ms1000,
+}
+
+impl UpdateSpeed {
+    fn suffix(&self) -> &'static str {
+        match *self {
+            UpdateSpeed::Ms100 => "@100ms",
+            UpdateSpeed::Ms1000 => "",
+        }
+    }
+}
+
+// <symbol>@depth<levels>[@100ms]
+pub fn partial_depth_stream(symbol: &str, levels: Levels, speed: UpdateSpeed) -> String {
+    format!("{}@depth{}{}", symbol.to_lowercase(), levels.count(), speed.suffix())
+}
+
+// <symbol>@depth[@100ms]
+pub fn diff_depth_stream(symbol: &str, speed: UpdateSpeed) -> String {
+    format!("{}@depth{}", symbol.to_lowercase(), speed.suffix())
+}
+
+type Callback<T> = Box<FnMut(&T)>;
+// Moves to the reader thread of spawn_event_loop together with the recording
+type SendCallback<T> = Box<FnMut(&T) + Send>;
+
+// Closures registered with the on_* methods, called after the handler traits
+#[derive(Default)]
+struct Callbacks {
+    account_update: Option<Callback<AccountUpdateEvent>>,
+    order_trade: Option<Callback<OrderTradeEvent>>,
+    balance_update: Option<Callback<BalanceUpdateEvent>>,
+    account_position: Option<Callback<AccountPositionEvent>>,
+    list_status: Option<Callback<OrderListStatusEvent>>,
+    margin_call: Option<Callback<MarginCallEvent>>,
+    listen_key_expired: Option<Callback<ListenKeyExpiredEvent>>,
+    futures_order_trade: Option<Callback<FuturesOrderTradeEvent>>,
+    futures_account_update: Option<Callback<FuturesAccountUpdateEvent>>,
+    agg_trade: Option<Callback<TradesEvent>>,
+    trade: Option<Callback<TradeEvent>>,
+    day_ticker: Option<Callback<Vec<DayTickerEvent>>>,
+    symbol_ticker: Option<Callback<DayTickerEvent>>,
+    mini_ticker: Option<Callback<MiniTickerEvent>>,
+    all_mini_ticker: Option<Callback<Vec<MiniTickerEvent>>>,
+    kline: Option<Callback<KlineEvent>>,
+    depth: Option<Callback<DepthOrderBookEvent>>,
+    partial_depth: Option<Callback<OrderBook>>,
+    book_ticker: Option<Callback<BookTickerEvent>>,
+    mark_price: Option<Callback<MarkPriceEvent>>,
+    liquidation: Option<Callback<LiquidationOrderEvent>>,
+    continuous_kline: Option<Callback<ContinuousKlineEvent>>,
+    keep_alive_error: Option<Callback<Error>>,
+    scheduled_reconnect: Option<Callback<Duration>>,
+    stale: Option<Callback<Duration>>,
+    recording_error: Option<SendCallback<Error>>,
+    connect: Option<Callback<ConnectionInfo>>,
+    disconnect: Option<Callback<DisconnectReason>>,
+    reconnect_attempt: Option<Callback<u32>>,
+}
+
+fn boxed<T, F>(callback: F) -> Option<Callback<T>>
+where
+    F: FnMut(&T) + 'static,
+{
+    Some(Box::new(callback))
+}
+
+fn call<T, C>(callback: &mut Option<Box<C>>, event: &T)
+where
+    C: FnMut(&T) + ?Sized,
+{
+    if let Some(ref mut callback) = *callback {
+        callback(event);
+    }
+}
+
+enum ReadOutcome {
+    Message,
+    Timeout,
+    Closed,
+}
+
+// {"method":"SUBSCRIBE","params":["btcusdt@aggTrade"],"id":1}
+#[derive(Serialize)]
+struct ControlRequest<'a> {
+    method: &'a str,
+    params: &'a [String],
+    id: u64,
+}
+
+// {"stream":"<streamName>","data":<rawPayload>} -> (rawPayload, Some(streamName)).
+// The payload is moved out of the envelope, other messages are returned as they are
+fn split_combined_stream(value: Value) -> (Value, Option<String>) {
+    match value {
+        Value::Object(mut map) => {
+            let is_combined = map.get(STREAM).map_or(false, Value::is_string) && map.contains_key(DATA);
+            if !is_combined {
+                return (Value::Object(map), None);
+            }
+            let stream = match map.remove(STREAM) {
+                Some(Value::String(stream)) => Some(stream),
+                _ => None,
+            };
+            (map.remove(DATA).unwrap_or(Value::Null), stream)
+        }
+        value => (value, None),
+    }
+}
+
+// "btcusdt@depth20" -> "BTCUSDT"
+fn symbol_of_stream(stream: &str) -> String {
+    stream.split('@').next().unwrap_or("").to_uppercase()
+}
+
+// Every payload the websocket streams deliver, serializes to the payload as received. Events this
+// crate doesn't know yet come as Unknown instead of failing
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum WebsocketEvent {
+    AccountUpdate(AccountUpdateEvent),
+    OrderTrade(OrderTradeEvent),
+    BalanceUpdate(BalanceUpdateEvent),
+    AccountPosition(AccountPositionEvent),
+    ListStatus(OrderListStatusEvent),
+    MarginCall(MarginCallEvent),
+    ListenKeyExpired(ListenKeyExpiredEvent),
+    FuturesOrderTrade(FuturesOrderTradeEvent),
+    FuturesAccountUpdate(FuturesAccountUpdateEvent),
+    AggTrade(TradesEvent),
+    Trade(TradeEvent),
+    DayTicker(Vec<DayTickerEvent>),
+    SymbolTicker(DayTickerEvent),
+    MiniTicker(MiniTickerEvent),
+    AllMiniTicker(Vec<MiniTickerEvent>),
+    Kline(KlineEvent),
+    DepthOrderBook(DepthOrderBookEvent),
+    OrderBook(OrderBook),
+    BookTicker(BookTickerEvent),
+    MarkPrice(MarkPriceEvent),
+    Liquidation(LiquidationOrderEvent),
+    ContinuousKline(ContinuousKlineEvent),
+    Unknown(Value),
+}
+
+impl WebsocketEvent {
+    // Parses a raw stream message, bare or in the combined stream envelope, the stream name is
+    // returned with the event of an envelope. Subscription acknowledgements are Unknown
+    pub fn parse(raw: &str) -> Result<(WebsocketEvent, Option<String>)> {
+        let (data, stream) = split_combined_stream(from_json(raw)?);
+        let event = parse_event(data, stream.as_deref())?;
+
+        Ok((event, stream))
+    }
+}
+
+// Parses a stream message, unwrapping the combined stream envelope. Subscription
+// acknowledgements give None
+pub(crate) fn parse_message(msg: &str, endpoint: Option<&str>) -> serde_json::Result<Option<WebsocketEvent>> {
+    Ok(parse_stream_message(msg, endpoint)?.map(|(event, _)| event))
+}
+
+// Like parse_message, with the stream name of a combined stream envelope
+pub(crate) fn parse_stream_message(
+    msg: &str, endpoint: Option<&str>,
+) -> serde_json::Result<Option<(WebsocketEvent, Option<String>)>> {
+    let value: Value = from_str(msg)?;
+
+    if value.get(REQUEST_ID).is_some() && value.get(EVENT_TYPE).is_none() {
+        return Ok(None);
+    }
+
+    let (data, stream) = split_combined_stream(value);
+    let event = parse_event(data, stream.as_deref().or(endpoint))?;
+
+    Ok(Some((event, stream)))
+}
+
+// stream is the stream name (or single stream endpoint) the payload arrived on
+fn parse_event(value: Value, stream: Option<&str>) -> serde_json::Result<WebsocketEvent> {
+    // Array payloads (e.g. !ticker@arr) share the type of their first element
+    let event_type: String = {
+        let event = match value {
+            Value::Array(ref events) => events.first(),
+            ref event => Some(event),
+        };
+        match event.and_then(|e| e.get(EVENT_TYPE)).and_then(Value::as_str) {
+            Some(event_type) => event_type.into(),
+            None => String::new(),
+        }
+    };
+
+    let event = if event_type == OUTBOUND_ACCOUNT_INFO {
+        WebsocketEvent::AccountUpdate(from_value(value)?)
+    } else if event_type == EXECUTION_REPORT {
+        WebsocketEvent::OrderTrade(from_value(value)?)
+    } else if event_type == BALANCE_UPDATE {
+        WebsocketEvent::BalanceUpdate(from_value(value)?)
+    } else if event_type == OUTBOUND_ACCOUNT_POSITION {
+        WebsocketEvent::AccountPosition(from_value(value)?)
+    } else if event_type == LIST_STATUS {
+        WebsocketEvent::ListStatus(from_value(value)?)
+    } else if event_type == MARGIN_CALL {
+        WebsocketEvent::MarginCall(from_value(value)?)
+    } else if event_type == LISTEN_KEY_EXPIRED {
+        WebsocketEvent::ListenKeyExpired(from_value(value)?)
+    } else if event_type == ORDER_TRADE_UPDATE {
+        WebsocketEvent::FuturesOrderTrade(from_value(value)?)
+    } else if event_type == FUTURES_ACCOUNT_UPDATE {
+        WebsocketEvent::FuturesAccountUpdate(from_value(value)?)
+    } else if event_type == AGGREGATED_TRADE {
+        WebsocketEvent::AggTrade(from_value(value)?)
+    } else if event_type == TRADE {
+        WebsocketEvent::Trade(from_value(value)?)
+    } else if event_type == DAYTICKER && value.is_array() {
+        WebsocketEvent::DayTicker(from_value(value)?)
+    } else if event_type == DAYTICKER {
+        WebsocketEvent::SymbolTicker(from_value(value)?)
+    } else if event_type == MINI_TICKER && value.is_array() {
+        WebsocketEvent::AllMiniTicker(from_value(value)?)
+    } else if event_type == MINI_TICKER {
+        WebsocketEvent::MiniTicker(from_value(value)?)
+    } else if event_type == KLINE {
+        WebsocketEvent::Kline(from_value(value)?)
+    } else if event_type == DEPTH_ORDERBOOK {
+        WebsocketEvent::DepthOrderBook(from_value(value)?)
+    } else if event_type == MARK_PRICE {
+        WebsocketEvent::MarkPrice(from_value(value)?)
+    } else if event_type == FORCE_ORDER {
+        WebsocketEvent::Liquidation(from_value(value)?)
+    } else if event_type == CONTINUOUS_KLINE {
+        WebsocketEvent::ContinuousKline(from_value(value)?)
+    } else if value.get(PARTIAL_ORDERBOOK).is_some() {
+        // Partial book depth snapshots carry no event type either
+        let mut partial_orderbook: OrderBook = from_value(value)?;
+        partial_orderbook.levels = stream
+            .and_then(Levels::from_stream_name)
+            .map(|levels| levels.count());
+        WebsocketEvent::OrderBook(partial_orderbook)
+    } else if event_type.is_empty() && BOOK_TICKER.iter().all(|field| value.get(field).is_some()) {
+        WebsocketEvent::BookTicker(from_value(value)?)
+    } else {
+        WebsocketEvent::Unknown(value)
+    };
+
+    Ok(event)
+}
+
+// Both the plain and the TLS stream wrap a TcpStream which carries the timeout
+pub(crate) fn set_read_timeout(socket: &WebSocket<AutoStream>, timeout: Option<Duration>) -> Result<()> {
+    match *socket.get_ref() {
+        Stream::Plain(ref stream) => stream.set_read_timeout(timeout)?,
+        Stream::Tls(ref stream) => stream.get_ref().set_read_timeout(timeout)?,
+    }
+
+    Ok(())
+}
+
+// Sends a Close frame and waits a little for the server to answer it
+pub(crate) fn close_socket(socket: &mut WebSocket<AutoStream>) -> Result<()> {
+    let timeout = Duration::from_millis(CLOSE_TIMEOUT_MS);
+    socket.close(None)?;
+    set_read_timeout(socket, Some(timeout))?;
+
+    // The acknowledgement surfaces as ConnectionClosed, anything unread is discarded
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        if socket.read_message().is_err() {
+            break;
+        }
+    }
+
+    Ok(())
 }
 
 #[derive(Default)]
+struct HealthState {
+    last_message_at: Option<Instant>,
+    messages_received: u64,
+    messages_dropped: u64,
+    connected: bool,
+    url: Option<String>,
+}
+
+// Connection statistics which can be read from other threads, see WebSockets::health
+#[derive(Clone, Default)]
+pub struct HealthHandle {
+    state: Arc<Mutex<HealthState>>,
+}
+
+impl HealthHandle {
+    // Time of the last frame of any kind, pings included
+    pub fn last_message_at(&self) -> Option<Instant> {
+        self.read(|state| state.last_message_at)
+    }
+
+    pub fn messages_received(&self) -> u64 {
+        self.read(|state| state.messages_received)
+    }
+
+    // Messages discarded by Overflow::DropOldest in event_loop_queued
+    pub fn messages_dropped(&self) -> u64 {
+        self.read(|state| state.messages_dropped)
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.read(|state| state.connected)
+    }
+
+    // Full url of the current (or last) connection
+    pub fn url(&self) -> Option<String> {
+        self.read(|state| state.url.clone())
+    }
+
+    fn read<T, F: FnOnce(&HealthState) -> T>(&self, f: F) -> T {
+        match self.state.lock() {
+            Ok(state) => f(&state),
+            Err(poisoned) => f(&poisoned.into_inner()),
+        }
+    }
+
+    fn update<F: FnOnce(&mut HealthState)>(&self, f: F) {
+        match self.state.lock() {
+            Ok(mut state) => f(&mut state),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+
+    fn message_received(&self) {
+        self.update(|state| {
+            state.last_message_at = Some(Instant::now());
+            state.messages_received += 1;
+        });
+    }
+
+    fn set_messages_dropped(&self, dropped: u64) {
+        self.update(|state| state.messages_dropped = dropped);
+    }
+
+    fn set_connected(&self, connected: bool) {
+        self.update(|state| state.connected = connected);
+    }
+
+    fn set_url(&self, url: &Url) {
+        self.update(|state| state.url = Some(url.to_string()));
+    }
+}
+
+// Returned by spawn_event_loop together with the receiving end of its queue
+pub struct EventLoopHandle {
+    stopper: QueueStopper<WebsocketEvent>,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl EventLoopHandle {
+    // Closes the connection and waits for the reader thread to finish
+    pub fn stop(mut self) -> Result<()> {
+        self.stopper.stop();
+
+        self.wait()
+    }
+
+    // Waits until the connection ends, Ok(()) after a normal close
+    pub fn join(mut self) -> Result<()> {
+        self.wait()
+    }
+
+    fn wait(&mut self) -> Result<()> {
+        match self.thread.take() {
+            Some(thread) => match thread.join() {
+                Ok(result) => result,
+                Err(_) => bail!(BinanceError::Internal("Websocket event loop thread panicked".into())),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+// Reader thread body, convert turns a text frame into the queued item
+fn run_event_loop<T, F>(
+    socket: WebSocket<AutoStream>, sender: EventSender<T>, health: HealthHandle, convert: F,
+) -> Result<()>
+where
+    F: FnMut(String) -> Result<Option<T>>,
+{
+    let result = read_into_queue(socket, sender, &health, convert);
+    health.set_connected(false);
+
+    result
+}
+
+fn read_into_queue<T, F>(
+    mut socket: WebSocket<AutoStream>, sender: EventSender<T>, health: &HealthHandle, mut convert: F,
+) -> Result<()>
+where
+    F: FnMut(String) -> Result<Option<T>>,
+{
+    loop {
+        if sender.is_stopped() {
+            return close_socket(&mut socket);
+        }
+
+        let message = socket.read_message();
+        if message.is_ok() {
+            health.message_received();
+        }
+
+        // Pings are answered by tungstenite on the next read
+        let msg: String = match message {
+            Ok(Message::Text(msg)) => msg,
+            Ok(Message::Binary(data)) => match String::from_utf8(data) {
+                Ok(msg) => msg,
+                Err(_) => continue,
+            },
+            Ok(_) => continue,
+            Err(TungsteniteError::Io(ref e))
+                if e.kind() == IoErrorKind::WouldBlock || e.kind() == IoErrorKind::TimedOut =>
+            {
+                continue;
+            }
+            Err(TungsteniteError::ConnectionClosed(_)) => return Ok(()),
+            Err(e) => bail!(e),
+        };
+
+        if let Some(item) = convert(msg)? {
+            // The receiver is gone or stop was called while the queue was full
+            if !sender.send(item) {
+                return close_socket(&mut socket);
+            }
+        }
+    }
+}
+
+// Exponential backoff used to re-establish a dropped connection
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+// Declares a connection stale when no frame, pings included, arrives within timeout.
+// Quiet streams (e.g. trades of an illiquid symbol) need a generous timeout
+#[derive(Debug, Clone)]
+pub struct Watchdog {
+    pub timeout: Duration,
+    // Close and reconnect (with the reconnect policy or its default) instead of only notifying
+    pub reconnect: bool,
+}
+
+// An established connection, see WebSockets::on_connect
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub url: String,
+    // Of the handshake response, 101 Switching Protocols
+    pub status: u16,
+    // Established by the event loop rather than by connect
+    pub reconnect: bool,
+}
+
+// Why a connection ended, see WebSockets::on_disconnect
+#[derive(Debug, Clone)]
+pub enum DisconnectReason {
+    // With the close frame of the server, if it sent one
+    ServerClosed(Option<CloseFrame<'static>>),
+    // e.g. a reset connection
+    ReadError(String),
+    // By disconnect or by dropping the WebSockets
+    Local,
+    // Replaced ahead of the 24 hour limit, see set_scheduled_reconnect
+    Expired,
+    // Replaced by the watchdog
+    Stale,
+}
+
 pub struct WebSockets {
     socket: Option<(WebSocket<AutoStream>, Response)>,
+    single_stream_url: String,
+    multi_stream_url: String,
+    url: Option<Url>,
+    reconnect_policy: Option<ReconnectPolicy>,
+    reconnects: u64,
+    close_frame: Option<CloseFrame<'static>>,
+    subscriptions: Vec<String>,
+    // Streams of the connect url since unsubscribed, a reconnect dials the url again
+    unsubscribed_url_streams: Vec<String>,
+    next_request_id: u64,
+    control_messages_sent: VecDeque<Instant>,
+    read_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    // no_proxy applies to the proxy of the environment, not to one set explicitly
+    proxy_from_env: bool,
+    health: HealthHandle,
+    connected_at: Option<Instant>,
+    scheduled_reconnect_margin: Option<Duration>,
+    watchdog: Option<Watchdog>,
+    watchdog_triggers: u64,
+    stale: bool,
     user_stream_handler: Option<Box<UserStreamEventHandler>>,
+    futures_user_stream_handler: Option<Box<FuturesUserStreamEventHandler>>,
     market_handler: Option<Box<MarketEventHandler>>,
+    market_handlers: HashMap<String, Box<MarketEventHandler>>,
+    trade_handler: Option<Box<TradeEventHandler>>,
     ticker_handler: Option<Box<DayTickerEventHandler>>,
+    mini_ticker_handler: Option<Box<MiniTickerEventHandler>>,
     kline_handler: Option<Box<KlineEventHandler>>,
+    kline_handlers: HashMap<String, Box<KlineEventHandler>>,
+    book_ticker_handler: Option<Box<BookTickerEventHandler>>,
+    mark_price_handler: Option<Box<MarkPriceEventHandler>>,
+    liquidation_handler: Option<Box<LiquidationEventHandler>>,
+    continuous_kline_handler: Option<Box<ContinuousKlineEventHandler>>,
+    combined_stream_handler: Option<Box<CombinedStreamEventHandler>>,
+    subscription_handler: Option<Box<SubscriptionEventHandler>>,
+    callbacks: Callbacks,
+    error_handler: Option<Box<ErrorEventHandler>>,
+    raw_message_handler: Option<Box<FnMut(&str)>>,
+    raw_messages_before_dispatch: bool,
+    // Takes every message instead of the handlers, see WebSocketsPool
+    forward: Option<Box<FnMut(&str)>>,
+    recorder: Option<Recorder>,
+}
+
+// Best effort close handshake, Binance counts dropped sockets against the connection limits
+impl Drop for WebSockets {
+    fn drop(&mut self) {
+        let _ = self.disconnect();
+    }
+}
+
+impl Default for WebSockets {
+    fn default() -> WebSockets {
+        WebSockets::new()
+    }
 }
 
 impl WebSockets {
     pub fn new() -> WebSockets {
+        WebSockets::new_with_endpoint(WEBSOCKET_URL)
+    }
+
+    // e.g. WebSockets::new_with_host(WebsocketHost::Futures) for the USD-M futures streams
+    pub fn new_with_host(host: WebsocketHost) -> WebSockets {
+        WebSockets::new_with_endpoint(host.base_url())
+    }
+
+    // base is the host, e.g. "wss://testnet.binance.vision" (see WebsocketHost)
+    pub fn new_with_endpoint(base: &str) -> WebSockets {
         WebSockets {
             socket: None,
+            single_stream_url: format!("{}{}", base, SINGLE_STREAM),
+            multi_stream_url: format!("{}{}", base, MULTI_STREAM),
+            url: None,
+            reconnect_policy: None,
+            reconnects: 0,
+            close_frame: None,
+            subscriptions: Vec::new(),
+            unsubscribed_url_streams: Vec::new(),
+            next_request_id: 1,
+            control_messages_sent: VecDeque::new(),
+            read_timeout: None,
+            proxy: Proxy::from_env(),
+            proxy_from_env: true,
+            health: HealthHandle::default(),
+            connected_at: None,
+            scheduled_reconnect_margin: Some(Duration::from_secs(SCHEDULED_RECONNECT_MARGIN_SECS)),
+            watchdog: None,
+            watchdog_triggers: 0,
+            stale: false,
             user_stream_handler: None,
+            futures_user_stream_handler: None,
             market_handler: None,
+            market_handlers: HashMap::new(),
+            trade_handler: None,
             ticker_handler: None,
+            mini_ticker_handler: None,
             kline_handler: None,
+            kline_handlers: HashMap::new(),
+            book_ticker_handler: None,
+            mark_price_handler: None,
+            liquidation_handler: None,
+            continuous_kline_handler: None,
+            combined_stream_handler: None,
+            subscription_handler: None,
+            callbacks: Callbacks::default(),
+            error_handler: None,
+            raw_message_handler: None,
+            raw_messages_before_dispatch: false,
+            forward: None,
+            recorder: None,
         }
     }
 
+    // Both connect and connect_multiple_streams use the new host
+    pub fn set_base_url(&mut self, base: &str) {
+        self.single_stream_url = format!("{}{}", base, SINGLE_STREAM);
+        self.multi_stream_url = format!("{}{}", base, MULTI_STREAM);
+    }
+
+    // Used by connect, connect_multiple_streams and reconnects. Defaults to https_proxy / all_proxy
+    // unless no_proxy exempts the host
+    pub fn set_proxy(&mut self, proxy: Option<Proxy>) {
+        self.proxy = proxy;
+        self.proxy_from_env = false;
+    }
+
+    // Full url prefix used by connect_multiple_streams, e.g. "wss://host/stream?streams="
+    pub fn set_multi_stream_url(&mut self, url: &str) {
+        self.multi_stream_url = url.into();
+    }
+
     pub fn connect(&mut self, endpoint: &str) -> Result<()> {
-        let wss: String = format!("{}{}", WEBSOCKET_URL, endpoint);
-        let url = Url::parse(&wss)?;
+        let wss: String = format!("{}{}", self.single_stream_url, endpoint);
 
-        match connect(url) {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+        self.connect_wss(&wss)
+    }
+
+    pub fn connect_with_proxy(&mut self, endpoint: &str, proxy: Proxy) -> Result<()> {
+        self.set_proxy(Some(proxy));
+
+        self.connect(endpoint)
     }
 
     pub fn connect_multiple_streams(&mut self, endpoints: &Vec<String>) -> Result<()> {
-        let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, endpoints.join("/"));
-        let url = Url::parse(&wss)?;
+        let wss: String = format!("{}{}", self.multi_stream_url, endpoints.join("/"));
 
-        match connect(url) {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
+        self.connect_wss(&wss)
+    }
+
+    // Connects and re-establishes the connection whenever a read fails
+    pub fn connect_with_reconnect(&mut self, endpoint: &str, policy: ReconnectPolicy) -> Result<()> {
+        self.set_reconnect_policy(policy);
+
+        self.connect(endpoint)
+    }
+
+    // Applies to both connect and connect_multiple_streams
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = Some(policy);
+    }
+
+    // Number of times the connection was re-established by the event loop
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnects
+    }
+
+    // Close frame sent by the server when it ended the last event loop
+    pub fn close_frame(&self) -> Option<&CloseFrame<'static>> {
+        self.close_frame.as_ref()
+    }
+
+    // Closes the connection, connect can be called again afterwards
+    pub fn disconnect(&mut self) -> Result<()> {
+        self.url = None;
+        self.subscriptions.clear();
+        self.unsubscribed_url_streams.clear();
+        self.health.set_connected(false);
+
+        match self.socket.take() {
+            Some((mut socket, _)) => {
+                let result = close_socket(&mut socket);
+                call(&mut self.callbacks.disconnect, &DisconnectReason::Local);
+                result
             }
+            None => Ok(()),
         }
     }
 
-    pub fn add_user_stream_handler<H>(&mut self, handler: H)
-    where
-        H: UserStreamEventHandler + 'static,
-    {
-        self.user_stream_handler = Some(Box::new(handler));
+    pub fn is_connected(&self) -> bool {
+        self.socket.is_some()
     }
 
-    pub fn add_market_handler<H>(&mut self, handler: H)
-    where
-        H: MarketEventHandler + 'static,
-    {
-        self.market_handler = Some(Box::new(handler));
+    // Shares the connection statistics with e.g. a monitoring thread
+    pub fn health(&self) -> HealthHandle {
+        self.health.clone()
     }
 
-    pub fn add_day_ticker_handler<H>(&mut self, handler: H)
-    where
-        H: DayTickerEventHandler + 'static,
-    {
-        self.ticker_handler = Some(Box::new(handler));
+    pub fn last_message_at(&self) -> Option<Instant> {
+        self.health.last_message_at()
     }
 
-    pub fn add_kline_handler<H>(&mut self, handler: H)
-    where
-        H: KlineEventHandler + 'static,
-    {
-        self.kline_handler = Some(Box::new(handler));
+    pub fn messages_received(&self) -> u64 {
+        self.health.messages_received()
     }
 
-    fn handle_msg(&self, msg: &String) {
-        if msg.find(OUTBOUND_ACCOUNT_INFO) != None {
-            let account_update: AccountUpdateEvent = from_str(msg.as_str()).unwrap();
+    // Full url of the current (or last) connection
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_ref().map(Url::as_str)
+    }
 
-            if let Some(ref h) = self.user_stream_handler {
-                h.account_update_handler(&account_update);
-            }
-        } else if msg.find(EXECUTION_REPORT) != None {
-            let order_trade: OrderTradeEvent = from_str(msg.as_str()).unwrap();
+    // Stream name of a single stream connection
+    fn endpoint(&self) -> Option<&str> {
+        self.url
+            .as_ref()
+            .and_then(|url| url.path_segments())
+            .and_then(|segments| segments.last())
+    }
 
-            if let Some(ref h) = self.user_stream_handler {
-                h.order_trade_handler(&order_trade);
-            }
-        } else if msg.find(AGGREGATED_TRADE) != None {
-            let trades: TradesEvent = from_str(msg.as_str()).unwrap();
+    // Streams named by the connect url, either the single stream or the combined ones
+    fn url_streams(&self) -> Vec<String> {
+        let combined = self.url.as_ref().and_then(|url| {
+            url.query_pairs()
+                .find(|&(ref key, _)| key == "streams")
+                .map(|(_, streams)| streams.split('/').map(String::from).collect())
+        });
 
-            if let Some(ref h) = self.market_handler {
-                h.aggregated_trades_handler(&trades);
-            }
-        } else if msg.find(DAYTICKER) != None {
-            let trades: Vec<DayTickerEvent> = from_str(msg.as_str()).unwrap();
+        match combined {
+            Some(streams) => streams,
+            None => self.endpoint().map(String::from).into_iter().collect(),
+        }
+    }
 
-            if let Some(ref h) = self.ticker_handler {
-                h.day_ticker_handler(&trades);
-            }
-        } else if msg.find(KLINE) != None {
-            let kline: KlineEvent = from_str(msg.as_str()).unwrap();
+    fn connect_wss(&mut self, wss: &str) -> Result<()> {
+        let url = Url::parse(wss)?;
 
-            if let Some(ref h) = self.kline_handler {
-                h.kline_handler(&kline);
-            }
-        } else if msg.find(PARTIAL_ORDERBOOK) != None {
-            let partial_orderbook: OrderBook = from_str(msg.as_str()).unwrap();
+        match self.open(&url) {
+            Ok(answer) => {
+                let info = ConnectionInfo {
+                    url: url.to_string(),
+                    status: (answer.1).code,
+                    reconnect: false,
+                };
+                self.socket = Some(answer);
+                self.health.set_url(&url);
+                self.health.set_connected(true);
+                self.connected_at = Some(Instant::now());
+                self.url = Some(url);
+                self.unsubscribed_url_streams.clear();
+                self.close_frame = None;
+                self.apply_read_timeout()?;
+                call(&mut self.callbacks.connect, &info);
 
-            if let Some(ref h) = self.market_handler {
-                h.partial_orderbook_handler(&partial_orderbook);
+                Ok(())
             }
-        } else if msg.find(DEPTH_ORDERBOOK) != None {
-            let depth_orderbook: DepthOrderBookEvent = from_str(msg.as_str()).unwrap();
+            Err(e) => bail!(e),
+        }
+    }
 
-            if let Some(ref h) = self.market_handler {
-                h.depth_orderbook_handler(&depth_orderbook);
-            }
-        } else if msg.find(STREAM) != None {
-            let i_msg = msg.find("\"data\":");
-            let i_end = msg.rfind("}");
-            if let (Some(i_msg_), Some(i_end_)) = (i_msg, i_end) {
-                let sub_string = msg.chars().skip(i_msg_).take(i_end_ - i_msg_ - 1).collect();
-                self.handle_msg(&sub_string);
-            };
+    fn open(&self, url: &Url) -> Result<(WebSocket<AutoStream>, Response)> {
+        let exempted = self.proxy_from_env && url.host_str().map_or(false, proxy::is_exempted);
+
+        match self.proxy {
+            Some(ref proxy) if !exempted => proxy::connect(url, proxy),
+            _ => Ok(connect(url.clone())?),
         }
     }
 
+    fn reconnect(&mut self, policy: &ReconnectPolicy) -> Result<()> {
+        let url = match self.url {
+            Some(ref url) => url.clone(),
+            None => bail!(BinanceError::Internal("No endpoint to reconnect to".into())),
+        };
+        self.socket = None;
+        self.health.set_connected(false);
 
-    pub fn event_loop(&mut self) {
-        loop {
-            let msg_opt =
-                match self.socket {
-                    Some (ref mut socket) => {
-                        let msg: String = socket.0.read_message().unwrap().into_text().unwrap().to_string();
-                        Some(msg)
-                    },
-                    None => None
-                };
-            if let Some(ref m) = msg_opt {
-                self.handle_msg(&m);
+        let mut delay = policy.base_delay;
+        let mut last_error = None;
+        for attempt in 0..policy.max_retries {
+            if attempt > 0 {
+                delay = cmp::min(delay * 2, policy.max_delay);
+            }
+            // Always wait before dialing so an unreachable host is not hammered
+            thread::sleep(delay);
+            call(&mut self.callbacks.reconnect_attempt, &(attempt + 1));
+
+            match self.open(&url) {
+                Ok(answer) => {
+                    let info = ConnectionInfo {
+                        url: url.to_string(),
+                        status: (answer.1).code,
+                        reconnect: true,
+                    };
+                    self.socket = Some(answer);
+                    self.health.set_connected(true);
+                    self.connected_at = Some(Instant::now());
+                    self.reconnects += 1;
+                    self.apply_read_timeout()?;
+                    if !self.unsubscribed_url_streams.is_empty() {
+                        let streams = self.unsubscribed_url_streams.clone();
+                        self.send_control(UNSUBSCRIBE, &streams)?;
+                    }
+                    if !self.subscriptions.is_empty() {
+                        let streams = self.subscriptions.clone();
+                        self.send_control(SUBSCRIBE, &streams)?;
+                    }
+                    call(&mut self.callbacks.connect, &info);
+                    return Ok(());
+                }
+                Err(e) => last_error = Some(e),
             }
-           // if let Some(ref mut socket) = self.socket {
-           //     let msg: String = socket.0.read_message().unwrap().into_text().unwrap();
-//
-           //     self.handle_msg(&msg);
-           // }
         }
+
+        match last_error {
+            Some(e) => bail!(e),
+            None => bail!(BinanceError::Parameter("Reconnect policy does not allow any attempt".into())),
+        }
+    }
+
+    // Both the plain and the TLS stream wrap a TcpStream which carries the timeout
+    fn apply_read_timeout(&mut self) -> Result<()> {
+        let poll = Duration::from_millis(LIFETIME_POLL_MS);
+        let timeout = match (self.read_timeout, &self.watchdog) {
+            (Some(timeout), &Some(ref watchdog)) => Some(cmp::min(timeout, watchdog.timeout)),
+            (Some(timeout), &None) => Some(timeout),
+            (None, &Some(ref watchdog)) => Some(cmp::min(poll, watchdog.timeout)),
+            (None, &None) if self.scheduled_reconnect_margin.is_some() => Some(poll),
+            (None, &None) => None,
+        };
+        if let Some(ref socket) = self.socket {
+            set_read_timeout(&socket.0, timeout)?;
+        }
+
+        Ok(())
+    }
+
+    // The event loop reconnects margin before Binance ends the connection at 24 hours
+    // (5 minutes by default), None leaves the server close to the reconnect policy
+    pub fn set_scheduled_reconnect(&mut self, margin: Option<Duration>) -> Result<()> {
+        self.scheduled_reconnect_margin = margin;
+
+        self.apply_read_timeout()
+    }
+
+    // Called by connect, and by the event loop after each reconnect on the thread running it
+    pub fn on_connect<F>(&mut self, callback: F)
+    where
+        F: FnMut(&ConnectionInfo) + 'static,
+    {
+        self.callbacks.connect = boxed(callback);
+    }
+
+    // Called by disconnect and by event_loop and event_loop_with_tick, before any reconnect.
+    // Not called for the connections of spawn_event_loop and event_loop_queued
+    pub fn on_disconnect<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DisconnectReason) + 'static,
+    {
+        self.callbacks.disconnect = boxed(callback);
+    }
+
+    // Called with the attempt number, from 1, before each dial of a reconnect
+    pub fn on_reconnect_attempt<F>(&mut self, callback: F)
+    where
+        F: FnMut(&u32) + 'static,
+    {
+        self.callbacks.reconnect_attempt = boxed(callback);
+    }
+
+    // Called with the age of the replaced connection, e.g. to mark a gap in recorded data
+    pub fn on_scheduled_reconnect<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Duration) + 'static,
+    {
+        self.callbacks.scheduled_reconnect = boxed(callback);
+    }
+
+    pub fn set_watchdog(&mut self, watchdog: Option<Watchdog>) -> Result<()> {
+        self.watchdog = watchdog;
+
+        self.apply_read_timeout()
+    }
+
+    // Called with the time since the last frame each time the watchdog fires
+    pub fn on_stale<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Duration) + 'static,
+    {
+        self.callbacks.stale = boxed(callback);
+    }
+
+    // How often the watchdog found the connection stale
+    pub fn watchdog_triggers(&self) -> u64 {
+        self.watchdog_triggers
+    }
+
+    // Fires once per silent period, returns true when the connection was replaced
+    fn check_watchdog(&mut self) -> Result<bool> {
+        let watchdog = match self.watchdog {
+            Some(ref watchdog) if !self.stale => watchdog.clone(),
+            _ => return Ok(false),
+        };
+
+        // A fresh connection counts as a frame
+        let last_frame = match self.health.last_message_at().into_iter().chain(self.connected_at).max() {
+            Some(last_frame) => last_frame,
+            None => return Ok(false),
+        };
+        let silence = last_frame.elapsed();
+        if silence < watchdog.timeout {
+            return Ok(false);
+        }
+
+        self.stale = true;
+        self.watchdog_triggers += 1;
+        call(&mut self.callbacks.stale, &silence);
+
+        if watchdog.reconnect {
+            if let Some((mut socket, _)) = self.socket.take() {
+                let _ = close_socket(&mut socket);
+                call(&mut self.callbacks.disconnect, &DisconnectReason::Stale);
+            }
+            let policy = self.reconnect_policy.clone().unwrap_or_default();
+            self.reconnect(&policy)?;
+            self.stale = false;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn connection_expiring(&self, margin: Duration) -> bool {
+        let lifetime = Duration::from_secs(CONNECTION_LIFETIME_SECS);
+
+        match self.connected_at {
+            Some(connected_at) => connected_at.elapsed() + margin >= lifetime,
+            None => false,
+        }
+    }
+
+    fn scheduled_reconnect(&mut self) -> Result<()> {
+        let age = self.connected_at.map(|at| at.elapsed()).unwrap_or_default();
+        if let Some((mut socket, _)) = self.socket.take() {
+            let _ = close_socket(&mut socket);
+            call(&mut self.callbacks.disconnect, &DisconnectReason::Expired);
+        }
+
+        let policy = self.reconnect_policy.clone().unwrap_or_default();
+        self.reconnect(&policy)?;
+        call(&mut self.callbacks.scheduled_reconnect, &age);
+
+        Ok(())
+    }
+
+    // Adds streams to the live connection, returns the request id of the acknowledgement
+    pub fn subscribe(&mut self, streams: &[String]) -> Result<u64> {
+        let id = self.send_control(SUBSCRIBE, streams)?;
+        self.unsubscribed_url_streams.retain(|stream| !streams.contains(stream));
+        for stream in streams {
+            if !self.subscriptions.contains(stream) {
+                self.subscriptions.push(stream.clone());
+            }
+        }
+
+        Ok(id)
+    }
+
+    pub fn unsubscribe(&mut self, streams: &[String]) -> Result<u64> {
+        let id = self.send_control(UNSUBSCRIBE, streams)?;
+        self.subscriptions.retain(|stream| !streams.contains(stream));
+        for stream in self.url_streams() {
+            if streams.contains(&stream) && !self.unsubscribed_url_streams.contains(&stream) {
+                self.unsubscribed_url_streams.push(stream);
+            }
+        }
+
+        Ok(id)
+    }
+
+    // The active streams are delivered to the subscription handler
+    pub fn list_subscriptions(&mut self) -> Result<u64> {
+        self.send_control(LIST_SUBSCRIPTIONS, &[])
+    }
+
+    fn send_control(&mut self, method: &str, params: &[String]) -> Result<u64> {
+        let id = self.next_request_id;
+        let request = to_string(&ControlRequest { method, params, id })?;
+
+        // Wait for a free slot rather than exceeding the control message rate limit
+        let second = Duration::from_secs(1);
+        while let Some(sent) = self.control_messages_sent.front().cloned() {
+            if sent.elapsed() >= second {
+                self.control_messages_sent.pop_front();
+            } else if self.control_messages_sent.len() >= MAX_CONTROL_MESSAGES_PER_SECOND {
+                thread::sleep(second - sent.elapsed());
+            } else {
+                break;
+            }
+        }
+
+        match self.socket {
+            Some(ref mut socket) => socket.0.write_message(Message::Text(request))?,
+            None => bail!(BinanceError::Internal("Websocket is not connected".into())),
+        }
+        self.control_messages_sent.push_back(Instant::now());
+        self.next_request_id += 1;
+
+        Ok(id)
+    }
+
+    pub fn add_user_stream_handler<H>(&mut self, handler: H)
+    where
+        H: UserStreamEventHandler + 'static,
+    {
+        self.user_stream_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_futures_user_stream_handler<H>(&mut self, handler: H)
+    where
+        H: FuturesUserStreamEventHandler + 'static,
+    {
+        self.futures_user_stream_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_market_handler<H>(&mut self, handler: H)
+    where
+        H: MarketEventHandler + 'static,
+    {
+        self.market_handler = Some(Box::new(handler));
+    }
+
+    // Receives the events of one symbol instead of the handler added with add_market_handler
+    pub fn add_market_handler_for<H>(&mut self, symbol: &str, handler: H)
+    where
+        H: MarketEventHandler + 'static,
+    {
+        self.market_handlers.insert(symbol.to_uppercase(), Box::new(handler));
+    }
+
+    pub fn add_trade_handler<H>(&mut self, handler: H)
+    where
+        H: TradeEventHandler + 'static,
+    {
+        self.trade_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_day_ticker_handler<H>(&mut self, handler: H)
+    where
+        H: DayTickerEventHandler + 'static,
+    {
+        self.ticker_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_mini_ticker_handler<H>(&mut self, handler: H)
+    where
+        H: MiniTickerEventHandler + 'static,
+    {
+        self.mini_ticker_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_kline_handler<H>(&mut self, handler: H)
+    where
+        H: KlineEventHandler + 'static,
+    {
+        self.kline_handler = Some(Box::new(handler));
+    }
+
+    // Receives the klines of one symbol instead of the handler added with add_kline_handler
+    pub fn add_kline_handler_for<H>(&mut self, symbol: &str, handler: H)
+    where
+        H: KlineEventHandler + 'static,
+    {
+        self.kline_handlers.insert(symbol.to_uppercase(), Box::new(handler));
+    }
+
+    pub fn add_book_ticker_handler<H>(&mut self, handler: H)
+    where
+        H: BookTickerEventHandler + 'static,
+    {
+        self.book_ticker_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_mark_price_handler<H>(&mut self, handler: H)
+    where
+        H: MarkPriceEventHandler + 'static,
+    {
+        self.mark_price_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_liquidation_handler<H>(&mut self, handler: H)
+    where
+        H: LiquidationEventHandler + 'static,
+    {
+        self.liquidation_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_continuous_kline_handler<H>(&mut self, handler: H)
+    where
+        H: ContinuousKlineEventHandler + 'static,
+    {
+        self.continuous_kline_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_subscription_handler<H>(&mut self, handler: H)
+    where
+        H: SubscriptionEventHandler + 'static,
+    {
+        self.subscription_handler = Some(Box::new(handler));
+    }
+
+    pub fn add_combined_stream_handler<H>(&mut self, handler: H)
+    where
+        H: CombinedStreamEventHandler + 'static,
+    {
+        self.combined_stream_handler = Some(Box::new(handler));
+    }
+
+    // Closure based alternatives to the handler traits
+    pub fn on_account_update<F>(&mut self, callback: F)
+    where
+        F: FnMut(&AccountUpdateEvent) + 'static,
+    {
+        self.callbacks.account_update = boxed(callback);
+    }
+
+    pub fn on_order_trade<F>(&mut self, callback: F)
+    where
+        F: FnMut(&OrderTradeEvent) + 'static,
+    {
+        self.callbacks.order_trade = boxed(callback);
+    }
+
+    pub fn on_balance_update<F>(&mut self, callback: F)
+    where
+        F: FnMut(&BalanceUpdateEvent) + 'static,
+    {
+        self.callbacks.balance_update = boxed(callback);
+    }
+
+    pub fn on_account_position<F>(&mut self, callback: F)
+    where
+        F: FnMut(&AccountPositionEvent) + 'static,
+    {
+        self.callbacks.account_position = boxed(callback);
+    }
+
+    pub fn on_list_status<F>(&mut self, callback: F)
+    where
+        F: FnMut(&OrderListStatusEvent) + 'static,
+    {
+        self.callbacks.list_status = boxed(callback);
+    }
+
+    pub fn on_margin_call<F>(&mut self, callback: F)
+    where
+        F: FnMut(&MarginCallEvent) + 'static,
+    {
+        self.callbacks.margin_call = boxed(callback);
+    }
+
+    pub fn on_listen_key_expired<F>(&mut self, callback: F)
+    where
+        F: FnMut(&ListenKeyExpiredEvent) + 'static,
+    {
+        self.callbacks.listen_key_expired = boxed(callback);
+    }
+
+    pub fn on_futures_order_trade<F>(&mut self, callback: F)
+    where
+        F: FnMut(&FuturesOrderTradeEvent) + 'static,
+    {
+        self.callbacks.futures_order_trade = boxed(callback);
+    }
+
+    pub fn on_futures_account_update<F>(&mut self, callback: F)
+    where
+        F: FnMut(&FuturesAccountUpdateEvent) + 'static,
+    {
+        self.callbacks.futures_account_update = boxed(callback);
+    }
+
+    pub fn on_agg_trade<F>(&mut self, callback: F)
+    where
+        F: FnMut(&TradesEvent) + 'static,
+    {
+        self.callbacks.agg_trade = boxed(callback);
+    }
+
+    pub fn on_trade<F>(&mut self, callback: F)
+    where
+        F: FnMut(&TradeEvent) + 'static,
+    {
+        self.callbacks.trade = boxed(callback);
+    }
+
+    pub fn on_day_ticker<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Vec<DayTickerEvent>) + 'static,
+    {
+        self.callbacks.day_ticker = boxed(callback);
+    }
+
+    pub fn on_symbol_ticker<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DayTickerEvent) + 'static,
+    {
+        self.callbacks.symbol_ticker = boxed(callback);
+    }
+
+    pub fn on_mini_ticker<F>(&mut self, callback: F)
+    where
+        F: FnMut(&MiniTickerEvent) + 'static,
+    {
+        self.callbacks.mini_ticker = boxed(callback);
+    }
+
+    // !miniTicker@arr
+    pub fn on_all_mini_ticker<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Vec<MiniTickerEvent>) + 'static,
+    {
+        self.callbacks.all_mini_ticker = boxed(callback);
+    }
+
+    pub fn on_kline<F>(&mut self, callback: F)
+    where
+        F: FnMut(&KlineEvent) + 'static,
+    {
+        self.callbacks.kline = boxed(callback);
+    }
+
+    pub fn on_depth<F>(&mut self, callback: F)
+    where
+        F: FnMut(&DepthOrderBookEvent) + 'static,
+    {
+        self.callbacks.depth = boxed(callback);
+    }
+
+    pub fn on_partial_depth<F>(&mut self, callback: F)
+    where
+        F: FnMut(&OrderBook) + 'static,
+    {
+        self.callbacks.partial_depth = boxed(callback);
+    }
+
+    pub fn on_book_ticker<F>(&mut self, callback: F)
+    where
+        F: FnMut(&BookTickerEvent) + 'static,
+    {
+        self.callbacks.book_ticker = boxed(callback);
+    }
+
+    pub fn on_mark_price<F>(&mut self, callback: F)
+    where
+        F: FnMut(&MarkPriceEvent) + 'static,
+    {
+        self.callbacks.mark_price = boxed(callback);
+    }
+
+    pub fn on_liquidation<F>(&mut self, callback: F)
+    where
+        F: FnMut(&LiquidationOrderEvent) + 'static,
+    {
+        self.callbacks.liquidation = boxed(callback);
+    }
+
+    pub fn on_continuous_kline<F>(&mut self, callback: F)
+    where
+        F: FnMut(&ContinuousKlineEvent) + 'static,
+    {
+        self.callbacks.continuous_kline = boxed(callback);
+    }
+
+    // Called when a listen key keepalive or close of event_loop_with_keep_alive fails
+    pub fn on_keep_alive_error<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Error) + 'static,
+    {
+        self.callbacks.keep_alive_error = boxed(callback);
+    }
+
+    // Called when writing the recording fails, recording is stopped and the stream goes on. Send as
+    // spawn_event_loop records on its reader thread
+    pub fn on_recording_error<F>(&mut self, callback: F)
+    where
+        F: FnMut(&Error) + Send + 'static,
+    {
+        self.callbacks.recording_error = Some(Box::new(callback));
+    }
+
+    // Appends every received text frame to path, see recording::replay_from
+    pub fn record_to<P>(&mut self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.record_to_with_rotation(path, Rotation::Never)
+    }
+
+    pub fn record_to_with_rotation<P>(&mut self, path: P, rotation: Rotation) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.recorder = Some(Recorder::open(path.as_ref(), rotation)?);
+
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
+    }
+
+    fn record(&mut self, msg: &str) {
+        let result = match self.recorder {
+            Some(ref mut recorder) => recorder.record(msg),
+            None => return,
+        };
+        if let Err(e) = result {
+            self.recorder = None;
+            call(&mut self.callbacks.recording_error, &e);
+        }
+    }
+
+    pub fn add_error_handler<H>(&mut self, handler: H)
+    where
+        H: ErrorEventHandler + 'static,
+    {
+        self.error_handler = Some(Box::new(handler));
+    }
+
+    // Receives the untouched text of every message no handler was found for,
+    // including the combined stream wrapper
+    pub fn add_raw_message_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&str) + 'static,
+    {
+        self.raw_message_handler = Some(Box::new(handler));
+    }
+
+    // When set the raw message handler sees every message before it is dispatched, e.g. for logging
+    pub fn set_raw_messages_before_dispatch(&mut self, before_dispatch: bool) {
+        self.raw_messages_before_dispatch = before_dispatch;
+    }
+
+    // Routes a raw payload exactly like the event loop does, e.g. to replay a recorded session
+    pub fn process_message(&mut self, msg: &str) -> Result<()> {
+        self.handle_msg(msg)
+    }
+
+    pub(crate) fn set_forward<F>(&mut self, forward: F)
+    where
+        F: FnMut(&str) + 'static,
+    {
+        self.forward = Some(Box::new(forward));
+    }
+
+    fn handle_msg(&mut self, msg: &str) -> Result<()> {
+        if let Some(ref mut forward) = self.forward {
+            forward(msg);
+            return Ok(());
+        }
+
+        if self.raw_messages_before_dispatch {
+            if let Some(ref mut h) = self.raw_message_handler {
+                h(msg);
+            }
+        }
+
+        match self.dispatch_msg(msg) {
+            Ok(true) => {}
+            Ok(false) => {
+                if !self.raw_messages_before_dispatch {
+                    if let Some(ref mut h) = self.raw_message_handler {
+                        h(msg);
+                    }
+                }
+            }
+            Err(e) => match self.error_handler {
+                Some(ref mut h) => h.error_handler(msg, &e),
+                None => bail!(e),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn dispatch_msg(&mut self, msg: &str) -> serde_json::Result<bool> {
+        let value: Value = from_str(msg)?;
+
+        if value.get(REQUEST_ID).is_some() && value.get(EVENT_TYPE).is_none() {
+            let response: SubscriptionResponse = from_value(value)?;
+
+            if let Some(ref mut h) = self.subscription_handler {
+                h.subscription_handler(&response);
+            }
+            return Ok(true);
+        }
+
+        let (data, stream) = split_combined_stream(value);
+        self.dispatch_value(data, stream.as_ref().map(String::as_str))
+    }
+
+    // Returns false when the payload matches none of the known events
+    fn dispatch_value(&mut self, value: Value, stream: Option<&str>) -> serde_json::Result<bool> {
+        // Partial book depth payloads only reveal their depth through the stream name
+        let stream_name: Option<String> = match stream {
+            Some(stream) => Some(stream.into()),
+            None => self.endpoint().map(String::from),
+        };
+
+        match parse_event(value, stream_name.as_ref().map(String::as_str))? {
+            WebsocketEvent::Unknown(_) => Ok(false),
+            event => {
+                self.dispatch_event(&event, stream);
+                Ok(true)
+            }
+        }
+    }
+
+    fn market_handler_for(&mut self, symbol: &str) -> Option<&mut Box<MarketEventHandler>> {
+        if self.market_handlers.contains_key(symbol) {
+            self.market_handlers.get_mut(symbol)
+        } else {
+            self.market_handler.as_mut()
+        }
+    }
+
+    fn dispatch_event(&mut self, event: &WebsocketEvent, stream: Option<&str>) {
+        match *event {
+            WebsocketEvent::AccountUpdate(ref account_update) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.account_update_handler(account_update);
+                }
+                call(&mut self.callbacks.account_update, account_update);
+            }
+            WebsocketEvent::OrderTrade(ref order_trade) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.order_trade_handler(order_trade);
+                }
+                call(&mut self.callbacks.order_trade, order_trade);
+            }
+            WebsocketEvent::BalanceUpdate(ref balance_update) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.balance_update_handler(balance_update);
+                }
+                call(&mut self.callbacks.balance_update, balance_update);
+            }
+            WebsocketEvent::AccountPosition(ref account_position) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.account_position_handler(account_position);
+                }
+                call(&mut self.callbacks.account_position, account_position);
+            }
+            WebsocketEvent::ListStatus(ref list_status) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.list_status_handler(list_status);
+                }
+                call(&mut self.callbacks.list_status, list_status);
+            }
+            WebsocketEvent::MarginCall(ref margin_call) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.margin_call_handler(margin_call);
+                }
+                if let Some(ref mut h) = self.futures_user_stream_handler {
+                    h.margin_call_handler(margin_call);
+                }
+                call(&mut self.callbacks.margin_call, margin_call);
+            }
+            WebsocketEvent::ListenKeyExpired(ref listen_key_expired) => {
+                if let Some(ref mut h) = self.user_stream_handler {
+                    h.listen_key_expired_handler(listen_key_expired);
+                }
+                if let Some(ref mut h) = self.futures_user_stream_handler {
+                    h.listen_key_expired_handler(listen_key_expired);
+                }
+                call(&mut self.callbacks.listen_key_expired, listen_key_expired);
+            }
+            WebsocketEvent::FuturesOrderTrade(ref order_trade) => {
+                if let Some(ref mut h) = self.futures_user_stream_handler {
+                    h.order_trade_update_handler(order_trade);
+                }
+                call(&mut self.callbacks.futures_order_trade, order_trade);
+            }
+            WebsocketEvent::FuturesAccountUpdate(ref account_update) => {
+                if let Some(ref mut h) = self.futures_user_stream_handler {
+                    h.account_update_handler(account_update);
+                }
+                call(&mut self.callbacks.futures_account_update, account_update);
+            }
+            WebsocketEvent::AggTrade(ref trades) => {
+                if let Some(h) = self.market_handler_for(&trades.symbol) {
+                    h.aggregated_trades_handler(trades);
+                }
+                call(&mut self.callbacks.agg_trade, trades);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.aggregated_trades_handler(stream, trades);
+                }
+            }
+            WebsocketEvent::Trade(ref trade) => {
+                if let Some(ref mut h) = self.trade_handler {
+                    h.trade_handler(trade);
+                }
+                call(&mut self.callbacks.trade, trade);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.trade_handler(stream, trade);
+                }
+            }
+            WebsocketEvent::DayTicker(ref tickers) => {
+                if let Some(ref mut h) = self.ticker_handler {
+                    h.day_ticker_handler(tickers);
+                }
+                call(&mut self.callbacks.day_ticker, tickers);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.day_ticker_handler(stream, tickers);
+                }
+            }
+            WebsocketEvent::SymbolTicker(ref ticker) => {
+                if let Some(ref mut h) = self.ticker_handler {
+                    h.symbol_ticker_handler(ticker);
+                }
+                call(&mut self.callbacks.symbol_ticker, ticker);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.symbol_ticker_handler(stream, ticker);
+                }
+            }
+            WebsocketEvent::AllMiniTicker(ref mini_tickers) => {
+                if let Some(ref mut h) = self.mini_ticker_handler {
+                    h.all_mini_ticker_handler(mini_tickers);
+                }
+                call(&mut self.callbacks.all_mini_ticker, mini_tickers);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.all_mini_ticker_handler(stream, mini_tickers);
+                }
+            }
+            WebsocketEvent::MiniTicker(ref mini_ticker) => {
+                if let Some(ref mut h) = self.mini_ticker_handler {
+                    h.mini_ticker_handler(mini_ticker);
+                }
+                call(&mut self.callbacks.mini_ticker, mini_ticker);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.mini_ticker_handler(stream, mini_ticker);
+                }
+            }
+            WebsocketEvent::Kline(ref kline) => {
+                let h = if self.kline_handlers.contains_key(&kline.symbol) {
+                    self.kline_handlers.get_mut(&kline.symbol)
+                } else {
+                    self.kline_handler.as_mut()
+                };
+                if let Some(h) = h {
+                    h.kline_handler(kline);
+                }
+                call(&mut self.callbacks.kline, kline);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.kline_handler(stream, kline);
+                }
+            }
+            WebsocketEvent::DepthOrderBook(ref depth_orderbook) => {
+                if let Some(h) = self.market_handler_for(&depth_orderbook.symbol) {
+                    h.depth_orderbook_handler(depth_orderbook);
+                }
+                call(&mut self.callbacks.depth, depth_orderbook);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.depth_orderbook_handler(stream, depth_orderbook);
+                }
+            }
+            WebsocketEvent::OrderBook(ref partial_orderbook) => {
+                // Partial book payloads carry no symbol, it is taken from the stream name
+                let symbol = match stream {
+                    Some(stream) => symbol_of_stream(stream),
+                    None => self.endpoint().map(symbol_of_stream).unwrap_or_default(),
+                };
+                if let Some(h) = self.market_handler_for(&symbol) {
+                    h.partial_orderbook_handler(partial_orderbook);
+                }
+                call(&mut self.callbacks.partial_depth, partial_orderbook);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.partial_orderbook_handler(stream, partial_orderbook);
+                }
+            }
+            WebsocketEvent::BookTicker(ref book_ticker) => {
+                if let Some(ref mut h) = self.book_ticker_handler {
+                    h.book_ticker_handler(book_ticker);
+                }
+                call(&mut self.callbacks.book_ticker, book_ticker);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.book_ticker_handler(stream, book_ticker);
+                }
+            }
+            WebsocketEvent::MarkPrice(ref mark_price) => {
+                if let Some(ref mut h) = self.mark_price_handler {
+                    h.mark_price_handler(mark_price);
+                }
+                call(&mut self.callbacks.mark_price, mark_price);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.mark_price_handler(stream, mark_price);
+                }
+            }
+            WebsocketEvent::Liquidation(ref liquidation) => {
+                if let Some(ref mut h) = self.liquidation_handler {
+                    h.liquidation_handler(liquidation);
+                }
+                call(&mut self.callbacks.liquidation, liquidation);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.liquidation_handler(stream, liquidation);
+                }
+            }
+            WebsocketEvent::ContinuousKline(ref continuous_kline) => {
+                if let Some(ref mut h) = self.continuous_kline_handler {
+                    h.continuous_kline_handler(continuous_kline);
+                }
+                call(&mut self.callbacks.continuous_kline, continuous_kline);
+                if let (&mut Some(ref mut h), Some(stream)) = (&mut self.combined_stream_handler, stream) {
+                    h.continuous_kline_handler(stream, continuous_kline);
+                }
+            }
+            // Handed to the raw message handler by dispatch_value
+            WebsocketEvent::Unknown(_) => {}
+        }
+    }
+
+    // Runs until the server closes the connection (see close_frame) or an error occurs.
+    // With a reconnect policy a server close is treated like any other drop.
+    pub fn event_loop(&mut self) -> Result<()> {
+        loop {
+            if let ReadOutcome::Closed = self.read_once()? {
+                return Ok(());
+            }
+        }
+    }
+
+    // Like event_loop, but calls on_tick at least every interval even when no message arrives.
+    // Returning false from on_tick ends the loop, the read timeout is the previous one again
+    pub fn event_loop_with_tick<F>(&mut self, interval: Duration, on_tick: F) -> Result<()>
+        where F: FnMut(&mut WebSockets) -> bool
+    {
+        let previous = self.read_timeout.replace(interval);
+        let result = self.apply_read_timeout().and_then(|()| self.tick_loop(interval, on_tick));
+        self.read_timeout = previous;
+        let restored = self.apply_read_timeout();
+
+        result.and(restored)
+    }
+
+    fn tick_loop<F>(&mut self, interval: Duration, mut on_tick: F) -> Result<()>
+        where F: FnMut(&mut WebSockets) -> bool
+    {
+        let mut last_tick = Instant::now();
+        loop {
+            if let ReadOutcome::Closed = self.read_once()? {
+                return Ok(());
+            }
+
+            if last_tick.elapsed() >= interval {
+                last_tick = Instant::now();
+                if !on_tick(self) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // Runs the event loop of a user data stream, renewing the listen key every 30 minutes.
+    // The listen key is closed once the loop exits. Spot listen keys only, margin streams renew
+    // theirs with UserStream::keep_alive_margin from event_loop_with_tick
+    pub fn event_loop_with_keep_alive(&mut self, user_stream: &UserStream, listen_key: &str) -> Result<()> {
+        let interval = Duration::from_secs(KEEP_ALIVE_INTERVAL_SECS);
+
+        let result = self.event_loop_with_tick(interval, |web_socket| {
+            if let Err(e) = user_stream.keep_alive(listen_key) {
+                call(&mut web_socket.callbacks.keep_alive_error, &e);
+            }
+            true
+        });
+
+        if let Err(e) = user_stream.close(listen_key) {
+            call(&mut self.callbacks.keep_alive_error, &e);
+        }
+
+        result
+    }
+
+    // Moves the connection to a reader thread which queues every event, this WebSockets is
+    // disconnected afterwards. The queue blocks the reader when full, see spawn_event_loop_with_queue
+    pub fn spawn_event_loop(&mut self) -> Result<(EventLoopHandle, EventReceiver<WebsocketEvent>)> {
+        self.spawn_event_loop_with_queue(DEFAULT_QUEUE_CAPACITY, Overflow::Block)
+    }
+
+    // Depth streams burst, Overflow::DropOldest keeps the reader from falling behind the server
+    pub fn spawn_event_loop_with_queue(
+        &mut self, capacity: usize, overflow: Overflow,
+    ) -> Result<(EventLoopHandle, EventReceiver<WebsocketEvent>)> {
+        let endpoint = self.endpoint().map(String::from);
+        let socket = match self.socket.take() {
+            Some((socket, _)) => socket,
+            None => bail!(BinanceError::WebsocketClosed { frame: self.close_frame.clone() }),
+        };
+        set_read_timeout(&socket, Some(Duration::from_millis(STOP_POLL_MS)))?;
+
+        let (sender, receiver) = bounded(capacity, overflow);
+        let stopper = sender.stopper();
+        let health = self.health.clone();
+        // The recording moves to the reader thread with its error callback, see record
+        let mut recorder = self.recorder.take();
+        let mut recording_error = self.callbacks.recording_error.take();
+        let thread = thread::spawn(move || {
+            run_event_loop(socket, sender, health, |msg| {
+                let result = match recorder {
+                    Some(ref mut recorder) => recorder.record(&msg),
+                    None => Ok(()),
+                };
+                if let Err(e) = result {
+                    recorder = None;
+                    call(&mut recording_error, &e);
+                }
+                Ok(parse_message(&msg, endpoint.as_ref().map(String::as_str))?)
+            })
+        });
+
+        Ok((EventLoopHandle { stopper, thread: Some(thread) }, receiver))
+    }
+
+    // Reads frames on a separate thread into a bounded queue so slow handlers cannot stall the
+    // socket, handlers still run on the calling thread. Runs until the connection ends; reconnects
+    // and the watchdog do not apply in this mode. Dropped messages show in HealthHandle
+    pub fn event_loop_queued(&mut self, capacity: usize, overflow: Overflow) -> Result<()> {
+        let socket = match self.socket.take() {
+            Some((socket, _)) => socket,
+            None => bail!(BinanceError::WebsocketClosed { frame: self.close_frame.clone() }),
+        };
+        set_read_timeout(&socket, Some(Duration::from_millis(STOP_POLL_MS)))?;
+
+        let (sender, receiver) = bounded(capacity, overflow);
+        let stopper = sender.stopper();
+        let health = self.health.clone();
+        let reader = thread::spawn(move || run_event_loop(socket, sender, health, |msg| Ok(Some(msg))));
+
+        let mut result = Ok(());
+        while let Some(msg) = receiver.recv() {
+            self.health.set_messages_dropped(receiver.dropped());
+            self.record(&msg);
+            if let Err(e) = self.handle_msg(&msg) {
+                result = Err(e);
+                break;
+            }
+        }
+        self.health.set_messages_dropped(receiver.dropped());
+
+        // Stops the reader when a handler failed, it has already ended otherwise
+        stopper.stop();
+        drop(receiver);
+        let read_result = match reader.join() {
+            Ok(read_result) => read_result,
+            Err(_) => bail!(BinanceError::Internal("Websocket reader thread panicked".into())),
+        };
+
+        result.and(read_result)
+    }
+
+    fn read_once(&mut self) -> Result<ReadOutcome> {
+        if let Some(margin) = self.scheduled_reconnect_margin {
+            if self.socket.is_some() && self.connection_expiring(margin) {
+                self.scheduled_reconnect()?;
+                return Ok(ReadOutcome::Message);
+            }
+        }
+
+        if self.socket.is_some() && self.check_watchdog()? {
+            return Ok(ReadOutcome::Message);
+        }
+
+        let message = match self.socket {
+            Some(ref mut socket) => socket.0.read_message(),
+            None => {
+                bail!(BinanceError::WebsocketClosed { frame: self.close_frame.clone() });
+            }
+        };
+        if message.is_ok() {
+            self.health.message_received();
+            self.stale = false;
+        }
+
+        let msg: String = match message {
+            Ok(Message::Text(msg)) => msg,
+            Ok(Message::Binary(data)) => match String::from_utf8(data) {
+                Ok(msg) => msg,
+                Err(_) => return Ok(ReadOutcome::Message),
+            },
+            // tungstenite queues the Pong of a Ping and sends it with the next read
+            Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => return Ok(ReadOutcome::Message),
+            // A read timeout leaves the connection intact, tungstenite resumes the partial frame
+            Err(TungsteniteError::Io(ref e))
+                if e.kind() == IoErrorKind::WouldBlock || e.kind() == IoErrorKind::TimedOut =>
+            {
+                return Ok(ReadOutcome::Timeout);
+            }
+            Err(e) => {
+                // The 24 hour disconnect is expected, not an error, even if it comes a little early
+                if let TungsteniteError::ConnectionClosed(_) = e {
+                    if let Some(margin) = self.scheduled_reconnect_margin {
+                        let tolerance = Duration::from_secs(SCHEDULED_RECONNECT_MARGIN_SECS);
+                        if self.connection_expiring(margin + tolerance) {
+                            self.scheduled_reconnect()?;
+                            return Ok(ReadOutcome::Message);
+                        }
+                    }
+                }
+                let reason = match e {
+                    TungsteniteError::ConnectionClosed(ref frame) => DisconnectReason::ServerClosed(frame.clone()),
+                    ref e => DisconnectReason::ReadError(e.to_string()),
+                };
+                call(&mut self.callbacks.disconnect, &reason);
+                if let Some(policy) = self.reconnect_policy.clone() {
+                    self.reconnect(&policy)?;
+                    return Ok(ReadOutcome::Message);
+                }
+                match e {
+                    TungsteniteError::ConnectionClosed(frame) => {
+                        self.socket = None;
+                        self.health.set_connected(false);
+                        self.close_frame = frame;
+                        return Ok(ReadOutcome::Closed);
+                    }
+                    e => {
+                        // A broken socket is not read again, is_connected tells it is gone
+                        self.socket = None;
+                        self.health.set_connected(false);
+                        bail!(e);
+                    }
+                }
+            }
+        };
+        self.record(&msg);
+        self.handle_msg(&msg)?;
+
+        Ok(ReadOutcome::Message)
+    }
+}
+
+#[cfg(test)]
+mod fixtures;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use recording::{replay_from, ReplaySpeed};
+    use std::cell::RefCell;
+    use std::{env, fs, process};
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+    use std::rc::Rc;
+    use tungstenite::{accept, accept_hdr};
+    use tungstenite::handshake::server::Request;
+    use tungstenite::protocol::frame::coding::CloseCode;
+
+    // Serves one websocket connection on a local port, the url is the base of new_with_endpoint
+    fn serve<F>(session: F) -> (String, JoinHandle<()>)
+    where
+        F: FnOnce(WebSocket<TcpStream>) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("ws://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            match accept(stream) {
+                Ok(socket) => session(socket),
+                Err(_) => panic!("websocket handshake failed"),
+            }
+        });
+
+        (base, server)
+    }
+
+    fn local(base: &str) -> WebSockets {
+        let mut web_socket = WebSockets::new_with_endpoint(base);
+        web_socket.set_proxy(None);
+        web_socket
+    }
+
+    #[test]
+    fn endpoint_base_is_the_host_dialed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("ws://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut paths = Vec::new();
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut path = String::new();
+                let mut socket = accept_hdr(stream, |request: &Request| {
+                    path = request.path.clone();
+                    Ok(None)
+                }).unwrap();
+                paths.push(path);
+                while socket.read_message().is_ok() {}
+            }
+            paths
+        });
+
+        let mut web_socket = local(&base);
+        web_socket.connect("btcusdt@trade").unwrap();
+        assert_eq!(web_socket.url(), Some(format!("{}/ws/btcusdt@trade", base).as_str()));
+        web_socket.disconnect().unwrap();
+        web_socket.connect_multiple_streams(&vec!["btcusdt@trade".into(), "ethusdt@trade".into()]).unwrap();
+        web_socket.disconnect().unwrap();
+
+        let paths = server.join().unwrap();
+        assert_eq!(paths, vec!["/ws/btcusdt@trade", "/stream?streams=btcusdt@trade/ethusdt@trade"]);
+    }
+
+    #[test]
+    fn broken_frame_ends_the_event_loop_with_an_error() {
+        let (base, server) = serve(|mut socket| {
+            // Text frame with the reserved bits set
+            socket.get_mut().write_all(&[0xF1, 0x00]).unwrap();
+        });
+        let mut web_socket = local(&base);
+        web_socket.connect("btcusdt@trade").unwrap();
+
+        match web_socket.event_loop() {
+            Err(BinanceError::Websocket(TungsteniteError::Protocol(_))) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn broken_frame_disconnects() {
+        let (base, server) = serve(|mut socket| {
+            socket.get_mut().write_all(&[0xF1, 0x00]).unwrap();
+        });
+        let mut web_socket = local(&base);
+        web_socket.connect("btcusdt@trade").unwrap();
+        let health = web_socket.health();
+        assert!(web_socket.is_connected() && health.is_connected());
+
+        assert!(web_socket.event_loop().is_err());
+        assert!(!web_socket.is_connected());
+        assert!(!health.is_connected());
+        match web_socket.event_loop() {
+            Err(BinanceError::WebsocketClosed { .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reconnect_keeps_unsubscribed_url_streams_away() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("ws://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut first = accept(listener.accept().unwrap().0).unwrap();
+            first.read_message().unwrap();
+            drop(first);
+
+            let mut second = accept(listener.accept().unwrap().0).unwrap();
+            match second.read_message().unwrap() {
+                Message::Text(request) => {
+                    assert!(request.contains("UNSUBSCRIBE") && request.contains("btcusdt@trade"));
+                    assert!(!request.contains("ethusdt@trade"));
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        });
+        let mut web_socket = local(&base);
+        web_socket.set_reconnect_policy(ReconnectPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+        });
+        let streams = vec!["btcusdt@trade".to_string(), "ethusdt@trade".to_string()];
+        web_socket.connect_multiple_streams(&streams).unwrap();
+        web_socket.unsubscribe(&streams[..1]).unwrap();
+
+        // The second connection is dropped too and the listener is gone by then
+        assert!(web_socket.event_loop().is_err());
+        assert_eq!(web_socket.reconnect_count(), 1);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn tick_loop_restores_the_read_timeout() {
+        let (base, server) = serve(|mut socket| while socket.read_message().is_ok() {});
+        let mut web_socket = local(&base);
+        web_socket.set_scheduled_reconnect(None).unwrap();
+        web_socket.connect("btcusdt@trade").unwrap();
+
+        web_socket.event_loop_with_tick(Duration::from_millis(10), |_| false).unwrap();
+        assert_eq!(web_socket.read_timeout, None);
+        match web_socket.socket.as_ref().map(|socket| socket.0.get_ref()) {
+            Some(&Stream::Plain(ref stream)) => assert_eq!(stream.read_timeout().unwrap(), None),
+            _ => panic!("no plain socket"),
+        }
+        web_socket.disconnect().unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn server_close_ends_the_event_loop() {
+        let (base, server) = serve(|mut socket| {
+            let frame = CloseFrame { code: CloseCode::Away, reason: "maintenance".into() };
+            socket.close(Some(frame)).unwrap();
+            while socket.read_message().is_ok() {}
+        });
+        let mut web_socket = local(&base);
+        web_socket.connect("btcusdt@trade").unwrap();
+
+        web_socket.event_loop().unwrap();
+        assert!(!web_socket.is_connected());
+        assert_eq!(web_socket.close_frame().map(|frame| frame.code), Some(CloseCode::Away));
+        match web_socket.event_loop() {
+            Err(BinanceError::WebsocketClosed { frame: Some(_) }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn truncated_json_is_an_error() {
+        let mut web_socket = WebSockets::new();
+
+        match web_socket.process_message(r#"{"e":"aggTrade","E":1499404907056,"s":"ETHB"#) {
+            Err(BinanceError::Json { .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_event_type_goes_to_the_raw_message_handler() {
+        let raw = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = WebSockets::new();
+        let received = raw.clone();
+        web_socket.add_raw_message_handler(move |msg| received.borrow_mut().push(msg.to_string()));
+
+        let msg = r#"{"e":"someFutureEvent","E":1499404907056,"s":"ETHBTC"}"#;
+        web_socket.process_message(msg).unwrap();
+        assert_eq!(*raw.borrow(), vec![msg.to_string()]);
+    }
+
+    #[test]
+    fn split_combined_stream_without_data_keeps_the_message() {
+        let value: Value = from_str(r#"{"stream":"btcusdt@trade"}"#).unwrap();
+
+        assert_eq!(split_combined_stream(value.clone()), (value, None));
+    }
+
+    #[test]
+    fn split_combined_stream_needs_a_stream_name() {
+        let value: Value = from_str(r#"{"stream":42,"data":{"e":"trade"}}"#).unwrap();
+
+        assert_eq!(split_combined_stream(value.clone()), (value, None));
+    }
+
+    #[test]
+    fn envelope_without_data_goes_to_the_raw_message_handler() {
+        let raw = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = WebSockets::new();
+        let received = raw.clone();
+        web_socket.add_raw_message_handler(move |msg| received.borrow_mut().push(msg.to_string()));
+
+        web_socket.process_message(r#"{"stream":"btcusdt@trade"}"#).unwrap();
+        assert_eq!(raw.borrow().len(), 1);
+    }
+
+    struct RecordErrors(Rc<RefCell<Vec<String>>>);
+
+    impl ErrorEventHandler for RecordErrors {
+        fn error_handler(&mut self, msg: &str, _error: &serde_json::Error) {
+            self.0.borrow_mut().push(msg.to_string());
+        }
+    }
+
+    #[test]
+    fn truncated_envelope_goes_to_the_error_handler() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = WebSockets::new();
+        web_socket.add_error_handler(RecordErrors(errors.clone()));
+
+        let msg = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1672515782136,"s":"BN"#;
+        web_socket.process_message(msg).unwrap();
+        assert_eq!(*errors.borrow(), vec![msg.to_string()]);
+    }
+
+    #[test]
+    fn envelope_with_a_malformed_event_goes_to_the_error_handler() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = WebSockets::new();
+        web_socket.add_error_handler(RecordErrors(errors.clone()));
+
+        let msg = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":"yesterday","s":"BTCUSDT"}}"#;
+        web_socket.process_message(msg).unwrap();
+        assert_eq!(errors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn pings_are_answered_while_the_event_loop_runs() {
+        let (base, server) = serve(|mut socket| {
+            socket.get_ref().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+            socket.write_message(Message::Ping(b"keepalive".to_vec())).unwrap();
+            match socket.read_message().unwrap() {
+                Message::Pong(data) => assert_eq!(data, b"keepalive".to_vec()),
+                other => panic!("unexpected {:?}", other),
+            }
+            socket.close(None).unwrap();
+            while socket.read_message().is_ok() {}
+        });
+        let mut web_socket = local(&base);
+        web_socket.connect("btcusdt@trade").unwrap();
+
+        web_socket.event_loop().unwrap();
+        server.join().unwrap();
+    }
+
+    // Records which callbacks fired
+    fn recording_callbacks(web_socket: &mut WebSockets) -> Rc<RefCell<Vec<&'static str>>> {
+        let fired = Rc::new(RefCell::new(Vec::new()));
+        let (order_trade, kline, agg_trade, trade) = (fired.clone(), fired.clone(), fired.clone(), fired.clone());
+        web_socket.on_order_trade(move |_| order_trade.borrow_mut().push("order_trade"));
+        web_socket.on_kline(move |_| kline.borrow_mut().push("kline"));
+        web_socket.on_agg_trade(move |_| agg_trade.borrow_mut().push("agg_trade"));
+        web_socket.on_trade(move |_| trade.borrow_mut().push("trade"));
+        fired
+    }
+
+    #[test]
+    fn client_order_id_naming_other_events_does_not_misroute() {
+        let mut web_socket = WebSockets::new();
+        let fired = recording_callbacks(&mut web_socket);
+
+        let msg = fixtures::EXECUTION_REPORT
+            .replace("mUvoqJxFIILMdfAW5iGSOW", r#"kline-aggTrade-\"e\":\"trade\"-stream-data"#);
+        web_socket.process_message(&msg).unwrap();
+        assert_eq!(*fired.borrow(), vec!["order_trade"]);
+    }
+
+    #[test]
+    fn symbol_naming_other_events_does_not_misroute() {
+        let mut web_socket = WebSockets::new();
+        let fired = recording_callbacks(&mut web_socket);
+
+        web_socket.process_message(&fixtures::AGG_TRADE.replace("BNBBTC", "KLINEUSDT")).unwrap();
+        web_socket.process_message(&fixtures::KLINE.replace("BNBBTC", "AGGTRADEUSDT")).unwrap();
+        assert_eq!(*fired.borrow(), vec!["agg_trade", "kline"]);
+    }
+
+    #[test]
+    fn stream_name_naming_other_events_does_not_misroute() {
+        let mut web_socket = WebSockets::new();
+        let fired = recording_callbacks(&mut web_socket);
+
+        let msg = format!(r#"{{"stream":"kline@executionReport","data":{}}}"#, fixtures::TRADE);
+        web_socket.process_message(&msg).unwrap();
+        assert_eq!(*fired.borrow(), vec!["trade"]);
+    }
+
+    #[test]
+    fn unknown_event_type_mentioning_known_ones_is_not_dispatched() {
+        let mut web_socket = WebSockets::new();
+        let fired = recording_callbacks(&mut web_socket);
+
+        let msg = r#"{"e":"klineTradeSummary","E":1672515782136,"s":"BNBBTC","note":"\"e\":\"aggTrade\""}"#;
+        web_socket.process_message(msg).unwrap();
+        assert!(fired.borrow().is_empty());
+    }
+
+    struct RecordBookTickers(Rc<RefCell<Vec<(Option<String>, BookTickerEvent)>>>);
+
+    impl BookTickerEventHandler for RecordBookTickers {
+        fn book_ticker_handler(&mut self, event: &BookTickerEvent) {
+            self.0.borrow_mut().push((None, event.clone()));
+        }
+    }
+
+    impl CombinedStreamEventHandler for RecordBookTickers {
+        fn book_ticker_handler(&mut self, stream: &str, event: &BookTickerEvent) {
+            self.0.borrow_mut().push((Some(stream.to_string()), event.clone()));
+        }
+    }
+
+    #[test]
+    fn book_ticker_on_a_single_stream_connection() {
+        let (base, server) = serve(|mut socket| {
+            socket.write_message(Message::Text(fixtures::BOOK_TICKER.into())).unwrap();
+            socket.close(None).unwrap();
+            while socket.read_message().is_ok() {}
+        });
+        let book_tickers = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = local(&base);
+        web_socket.add_book_ticker_handler(RecordBookTickers(book_tickers.clone()));
+        web_socket.connect("bnbusdt@bookTicker").unwrap();
+
+        web_socket.event_loop().unwrap();
+        server.join().unwrap();
+        let book_tickers = book_tickers.borrow();
+        assert_eq!(book_tickers.len(), 1);
+        assert_eq!(book_tickers[0].1.symbol, "BNBUSDT");
+        assert_eq!(book_tickers[0].1.best_bid_qty, amount("31.21"));
+    }
+
+    #[test]
+    fn book_ticker_on_a_combined_stream_connection() {
+        let book_tickers = Rc::new(RefCell::new(Vec::new()));
+        let mut web_socket = WebSockets::new();
+        web_socket.add_book_ticker_handler(RecordBookTickers(book_tickers.clone()));
+        web_socket.add_combined_stream_handler(RecordBookTickers(book_tickers.clone()));
+
+        let msg = format!(r#"{{"stream":"!bookTicker","data":{}}}"#, fixtures::BOOK_TICKER);
+        web_socket.process_message(&msg).unwrap();
+        let book_tickers = book_tickers.borrow();
+        assert_eq!(book_tickers.len(), 2);
+        assert_eq!(book_tickers[0].0, None);
+        assert_eq!(book_tickers[1].0.as_ref().map(String::as_str), Some("!bookTicker"));
+        assert_eq!(book_tickers[1].1.update_id, 400900217);
+    }
+
+    #[derive(Default)]
+    struct RecordUserStream {
+        balance_updates: Rc<RefCell<Vec<BalanceUpdateEvent>>>,
+    }
+
+    impl UserStreamEventHandler for RecordUserStream {
+        fn account_update_handler(&mut self, _event: &AccountUpdateEvent) {}
+
+        fn order_trade_handler(&mut self, _event: &OrderTradeEvent) {}
+
+        fn balance_update_handler(&mut self, event: &BalanceUpdateEvent) {
+            self.balance_updates.borrow_mut().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn balance_update_reaches_the_user_stream_handler() {
+        let handler = RecordUserStream::default();
+        let balance_updates = handler.balance_updates.clone();
+        let mut web_socket = WebSockets::new();
+        web_socket.add_user_stream_handler(handler);
+
+        web_socket.process_message(fixtures::BALANCE_UPDATE).unwrap();
+        let withdrawal = fixtures::BALANCE_UPDATE.replace(r#""100.00000000""#, r#""-0.50000000""#);
+        web_socket.process_message(&withdrawal).unwrap();
+
+        let balance_updates = balance_updates.borrow();
+        assert_eq!(balance_updates.len(), 2);
+        assert_eq!(balance_updates[0].asset, "BTC");
+        assert_eq!(balance_updates[0].delta, amount("100"));
+        assert_eq!(balance_updates[0].event_time, 1573200697110);
+        assert_eq!(balance_updates[0].clear_time, 1573200697068);
+        assert_eq!(balance_updates[1].delta, amount("-0.5"));
+    }
+
+    #[test]
+    fn balance_update_round_trips() {
+        let event: BalanceUpdateEvent = from_str(fixtures::BALANCE_UPDATE).unwrap();
+        let value: Value = from_str(&to_string(&event).unwrap()).unwrap();
+
+        assert_eq!(value["e"], "balanceUpdate");
+        assert_eq!(from_value::<BalanceUpdateEvent>(value).unwrap(), event);
+    }
+
+    #[derive(Default)]
+    struct RecordTickers {
+        arrays: Rc<RefCell<Vec<Vec<String>>>>,
+        symbols: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl DayTickerEventHandler for RecordTickers {
+        fn day_ticker_handler(&mut self, events: &[DayTickerEvent]) {
+            self.arrays.borrow_mut().push(events.iter().map(|event| event.symbol.clone()).collect());
+        }
+
+        fn symbol_ticker_handler(&mut self, event: &DayTickerEvent) {
+            self.symbols.borrow_mut().push(event.symbol.clone());
+        }
+    }
+
+    #[test]
+    fn symbol_ticker_and_ticker_array_reach_their_handler_methods() {
+        let handler = RecordTickers::default();
+        let (arrays, symbols) = (handler.arrays.clone(), handler.symbols.clone());
+        let mut web_socket = WebSockets::new();
+        web_socket.add_day_ticker_handler(handler);
+
+        web_socket.process_message(fixtures::SYMBOL_TICKER).unwrap();
+        web_socket.process_message(fixtures::DAY_TICKER_ARRAY).unwrap();
+        let combined = format!(r#"{{"stream":"bnbbtc@ticker","data":{}}}"#, fixtures::SYMBOL_TICKER);
+        web_socket.process_message(&combined).unwrap();
+        let combined = format!(r#"{{"stream":"!ticker@arr","data":{}}}"#, fixtures::DAY_TICKER_ARRAY);
+        web_socket.process_message(&combined).unwrap();
+
+        assert_eq!(*symbols.borrow(), vec!["BNBBTC", "BNBBTC"]);
+        let array = vec!["BNBBTC".to_string(), "ETHBTC".to_string()];
+        assert_eq!(*arrays.borrow(), vec![array.clone(), array]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn recording_error_of_the_reader_thread_is_reported_once() {
+        let (base, server) = serve(|mut socket| {
+            for _ in 0..2 {
+                socket.write_message(Message::Text(fixtures::AGG_TRADE.to_string())).unwrap();
+            }
+            socket.close(None).unwrap();
+            while socket.read_message().is_ok() {}
+        });
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let mut web_socket = local(&base);
+        // Every write to /dev/full fails with ENOSPC
+        web_socket.record_to("/dev/full").unwrap();
+        {
+            let errors = errors.clone();
+            web_socket.on_recording_error(move |e| errors.lock().unwrap().push(e.to_string()));
+        }
+        web_socket.connect("bnbbtc@aggTrade").unwrap();
+
+        let (handle, events) = web_socket.spawn_event_loop().unwrap();
+        assert!(events.recv().is_some() && events.recv().is_some());
+        handle.join().unwrap();
+        server.join().unwrap();
+        assert_eq!(errors.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn non_ascii_messages_through_the_queue_and_the_recording() {
+        let trade = fixtures::AGG_TRADE.replace(r#""M":true}"#, r#""M":true,"note":"Ошибка: 無効な数量 ✓"}"#);
+        let combined = format!(r#"{{"stream":"bnbbtc@aggTrade","data":{}}}"#, trade);
+        let notice = r#"{"e":"系统通知","msg":"维护中 🛠 – ünïcödé"}"#;
+        let sent = vec![combined.clone(), notice.to_string()];
+        let (base, server) = serve(move |mut socket| {
+            for msg in sent {
+                socket.write_message(Message::Text(msg)).unwrap();
+            }
+            socket.close(None).unwrap();
+            while socket.read_message().is_ok() {}
+        });
+        let path = env::temp_dir().join(format!("binance-rs-{}-non-ascii.jsonl", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut web_socket = local(&base);
+        web_socket.record_to(&path).unwrap();
+        web_socket.connect_multiple_streams(&vec!["bnbbtc@aggTrade".to_string()]).unwrap();
+        let (handle, events) = web_socket.spawn_event_loop_with_queue(16, Overflow::Block).unwrap();
+        match events.recv() {
+            Some(WebsocketEvent::AggTrade(event)) => assert_eq!(event.symbol, "BNBBTC"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match events.recv() {
+            Some(WebsocketEvent::Unknown(value)) => assert_eq!(value["msg"], "维护中 🛠 – ünïcödé"),
+            other => panic!("unexpected {:?}", other),
+        }
+        handle.join().unwrap();
+        server.join().unwrap();
+
+        // The recording keeps the frames byte for byte
+        let raw = Rc::new(RefCell::new(Vec::new()));
+        let mut replay = WebSockets::new();
+        let received = raw.clone();
+        replay.add_raw_message_handler(move |msg| received.borrow_mut().push(msg.to_string()));
+        let trades = Rc::new(RefCell::new(0));
+        let traded = trades.clone();
+        replay.on_agg_trade(move |_| *traded.borrow_mut() += 1);
+
+        let replayed = replay_from(&path, &mut replay, ReplaySpeed::AsFastAsPossible);
+        let _ = fs::remove_file(&path);
+        assert_eq!(replayed.unwrap(), 2);
+        assert_eq!(*trades.borrow(), 1);
+        assert_eq!(*raw.borrow(), vec![notice.to_string()]);
     }
 }
@@ -0,0 +1,416 @@
+// Payloads captured from the Binance streams (or copied from the API documentation where the event
+// is rare), one for every WebsocketEvent
+use super::*;
+
+pub static OUTBOUND_ACCOUNT_INFO: &'static str = r#"{"e":"outboundAccountInfo","E":1499405658849,"m":0,"t":0,"b":0,"s":0,"T":true,"W":true,"D":true,"u":1499405658848,"B":[{"a":"LTC","f":"17366.18538083","l":"0.00000000"},{"a":"BTC","f":"10537.85314051","l":"2.19464093"}]}"#;
+
+pub static EXECUTION_REPORT: &'static str = r#"{"e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"mUvoqJxFIILMdfAW5iGSOW","S":"BUY","o":"LIMIT","f":"GTC","q":"1.00000000","p":"0.10264410","P":"0.00000000","F":"0.00000000","g":-1,"C":"","x":"NEW","X":"NEW","r":"NONE","i":4293153,"l":"0.00000000","z":"0.00000000","L":"0.00000000","n":"0","N":null,"T":1499405658657,"t":-1,"I":8641984,"w":true,"m":false,"M":false,"O":1499405658657,"Z":"0.00000000","Y":"0.00000000","Q":"0.00000000","W":1499405658657,"V":"NONE"}"#;
+
+// Last fill of a MARKET order of an OCO, placed with quoteOrderQty
+pub static EXECUTION_REPORT_FILLED: &'static str = r#"{"e":"executionReport","E":1682837415752,"s":"BNBUSDT","c":"x-A6SIDXVS16828374157341","S":"BUY","o":"MARKET","f":"GTC","q":"0.03700000","p":"0.00000000","P":"0.00000000","F":"0.00000000","g":1271304,"C":"","x":"TRADE","X":"FILLED","r":"NONE","i":5935139,"l":"0.01700000","z":"0.03700000","L":"270.10000000","n":"0.00001700","N":"BNB","T":1682837415751,"t":381102,"I":12147990,"w":false,"m":false,"M":true,"O":1682837415750,"Z":"9.99510000","Y":"4.59170000","Q":"10.00000000","W":1682837415750,"V":"EXPIRE_MAKER"}"#;
+
+pub static BALANCE_UPDATE: &'static str = r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#;
+
+pub static OUTBOUND_ACCOUNT_POSITION: &'static str = r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"ETH","f":"10000.000000","l":"0.000000"}]}"#;
+
+pub static LIST_STATUS: &'static str = r#"{"e":"listStatus","E":1564035303637,"s":"ETHBTC","g":2,"c":"OCO","l":"EXEC_STARTED","L":"EXECUTING","r":"NONE","C":"F4QN4G8DlFATFlIUQ0cjdD","T":1564035303625,"O":[{"s":"ETHBTC","i":17,"c":"AJYsMjErWJesZvqlJCTUgL"},{"s":"ETHBTC","i":18,"c":"bfYPSQdLoqAJeNrOr9adzq"}]}"#;
+
+pub static MARGIN_CALL: &'static str = r#"{"e":"MARGIN_CALL","E":1587727187525,"cw":"3.16812045","p":[{"s":"ETHUSDT","ps":"LONG","pa":"1.327","mt":"CROSSED","iw":"0","mp":"187.17127","up":"-1.166074","mm":"1.614445"}]}"#;
+
+pub static LISTEN_KEY_EXPIRED: &'static str = r#"{"e":"listenKeyExpired","E":"1699596037418","listenKey":"OfYGbUzi3PraNagEkdKuFwUHn48brFsItTdsuiIXrucEvD0rhRXZ7I6URWfE8YE8"}"#;
+
+pub static ORDER_TRADE_UPDATE: &'static str = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"TRAILING_STOP_MARKET","f":"GTC","q":"0.001","p":"0","ap":"0","sp":"7103.04","x":"NEW","X":"NEW","i":8886774,"l":"0","z":"0","L":"0","N":"USDT","n":"0","T":1568879465650,"t":0,"b":"0","a":"9.91","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"TRAILING_STOP_MARKET","ps":"LONG","cp":false,"AP":"7476.89","cr":"5.0","rp":"0"}}"#;
+
+pub static ACCOUNT_UPDATE: &'static str = r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"122624.12345678","cw":"100.12345678","bc":"50.12345678"}],"P":[{"s":"BTCUSDT","pa":"0","ep":"0.00000","cr":"200","up":"0","mt":"isolated","iw":"0.00000000","ps":"BOTH"}]}}"#;
+
+pub static AGG_TRADE: &'static str = r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#;
+
+pub static TRADE: &'static str = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","b":88,"a":50,"T":1672515782136,"m":true,"M":true}"#;
+
+pub static SYMBOL_TICKER: &'static str = r#"{"e":"24hrTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015","P":"250.00","w":"0.0018","x":"0.0009","c":"0.0025","Q":"10","b":"0.0024","B":"10","a":"0.0026","A":"100","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18","O":0,"C":86400000,"F":0,"L":18150,"n":18151}"#;
+
+pub static DAY_TICKER_ARRAY: &'static str = r#"[{"e":"24hrTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015","P":"250.00","w":"0.0018","x":"0.0009","c":"0.0025","Q":"10","b":"0.0024","B":"10","a":"0.0026","A":"100","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18","O":0,"C":86400000,"F":0,"L":18150,"n":18151},{"e":"24hrTicker","E":1672515782136,"s":"ETHBTC","p":"-0.00050000","P":"-0.711","w":"0.06991225","x":"0.07030000","c":"0.06980000","Q":"0.4510","b":"0.06979000","B":"12.1935","a":"0.06980000","A":"3.6625","o":"0.07030000","h":"0.07062000","l":"0.06921000","v":"31097.9874","q":"2174.10650190","O":1672429382136,"C":1672515782136,"F":408938643,"L":409045725,"n":107083}]"#;
+
+pub static MINI_TICKER: &'static str = r#"{"e":"24hrMiniTicker","E":1672515782136,"s":"BNBBTC","c":"0.0025","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18"}"#;
+
+pub static MINI_TICKER_ARRAY: &'static str = r#"[{"e":"24hrMiniTicker","E":1672515782136,"s":"BNBBTC","c":"0.0025","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18"},{"e":"24hrMiniTicker","E":1672515782136,"s":"ETHBTC","c":"0.06980000","o":"0.07030000","h":"0.07062000","l":"0.06921000","v":"31097.9874","q":"2174.10650190"}]"#;
+
+pub static KLINE: &'static str = r#"{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#;
+
+pub static DEPTH_UPDATE: &'static str = r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"]],"a":[["0.0026","100"]]}"#;
+
+pub static PARTIAL_DEPTH: &'static str = r#"{"lastUpdateId":160,"bids":[["0.0024","10"],["0.0023","7.5"]],"asks":[["0.0026","100"]]}"#;
+
+pub static BOOK_TICKER: &'static str = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+
+pub static MARK_PRICE: &'static str = r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000}"#;
+
+pub static FORCE_ORDER: &'static str = r#"{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}"#;
+
+pub static CONTINUOUS_KLINE: &'static str = r#"{"e":"continuous_kline","E":1607443058651,"ps":"BTCUSDT","ct":"PERPETUAL","k":{"t":1607443020000,"T":1607443079999,"i":"1m","f":116467658886,"L":116468012423,"o":"18787.00","c":"18804.04","h":"18804.04","l":"18786.54","v":"197.664","n":543,"x":false,"q":"3715253.19494","V":"184.769","Q":"3472925.84746","B":"0"}}"#;
+
+// The same trade through a combined stream connection
+pub static COMBINED_AGG_TRADE: &'static str = r#"{"stream":"bnbbtc@aggTrade","data":{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}}"#;
+
+fn parse(raw: &str) -> WebsocketEvent {
+    WebsocketEvent::parse(raw).unwrap().0
+}
+
+#[test]
+fn account_update() {
+    match parse(OUTBOUND_ACCOUNT_INFO) {
+        WebsocketEvent::AccountUpdate(event) => {
+            assert_eq!(event.event_time, 1499405658849);
+            assert_eq!(event.balance.len(), 2);
+            assert_eq!(event.balance[1].asset, "BTC");
+            assert_eq!(event.balance[1].locked, amount("2.19464093"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn order_trade() {
+    match parse(EXECUTION_REPORT) {
+        WebsocketEvent::OrderTrade(event) => {
+            assert_eq!(event.symbol, "ETHBTC");
+            assert_eq!(event.new_client_order_id, "mUvoqJxFIILMdfAW5iGSOW");
+            assert_eq!(event.side, OrderSide::Buy);
+            assert_eq!(event.order_status, OrderStatus::New);
+            assert_eq!(event.order_id, 4293153);
+            assert_eq!(event.price, amount("0.10264410"));
+            assert_eq!(event.order_list_id, -1);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn order_trade_fields_of_a_fill() {
+    match parse(EXECUTION_REPORT_FILLED) {
+        WebsocketEvent::OrderTrade(event) => {
+            assert_eq!(event.order_list_id, 1271304);
+            assert_eq!(event.quote_order_qty, Some(amount("10")));
+            assert_eq!(event.last_quote_qty, Some(amount("4.5917")));
+            assert_eq!(event.cummulative_quote_qty, Some(amount("9.9951")));
+            assert_eq!(event.order_status, OrderStatus::Filled);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // and from the 2017 payload, without them
+    match parse(r#"{"e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"mUvoqJxFIILMdfAW5iGSOW","S":"BUY","o":"LIMIT","f":"GTC","q":"1.00000000","p":"0.10264410","P":"0.00000000","F":"0.00000000","g":-1,"C":"","x":"NEW","X":"NEW","r":"NONE","i":4293153,"l":"0.00000000","z":"0.00000000","L":"0.00000000","n":"0","N":null,"T":1499405658657,"t":-1,"I":8641984,"w":true,"m":false,"M":false}"#) {
+        WebsocketEvent::OrderTrade(event) => {
+            assert_eq!((event.quote_order_qty, event.last_quote_qty, event.cummulative_quote_qty), (None, None, None));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn balance_update() {
+    match parse(BALANCE_UPDATE) {
+        WebsocketEvent::BalanceUpdate(event) => {
+            assert_eq!(event.asset, "BTC");
+            assert_eq!(event.delta, amount("100"));
+            assert_eq!(event.clear_time, 1573200697068);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn account_position() {
+    match parse(OUTBOUND_ACCOUNT_POSITION) {
+        WebsocketEvent::AccountPosition(event) => {
+            assert_eq!(event.last_update_time, 1564034571073);
+            assert_eq!(event.balances[0].asset, "ETH");
+            assert_eq!(event.balances[0].free, amount("10000"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn list_status() {
+    match parse(LIST_STATUS) {
+        WebsocketEvent::ListStatus(event) => {
+            assert_eq!(event.order_list_id, 2);
+            assert_eq!(event.list_order_status, "EXECUTING");
+            assert_eq!(event.orders.iter().map(|order| order.order_id).collect::<Vec<_>>(), vec![17, 18]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn margin_call() {
+    match parse(MARGIN_CALL) {
+        WebsocketEvent::MarginCall(event) => {
+            assert_eq!(event.cross_wallet_balance, Some(amount("3.16812045")));
+            assert_eq!(event.positions[0].symbol, "ETHUSDT");
+            assert_eq!(event.positions[0].unrealized_pnl, amount("-1.166074"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn listen_key_expired() {
+    match parse(LISTEN_KEY_EXPIRED) {
+        WebsocketEvent::ListenKeyExpired(event) => {
+            assert_eq!(event.event_time, 1699596037418);
+            assert!(event.listen_key.unwrap().starts_with("OfYGbUzi"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn futures_order_trade() {
+    match parse(ORDER_TRADE_UPDATE) {
+        WebsocketEvent::FuturesOrderTrade(event) => {
+            assert_eq!(event.order.symbol, "BTCUSDT");
+            assert_eq!(event.order.order_type, FuturesOrderType::TrailingStopMarket);
+            assert_eq!(event.order.position_side, PositionSide::Long);
+            assert_eq!(event.order.activation_price, Some(amount("7476.89")));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn futures_account_update() {
+    match parse(ACCOUNT_UPDATE) {
+        WebsocketEvent::FuturesAccountUpdate(event) => {
+            assert_eq!(event.update.reason, "ORDER");
+            assert_eq!(event.update.balances[0].wallet_balance, amount("122624.12345678"));
+            assert_eq!(event.update.positions[0].position_side, PositionSide::Both);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn agg_trade() {
+    match parse(AGG_TRADE) {
+        WebsocketEvent::AggTrade(event) => {
+            assert_eq!(event.symbol, "BNBBTC");
+            assert_eq!(event.aggregated_trade_id, 12345);
+            assert_eq!(event.price, amount("0.001"));
+            assert_eq!(event.qty, amount("100"));
+            assert!(event.is_buyer_maker);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn trade() {
+    match parse(TRADE) {
+        WebsocketEvent::Trade(event) => {
+            assert_eq!(event.trade_id, 12345);
+            assert_eq!(event.buyer_order_id, 88);
+            assert_eq!(event.seller_order_id, 50);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn day_ticker() {
+    match parse(DAY_TICKER_ARRAY) {
+        WebsocketEvent::DayTicker(events) => {
+            assert_eq!(events.iter().map(|event| event.symbol.as_str()).collect::<Vec<_>>(), vec!["BNBBTC", "ETHBTC"]);
+            assert_eq!(events[1].price_change, amount("-0.0005"));
+            assert_eq!(events[1].num_trades, 107083);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn symbol_ticker() {
+    match parse(SYMBOL_TICKER) {
+        WebsocketEvent::SymbolTicker(event) => {
+            assert_eq!(event.symbol, "BNBBTC");
+            assert_eq!(event.current_close, amount("0.0025"));
+            assert_eq!(event.last_trade_id, 18150);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn mini_ticker() {
+    match parse(MINI_TICKER) {
+        WebsocketEvent::MiniTicker(event) => {
+            assert_eq!(event.symbol, "BNBBTC");
+            assert_eq!(event.close, amount("0.0025"));
+            assert_eq!(event.quote_volume, amount("18"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn all_mini_ticker() {
+    match parse(MINI_TICKER_ARRAY) {
+        WebsocketEvent::AllMiniTicker(events) => {
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[1].symbol, "ETHBTC");
+            assert_eq!(events[1].high, amount("0.07062"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn kline() {
+    match parse(KLINE) {
+        WebsocketEvent::Kline(event) => {
+            assert_eq!(event.kline.interval, KlineInterval::Minutes1);
+            assert_eq!(event.kline.start_time, 1672515780000);
+            assert_eq!(event.kline.close, amount("0.002"));
+            assert_eq!(event.kline.number_of_trades, 100);
+            assert!(!event.kline.is_final_bar);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn depth_order_book() {
+    match parse(DEPTH_UPDATE) {
+        WebsocketEvent::DepthOrderBook(event) => {
+            assert_eq!((event.first_update_id, event.final_update_id), (157, 160));
+            assert_eq!(event.bids[0].price, amount("0.0024"));
+            assert_eq!(event.asks[0].qty, amount("100"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn order_book() {
+    match WebsocketEvent::parse(&format!(r#"{{"stream":"bnbbtc@depth5","data":{}}}"#, PARTIAL_DEPTH)).unwrap() {
+        (WebsocketEvent::OrderBook(book), stream) => {
+            assert_eq!(stream.as_ref().map(String::as_str), Some("bnbbtc@depth5"));
+            assert_eq!(book.last_update_id, 160);
+            assert_eq!(book.bids.len(), 2);
+            assert_eq!(book.bids[1].qty, amount("7.5"));
+            assert_eq!(book.levels, Some(5));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn book_ticker() {
+    match parse(BOOK_TICKER) {
+        WebsocketEvent::BookTicker(event) => {
+            assert_eq!(event.update_id, 400900217);
+            assert_eq!(event.symbol, "BNBUSDT");
+            assert_eq!(event.best_bid, amount("25.3519"));
+            assert_eq!(event.best_ask_qty, amount("40.66"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn mark_price() {
+    match parse(MARK_PRICE) {
+        WebsocketEvent::MarkPrice(event) => {
+            assert_eq!(event.mark_price, amount("11794.15"));
+            assert_eq!(event.funding_rate, amount("0.00038167"));
+            assert_eq!(event.next_funding_time, 1562306400000);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn liquidation() {
+    match parse(FORCE_ORDER) {
+        WebsocketEvent::Liquidation(event) => {
+            assert_eq!(event.order.side, OrderSide::Sell);
+            assert_eq!(event.order.order_status, OrderStatus::Filled);
+            assert_eq!(event.order.accumulated_filled_qty, amount("0.014"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn continuous_kline() {
+    match parse(CONTINUOUS_KLINE) {
+        WebsocketEvent::ContinuousKline(event) => {
+            assert_eq!(event.pair, "BTCUSDT");
+            assert_eq!(event.contract_type, "PERPETUAL");
+            assert_eq!(event.kline.first_update_id, 116467658886);
+            assert_eq!(event.kline.open, amount("18787"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn unknown() {
+    match parse(r#"{"e":"someFutureEvent","E":1672515782136}"#) {
+        WebsocketEvent::Unknown(value) => assert_eq!(value["e"], "someFutureEvent"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn combined_stream_envelope() {
+    let (event, stream) = WebsocketEvent::parse(COMBINED_AGG_TRADE).unwrap();
+
+    assert_eq!(stream.as_ref().map(String::as_str), Some("bnbbtc@aggTrade"));
+    assert_eq!(event, parse(AGG_TRADE));
+}
+
+// Every fixture through the dispatcher, the matching callback fires once
+#[test]
+fn process_message_fires_the_callback_of_every_event() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let mut web_socket = WebSockets::new();
+    macro_rules! record {
+        ($($on:ident),*) => {
+            $(
+                let fired_by = fired.clone();
+                web_socket.$on(move |_| fired_by.borrow_mut().push(stringify!($on)));
+            )*
+        };
+    }
+    record!(
+        on_account_update, on_order_trade, on_balance_update, on_account_position, on_list_status,
+        on_margin_call, on_listen_key_expired, on_futures_order_trade, on_futures_account_update,
+        on_agg_trade, on_trade, on_day_ticker, on_symbol_ticker, on_mini_ticker, on_all_mini_ticker, on_kline,
+        on_depth, on_partial_depth, on_book_ticker, on_mark_price, on_liquidation, on_continuous_kline
+    );
+
+    let fixtures = [
+        OUTBOUND_ACCOUNT_INFO, EXECUTION_REPORT, BALANCE_UPDATE, OUTBOUND_ACCOUNT_POSITION, LIST_STATUS,
+        MARGIN_CALL, LISTEN_KEY_EXPIRED, ORDER_TRADE_UPDATE, ACCOUNT_UPDATE, AGG_TRADE, TRADE,
+        DAY_TICKER_ARRAY, SYMBOL_TICKER, MINI_TICKER, MINI_TICKER_ARRAY, KLINE, DEPTH_UPDATE, PARTIAL_DEPTH,
+        BOOK_TICKER, MARK_PRICE, FORCE_ORDER, CONTINUOUS_KLINE, COMBINED_AGG_TRADE,
+    ];
+    for fixture in fixtures.iter() {
+        web_socket.process_message(fixture).unwrap();
+    }
+
+    assert_eq!(
+        *fired.borrow(),
+        vec![
+            "on_account_update", "on_order_trade", "on_balance_update", "on_account_position", "on_list_status",
+            "on_margin_call", "on_listen_key_expired", "on_futures_order_trade", "on_futures_account_update",
+            "on_agg_trade", "on_trade", "on_day_ticker", "on_symbol_ticker", "on_mini_ticker",
+            "on_all_mini_ticker", "on_kline", "on_depth", "on_partial_depth", "on_book_ticker", "on_mark_price",
+            "on_liquidation", "on_continuous_kline", "on_agg_trade",
+        ]
+    );
+}
@@ -0,0 +1,105 @@
+use errors::*;
+use websockets::*;
+use url::Url;
+use futures::{future, Async, Future, Poll, Stream};
+use tokio_core::reactor::Remote;
+use tokio_tungstenite::connect_async;
+use tungstenite::{Error as TungsteniteError, Message};
+
+static SINGLE_STREAM: &'static str = "/ws/";
+static MULTI_STREAM: &'static str = "/stream?streams=";
+
+type MessageStream = Box<Stream<Item = Message, Error = TungsteniteError>>;
+
+// Futures based counterpart of WebSockets, connections resolve to a Stream of WebsocketEvent
+pub struct AsyncWebSockets {
+    single_stream_url: String,
+    multi_stream_url: String,
+}
+
+impl Default for AsyncWebSockets {
+    fn default() -> AsyncWebSockets {
+        AsyncWebSockets::new()
+    }
+}
+
+impl AsyncWebSockets {
+    pub fn new() -> AsyncWebSockets {
+        AsyncWebSockets::new_with_endpoint(WebsocketHost::Binance.base_url())
+    }
+
+    // base is the host, e.g. "wss://testnet.binance.vision" (see WebsocketHost)
+    pub fn new_with_endpoint(base: &str) -> AsyncWebSockets {
+        AsyncWebSockets {
+            single_stream_url: format!("{}{}", base, SINGLE_STREAM),
+            multi_stream_url: format!("{}{}", base, MULTI_STREAM),
+        }
+    }
+
+    pub fn connect(&self, endpoint: &str, handle: Remote) -> Box<Future<Item = WebsocketEventStream, Error = Error>> {
+        let wss: String = format!("{}{}", self.single_stream_url, endpoint);
+
+        connect_wss(&wss, Some(endpoint.into()), handle)
+    }
+
+    pub fn connect_multiple_streams(
+        &self, endpoints: &[String], handle: Remote,
+    ) -> Box<Future<Item = WebsocketEventStream, Error = Error>> {
+        let wss: String = format!("{}{}", self.multi_stream_url, endpoints.join("/"));
+
+        connect_wss(&wss, None, handle)
+    }
+}
+
+fn connect_wss(
+    wss: &str, endpoint: Option<String>, handle: Remote,
+) -> Box<Future<Item = WebsocketEventStream, Error = Error>> {
+    let url = match Url::parse(wss) {
+        Ok(url) => url,
+        Err(e) => return Box::new(future::err(e.into())),
+    };
+
+    Box::new(
+        connect_async(url, handle)
+            .map(move |(socket, _)| WebsocketEventStream {
+                socket: Box::new(socket),
+                endpoint: endpoint,
+            })
+            .map_err(|e| e.into()),
+    )
+}
+
+// Ends when the server closes the connection
+pub struct WebsocketEventStream {
+    socket: MessageStream,
+    endpoint: Option<String>,
+}
+
+impl Stream for WebsocketEventStream {
+    type Item = WebsocketEvent;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<WebsocketEvent>, Error> {
+        loop {
+            let msg = match self.socket.poll() {
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Ok(Async::Ready(None)) | Err(TungsteniteError::ConnectionClosed(_)) => {
+                    return Ok(Async::Ready(None));
+                }
+                Ok(Async::Ready(Some(Message::Text(msg)))) => msg,
+                Ok(Async::Ready(Some(Message::Binary(data)))) => match String::from_utf8(data) {
+                    Ok(msg) => msg,
+                    Err(_) => continue,
+                },
+                // Pongs are queued by tungstenite and sent with the next read
+                Ok(Async::Ready(Some(_))) => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            let endpoint = self.endpoint.as_ref().map(String::as_str);
+            if let Some(event) = parse_message(&msg, endpoint)? {
+                return Ok(Async::Ready(Some(event)));
+            }
+        }
+    }
+}
@@ -0,0 +1,683 @@
+use errors::*;
+use websockets::*;
+use queue::{bounded, EventReceiver, EventSender, Overflow};
+use serde_json::{from_str, Value};
+use std::cmp;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Binance refuses combined stream connections with more streams
+pub static MAX_STREAMS_PER_CONNECTION: usize = 1024;
+
+static DEFAULT_QUEUE_CAPACITY: usize = 1024;
+// How often a connection thread looks for commands and the stop flag
+static TICK_MS: u64 = 100;
+static CONNECTION_LIFETIME_SECS: u64 = 24 * 60 * 60;
+static SCHEDULED_RECONNECT_MARGIN_SECS: u64 = 5 * 60;
+
+// An event of one of the connections of a WebSocketsPool
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolEvent {
+    // Id of the connection, see ShardStatus
+    pub shard: usize,
+    // e.g. "btcusdt@bookTicker"
+    pub stream: Option<String>,
+    pub event: WebsocketEvent,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolOptions {
+    // e.g. WebsocketHost::Futures.base_url()
+    pub base_url: String,
+    pub max_per_connection: usize,
+    // Applies to each connection on its own
+    pub reconnect_policy: ReconnectPolicy,
+    // Of the queue shared by all connections
+    pub capacity: usize,
+    pub overflow: Overflow,
+}
+
+impl Default for PoolOptions {
+    fn default() -> PoolOptions {
+        PoolOptions {
+            base_url: WebsocketHost::Binance.base_url().into(),
+            max_per_connection: MAX_STREAMS_PER_CONNECTION,
+            reconnect_policy: ReconnectPolicy::default(),
+            capacity: DEFAULT_QUEUE_CAPACITY,
+            overflow: Overflow::Block,
+        }
+    }
+}
+
+// One connection of a WebSocketsPool as seen by WebSocketsPool::shards
+#[derive(Clone)]
+pub struct ShardStatus {
+    pub id: usize,
+    pub streams: Vec<String>,
+    pub health: HealthHandle,
+    pub reconnects: usize,
+    // Why the connection last dropped
+    pub last_error: Option<String>,
+    // False once the connection gave up reconnecting, see WebSocketsPool::restart_stopped
+    pub running: bool,
+}
+
+enum ShardCommand {
+    Subscribe(Vec<String>, Sender<Result<()>>),
+    Unsubscribe(Vec<String>, Sender<Result<()>>),
+}
+
+struct Shard {
+    id: usize,
+    streams: Vec<String>,
+    health: HealthHandle,
+    commands: Sender<ShardCommand>,
+    stopped: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    reconnects: Arc<AtomicUsize>,
+    last_error: Arc<Mutex<Option<String>>>,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl Shard {
+    fn status(&self) -> ShardStatus {
+        ShardStatus {
+            id: self.id,
+            streams: self.streams.clone(),
+            health: self.health.clone(),
+            reconnects: self.reconnects.load(Ordering::SeqCst),
+            last_error: match self.last_error.lock() {
+                Ok(last_error) => last_error.clone(),
+                Err(poisoned) => poisoned.into_inner().clone(),
+            },
+            running: self.running.load(Ordering::SeqCst),
+        }
+    }
+
+    fn send(&self, command: ShardCommand, reply: Receiver<Result<()>>) -> Result<()> {
+        if self.commands.send(command).is_err() {
+            bail!(BinanceError::WebsocketClosed { frame: None });
+        }
+
+        // The sender is dropped unanswered when the connection thread has ended
+        match reply.recv() {
+            Ok(result) => result,
+            Err(_) => bail!(BinanceError::WebsocketClosed { frame: None }),
+        }
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.stopped.store(true, Ordering::SeqCst);
+
+        match self.thread.take() {
+            Some(thread) => match thread.join() {
+                Ok(result) => result,
+                Err(_) => bail!(BinanceError::Internal("Websocket pool thread panicked".into())),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+// Spreads streams over as many combined stream connections as max_per_connection requires, each
+// on its own thread with its own reconnects. The events of all connections arrive on one queue.
+// Subscribing fills the connections with room before opening a new one, unsubscribing only touches
+// the connections carrying the streams and closes those left empty
+pub struct WebSocketsPool {
+    options: PoolOptions,
+    shards: Vec<Shard>,
+    next_id: usize,
+    sender: Option<EventSender<PoolEvent>>,
+}
+
+impl Drop for WebSocketsPool {
+    fn drop(&mut self) {
+        for shard in &self.shards {
+            shard.stopped.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+impl WebSocketsPool {
+    pub fn connect(
+        streams: &[String], max_per_connection: usize,
+    ) -> Result<(WebSocketsPool, EventReceiver<PoolEvent>)> {
+        let options = PoolOptions {
+            max_per_connection,
+            ..PoolOptions::default()
+        };
+
+        WebSocketsPool::connect_with_options(streams, options)
+    }
+
+    // Fails if any of the connections fails, the others are closed again
+    pub fn connect_with_options(
+        streams: &[String], options: PoolOptions,
+    ) -> Result<(WebSocketsPool, EventReceiver<PoolEvent>)> {
+        if options.max_per_connection == 0 || options.max_per_connection > MAX_STREAMS_PER_CONNECTION {
+            bail!(BinanceError::Parameter(format!(
+                "max_per_connection must be between 1 and {}",
+                MAX_STREAMS_PER_CONNECTION
+            )));
+        }
+
+        let (sender, receiver) = bounded(options.capacity, options.overflow);
+        let mut pool = WebSocketsPool {
+            options,
+            shards: Vec::new(),
+            next_id: 0,
+            sender: Some(sender),
+        };
+        pool.subscribe(streams)?;
+
+        Ok((pool, receiver))
+    }
+
+    // Every stream of the pool
+    pub fn streams(&self) -> Vec<String> {
+        self.shards.iter().flat_map(|shard| shard.streams.iter().cloned()).collect()
+    }
+
+    pub fn shards(&self) -> Vec<ShardStatus> {
+        self.shards.iter().map(Shard::status).collect()
+    }
+
+    // Streams already in the pool are skipped
+    pub fn subscribe(&mut self, streams: &[String]) -> Result<()> {
+        let mut new_streams: Vec<String> = Vec::new();
+        {
+            let mut known: HashSet<&String> = self.shards.iter().flat_map(|shard| shard.streams.iter()).collect();
+            for stream in streams {
+                if known.insert(stream) {
+                    new_streams.push(stream.clone());
+                }
+            }
+        }
+
+        let max = self.options.max_per_connection;
+        for shard in self.shards.iter_mut() {
+            let room = max.saturating_sub(shard.streams.len());
+            if room == 0 || new_streams.is_empty() || !shard.running.load(Ordering::SeqCst) {
+                continue;
+            }
+            let rest = new_streams.split_off(cmp::min(room, new_streams.len()));
+            let (reply_sender, reply) = channel();
+            shard.send(ShardCommand::Subscribe(new_streams.clone(), reply_sender), reply)?;
+            shard.streams.extend(new_streams);
+            new_streams = rest;
+        }
+
+        while !new_streams.is_empty() {
+            let rest = new_streams.split_off(cmp::min(max, new_streams.len()));
+            let shard = self.spawn_shard(new_streams)?;
+            self.shards.push(shard);
+            new_streams = rest;
+        }
+
+        Ok(())
+    }
+
+    pub fn unsubscribe(&mut self, streams: &[String]) -> Result<()> {
+        let mut result = Ok(());
+        for shard in self.shards.iter_mut() {
+            let removed: Vec<String> = shard
+                .streams
+                .iter()
+                .filter(|stream| streams.contains(stream))
+                .cloned()
+                .collect();
+            // An emptied connection is closed instead
+            if removed.is_empty() || removed.len() == shard.streams.len() {
+                continue;
+            }
+            let (reply_sender, reply) = channel();
+            shard.send(ShardCommand::Unsubscribe(removed, reply_sender), reply)?;
+            shard.streams.retain(|stream| !streams.contains(stream));
+        }
+
+        let (emptied, shards): (Vec<Shard>, Vec<Shard>) = self
+            .shards
+            .drain(..)
+            .partition(|shard| shard.streams.iter().all(|stream| streams.contains(stream)));
+        self.shards = shards;
+        for mut shard in emptied {
+            result = result.and(shard.stop());
+        }
+
+        result
+    }
+
+    // Replaces the connections which gave up reconnecting, returns how many were restarted
+    pub fn restart_stopped(&mut self) -> Result<usize> {
+        let mut restarted = 0;
+        for index in 0..self.shards.len() {
+            if self.shards[index].running.load(Ordering::SeqCst) {
+                continue;
+            }
+            // The reconnect error it ended with is in its last_error
+            let _ = self.shards[index].stop();
+            let streams = self.shards[index].streams.clone();
+            self.shards[index] = self.spawn_shard(streams)?;
+            restarted += 1;
+        }
+
+        Ok(restarted)
+    }
+
+    // Closes every connection, the receiver ends once it has delivered the queued events.
+    // Returns the first error a connection ended with
+    pub fn stop(mut self) -> Result<()> {
+        self.sender = None;
+        for shard in &self.shards {
+            shard.stopped.store(true, Ordering::SeqCst);
+        }
+
+        let mut result = Ok(());
+        for mut shard in self.shards.drain(..) {
+            result = result.and(shard.stop());
+        }
+
+        result
+    }
+
+    fn spawn_shard(&mut self, streams: Vec<String>) -> Result<Shard> {
+        let sender = match self.sender {
+            Some(ref sender) => sender.clone(),
+            None => bail!(BinanceError::Internal("Websocket pool is stopped".into())),
+        };
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let (commands, command_receiver) = channel();
+        let (ready_sender, ready) = channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let running = Arc::new(AtomicBool::new(true));
+        let reconnects = Arc::new(AtomicUsize::new(0));
+        let last_error = Arc::new(Mutex::new(None));
+
+        let connection = ShardConnection {
+            id,
+            options: self.options.clone(),
+            streams: streams.clone(),
+            commands: command_receiver,
+            stopped: stopped.clone(),
+            reconnects: reconnects.clone(),
+            last_error: last_error.clone(),
+        };
+        let thread_running = running.clone();
+        let thread = thread::spawn(move || {
+            let last_error = connection.last_error.clone();
+            let result = connection.run(sender, ready_sender);
+            if let Err(ref e) = result {
+                set_last_error(&last_error, e);
+            }
+            thread_running.store(false, Ordering::SeqCst);
+            result
+        });
+
+        let health = match ready.recv() {
+            Ok(Ok(health)) => health,
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                bail!(e);
+            }
+            Err(_) => bail!(BinanceError::Internal("Websocket pool thread panicked".into())),
+        };
+
+        Ok(Shard {
+            id,
+            streams,
+            health,
+            commands,
+            stopped,
+            running,
+            reconnects,
+            last_error,
+            thread: Some(thread),
+        })
+    }
+}
+
+// The connection thread of a Shard
+struct ShardConnection {
+    id: usize,
+    options: PoolOptions,
+    streams: Vec<String>,
+    commands: Receiver<ShardCommand>,
+    stopped: Arc<AtomicBool>,
+    reconnects: Arc<AtomicUsize>,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl ShardConnection {
+    fn run(mut self, sender: EventSender<PoolEvent>, ready: Sender<Result<HealthHandle>>) -> Result<()> {
+        let mut web_socket = WebSockets::new_with_endpoint(&self.options.base_url);
+        // Reconnects dial the current streams of the shard, the WebSockets ones would dial
+        // the url it was opened with and send the changes since
+        web_socket.set_scheduled_reconnect(None)?;
+
+        let receiver_gone = Arc::new(AtomicBool::new(false));
+        {
+            let receiver_gone = receiver_gone.clone();
+            let id = self.id;
+            web_socket.set_forward(move |msg| {
+                if let Some(event) = pool_event(id, msg) {
+                    if !sender.send(event) {
+                        receiver_gone.store(true, Ordering::SeqCst);
+                    }
+                }
+            });
+        }
+
+        match web_socket.connect_multiple_streams(&self.streams) {
+            Ok(()) => {
+                let _ = ready.send(Ok(web_socket.health()));
+            }
+            Err(e) => {
+                let _ = ready.send(Err(e));
+                return Ok(());
+            }
+        }
+
+        let interval = Duration::from_millis(TICK_MS);
+        let lifetime = Duration::from_secs(CONNECTION_LIFETIME_SECS - SCHEDULED_RECONNECT_MARGIN_SECS);
+        loop {
+            let connected_at = Instant::now();
+            let mut ended = false;
+            let result = web_socket.event_loop_with_tick(interval, |web_socket| {
+                if self.stopped.load(Ordering::SeqCst) || receiver_gone.load(Ordering::SeqCst) {
+                    ended = true;
+                    return false;
+                }
+                if !self.apply_commands(Some(web_socket)) {
+                    ended = true;
+                    return false;
+                }
+                // Replaced before Binance ends it at 24 hours
+                connected_at.elapsed() < lifetime
+            });
+            if ended {
+                return web_socket.disconnect();
+            }
+            if let Err(ref e) = result {
+                set_last_error(&self.last_error, e);
+            }
+
+            let _ = web_socket.disconnect();
+            if !self.reconnect(&mut web_socket)? {
+                return Ok(());
+            }
+            self.reconnects.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // Returns false once the pool is gone. Without a connection only the streams of the next
+    // one change
+    fn apply_commands(&mut self, mut web_socket: Option<&mut WebSockets>) -> bool {
+        loop {
+            let command = match self.commands.try_recv() {
+                Ok(command) => command,
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => return false,
+            };
+
+            match command {
+                ShardCommand::Subscribe(streams, reply) => {
+                    let result = match web_socket {
+                        Some(ref mut web_socket) => web_socket.subscribe(&streams).map(|_| ()),
+                        None => Ok(()),
+                    };
+                    if result.is_ok() {
+                        self.streams.extend(streams);
+                    }
+                    let _ = reply.send(result);
+                }
+                ShardCommand::Unsubscribe(streams, reply) => {
+                    let result = match web_socket {
+                        Some(ref mut web_socket) => web_socket.unsubscribe(&streams).map(|_| ()),
+                        None => Ok(()),
+                    };
+                    if result.is_ok() {
+                        self.streams.retain(|stream| !streams.contains(stream));
+                    }
+                    let _ = reply.send(result);
+                }
+            }
+        }
+    }
+
+    // Backs off like WebSockets does, false when stopped meanwhile
+    fn reconnect(&mut self, web_socket: &mut WebSockets) -> Result<bool> {
+        let policy = self.options.reconnect_policy.clone();
+        let mut delay = policy.base_delay;
+        let mut last_error = None;
+        for attempt in 0..policy.max_retries {
+            if attempt > 0 {
+                delay = cmp::min(delay * 2, policy.max_delay);
+            }
+            if !self.wait(delay) {
+                return Ok(false);
+            }
+
+            match web_socket.connect_multiple_streams(&self.streams) {
+                Ok(()) => return Ok(true),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) => bail!(e),
+            None => bail!(BinanceError::Parameter("Reconnect policy does not allow any attempt".into())),
+        }
+    }
+
+    // Keeps answering commands while waiting, false when stopped
+    fn wait(&mut self, delay: Duration) -> bool {
+        let started = Instant::now();
+        while started.elapsed() < delay {
+            if self.stopped.load(Ordering::SeqCst) || !self.apply_commands(None) {
+                return false;
+            }
+            thread::sleep(cmp::min(Duration::from_millis(TICK_MS), delay));
+        }
+
+        true
+    }
+}
+
+fn set_last_error(last_error: &Mutex<Option<String>>, error: &Error) {
+    match last_error.lock() {
+        Ok(mut last_error) => *last_error = Some(error.to_string()),
+        Err(poisoned) => *poisoned.into_inner() = Some(error.to_string()),
+    }
+}
+
+// Payloads that fail to deserialize are delivered as WebsocketEvent::Unknown, subscription
+// acknowledgements not at all
+fn pool_event(shard: usize, msg: &str) -> Option<PoolEvent> {
+    let (event, stream) = match parse_stream_message(msg, None) {
+        Ok(Some(parsed)) => parsed,
+        Ok(None) => return None,
+        Err(_) => match from_str::<Value>(msg) {
+            Ok(value) => (WebsocketEvent::Unknown(value), None),
+            Err(_) => return None,
+        },
+    };
+
+    Some(PoolEvent { shard, stream, event })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{TcpListener, TcpStream};
+    use std::io::ErrorKind as IoErrorKind;
+    use tungstenite::{accept_hdr, Message};
+    use tungstenite::handshake::server::Request;
+    use tungstenite::protocol::WebSocket;
+    use tungstenite::Error as TungsteniteError;
+
+    // Local stream server logging "<connection>: open|SUBSCRIBE|UNSUBSCRIBE <streams>" and
+    // "<connection>: closed". Connections naming a refused stream are dropped and turned away
+    struct Server {
+        base_url: String,
+        log: Arc<Mutex<Vec<String>>>,
+        refused: Arc<Mutex<HashSet<String>>>,
+    }
+
+    impl Server {
+        fn start() -> Server {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("ws://{}", listener.local_addr().unwrap());
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let refused = Arc::new(Mutex::new(HashSet::new()));
+            {
+                let log = log.clone();
+                let refused = refused.clone();
+                thread::spawn(move || {
+                    for (connection, stream) in listener.incoming().enumerate() {
+                        let (log, refused) = (log.clone(), refused.clone());
+                        thread::spawn(move || serve(connection, stream.unwrap(), log, refused));
+                    }
+                });
+            }
+
+            Server { base_url, log, refused }
+        }
+
+        fn options(&self, max_per_connection: usize) -> PoolOptions {
+            PoolOptions {
+                base_url: self.base_url.clone(),
+                max_per_connection,
+                reconnect_policy: ReconnectPolicy {
+                    max_retries: 1,
+                    base_delay: Duration::from_millis(10),
+                    max_delay: Duration::from_millis(10),
+                },
+                ..PoolOptions::default()
+            }
+        }
+
+        // The log once it has at least count lines
+        fn log(&self, count: usize) -> Vec<String> {
+            let started = Instant::now();
+            loop {
+                let log = self.log.lock().unwrap().clone();
+                if log.len() >= count || started.elapsed() > Duration::from_secs(5) {
+                    return log;
+                }
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+
+    fn serve(
+        connection: usize, stream: TcpStream, log: Arc<Mutex<Vec<String>>>, refused: Arc<Mutex<HashSet<String>>>,
+    ) {
+        let mut streams: Vec<String> = Vec::new();
+        let handshake = accept_hdr(stream, |request: &Request| {
+            let named = request.path.find('=').map_or("", |equals| &request.path[equals + 1..]);
+            streams = named.split('/').map(String::from).collect();
+            if streams.iter().any(|stream| refused.lock().unwrap().contains(stream)) {
+                return Err(TungsteniteError::Protocol("refused".into()));
+            }
+            Ok(None)
+        });
+        let mut socket: WebSocket<TcpStream> = match handshake {
+            Ok(socket) => socket,
+            Err(_) => return,
+        };
+        log.lock().unwrap().push(format!("{}: open {}", connection, streams.join(",")));
+        socket.get_ref().set_read_timeout(Some(Duration::from_millis(10))).unwrap();
+
+        loop {
+            if streams.iter().any(|stream| refused.lock().unwrap().contains(stream)) {
+                return;
+            }
+            let request: Value = match socket.read_message() {
+                Ok(Message::Text(request)) => from_str(&request).unwrap(),
+                Ok(_) => continue,
+                Err(TungsteniteError::Io(ref e))
+                    if e.kind() == IoErrorKind::WouldBlock || e.kind() == IoErrorKind::TimedOut =>
+                {
+                    continue;
+                }
+                Err(_) => break,
+            };
+            let params: Vec<&str> = request["params"].as_array().unwrap().iter().filter_map(Value::as_str).collect();
+            let method = request["method"].as_str().unwrap();
+            log.lock().unwrap().push(format!("{}: {} {}", connection, method, params.join(",")));
+            let _ = socket.write_message(Message::Text(format!(r#"{{"result":null,"id":{}}}"#, request["id"])));
+        }
+        log.lock().unwrap().push(format!("{}: closed", connection));
+    }
+
+    fn streams(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn subscribe_fills_shards_with_room_and_skips_known_streams() {
+        let server = Server::start();
+        let (mut pool, _events) =
+            WebSocketsPool::connect_with_options(&streams(&["a@trade"]), server.options(2)).unwrap();
+
+        pool.subscribe(&streams(&["b@trade", "a@trade", "c@trade"])).unwrap();
+        pool.subscribe(&streams(&["c@trade", "b@trade"])).unwrap();
+        assert_eq!(pool.streams(), streams(&["a@trade", "b@trade", "c@trade"]));
+        let shards: Vec<Vec<String>> = pool.shards().into_iter().map(|shard| shard.streams).collect();
+        assert_eq!(shards, vec![streams(&["a@trade", "b@trade"]), streams(&["c@trade"])]);
+
+        assert_eq!(server.log(3), vec!["0: open a@trade", "0: SUBSCRIBE b@trade", "1: open c@trade"]);
+        pool.stop().unwrap();
+    }
+
+    #[test]
+    fn unsubscribe_closes_emptied_shards() {
+        let server = Server::start();
+        let (mut pool, _events) = WebSocketsPool::connect_with_options(
+            &streams(&["a@trade", "b@trade", "c@trade"]),
+            server.options(2),
+        ).unwrap();
+
+        pool.unsubscribe(&streams(&["c@trade", "a@trade"])).unwrap();
+        let shards = pool.shards();
+        assert_eq!(shards.len(), 1);
+        assert_eq!((shards[0].id, shards[0].streams.clone()), (0, streams(&["b@trade"])));
+
+        let mut log = server.log(4);
+        log.sort();
+        assert_eq!(log, vec!["0: UNSUBSCRIBE a@trade", "0: open a@trade,b@trade", "1: closed", "1: open c@trade"]);
+        pool.stop().unwrap();
+    }
+
+    #[test]
+    fn restart_stopped_replaces_only_the_stopped_shards() {
+        let server = Server::start();
+        let (mut pool, _events) =
+            WebSocketsPool::connect_with_options(&streams(&["a@trade", "b@trade"]), server.options(1)).unwrap();
+        assert_eq!(pool.restart_stopped().unwrap(), 0);
+
+        // The connection of b drops and its reconnect is turned away
+        server.refused.lock().unwrap().insert("b@trade".into());
+        let started = Instant::now();
+        while pool.shards()[1].running && started.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!pool.shards()[1].running && pool.shards()[0].running);
+        assert!(pool.shards()[1].last_error.is_some());
+
+        server.refused.lock().unwrap().clear();
+        assert_eq!(pool.restart_stopped().unwrap(), 1);
+        let shards = pool.shards();
+        assert_eq!((shards[0].id, shards[0].reconnects), (0, 0));
+        assert_eq!((shards[1].id, shards[1].streams.clone(), shards[1].running), (2, streams(&["b@trade"]), true));
+        let opened = server.log(3).into_iter().filter(|line| line.contains("open")).count();
+        assert_eq!(opened, 3);
+        pool.stop().unwrap();
+    }
+}